@@ -1,24 +1,29 @@
 use crate::{
     components::{
+        compare_modal::CompareModal,
         help_modal::HelpModal,
         status_bar::StatusBar,
         tab::{PersistedTab, Tab},
         tab_bar::{PersistedTabBar, TabBar},
         Component,
     },
-    config::Config,
-    model::connection::{Connection, ConnectionManager},
+    config::{color_map::ColorKey, Config},
+    model::{
+        connection::{Connection, ConnectionManager},
+        launch::LaunchTarget,
+    },
     persistence::PersistedComponent,
     system::{
         command::{Command, CommandCategory, CommandGroup, CommandManager},
         event::Event,
-        message::{AppAction, Message},
+        message::{AppAction, Message, PrimScreenAction},
         signal::{Signal, SignalQueue},
     },
     utils::storage::{FileStorage, Storage},
 };
 use anyhow::Result;
-use crossterm::event::{Event as CrosstermEvent, KeyCode};
+use crossterm::event::{Event as CrosstermEvent, KeyCode, KeyModifiers};
+use mongodb::bson::Document;
 use ratatui::{backend::Backend, prelude::*};
 use serde::{Deserialize, Serialize};
 use std::{
@@ -33,6 +38,7 @@ enum Mode {
     Normal,
     Raw,
     HelpModal,
+    CompareModal,
 }
 
 #[derive(Debug)]
@@ -42,6 +48,7 @@ pub struct App<'a> {
     tab_bar: TabBar,
     status_bar: StatusBar,
     help_modal: HelpModal,
+    compare_modal: CompareModal,
 
     // shared data
     cursor_pos: Rc<Cell<(u16, u16)>>,
@@ -49,6 +56,9 @@ pub struct App<'a> {
     connection_manager: ConnectionManager,
     command_manager: CommandManager,
 
+    /// A document waiting to be compared with the next one that is marked.
+    marked_doc: Option<Document>,
+
     // config
     config: Config,
 
@@ -68,9 +78,11 @@ impl Default for App<'_> {
             tab_bar: TabBar::default(),
             status_bar: StatusBar::default(),
             help_modal: HelpModal::new(command_manager.clone(), config.clone()),
+            compare_modal: CompareModal::new(config.clone()),
             cursor_pos: Rc::new(Cell::new((0, 0))),
             connection_manager: ConnectionManager::new(vec![], storage.clone()),
             command_manager,
+            marked_doc: None,
             storage,
             config,
             mode: Mode::Normal,
@@ -104,18 +116,21 @@ impl App<'_> {
         let tab_bar = TabBar::new(selected_connection, config.clone());
         let status_bar = StatusBar::new(command_manager.clone(), config.clone());
         let help_modal = HelpModal::new(command_manager.clone(), config.clone());
+        let compare_modal = CompareModal::new(config.clone());
 
         Self {
             tabs: vec![tab],
             tab_bar,
             status_bar,
             help_modal,
+            compare_modal,
 
             config,
             cursor_pos,
             storage,
             connection_manager,
             command_manager,
+            marked_doc: None,
 
             mode: Mode::Normal,
             force_clear: false,
@@ -123,6 +138,16 @@ impl App<'_> {
         }
     }
 
+    /// Opens a database (and maybe a collection and query) in the current tab,
+    /// as if the user had selected them.
+    pub fn open_target(&mut self, target: LaunchTarget) {
+        let mut queue = SignalQueue::default();
+        queue.push(Message::to_prim_scr(PrimScreenAction::OpenTarget(
+            Box::new(target),
+        )));
+        self.process_signals(&mut queue);
+    }
+
     fn create_tab(&self) -> Tab<'static> {
         Tab::new(
             None,
@@ -236,14 +261,16 @@ impl Component for App<'_> {
                 CommandGroup::new(vec![Command::Quit], "quit")
                     .in_cat(CommandCategory::StatusBarOnly),
             ],
-            Mode::Raw => vec![],
+            Mode::Raw | Mode::CompareModal => vec![],
             Mode::HelpModal => {
                 vec![CommandGroup::new(vec![Command::Quit], "quit").in_cat(CommandCategory::AppNav)]
             }
         };
 
-        if matches!(self.mode, Mode::HelpModal) {
-            out.append(&mut self.help_modal.commands());
+        match self.mode {
+            Mode::HelpModal => out.append(&mut self.help_modal.commands()),
+            Mode::CompareModal => out.append(&mut self.compare_modal.commands()),
+            Mode::Normal | Mode::Raw => {}
         }
 
         out.append(&mut self.status_bar.commands());
@@ -261,6 +288,10 @@ impl Component for App<'_> {
             self.help_modal.handle_command(command, queue);
             return;
         }
+        if matches!(self.mode, Mode::CompareModal) {
+            self.compare_modal.handle_command(command, queue);
+            return;
+        }
 
         match command {
             Command::Quit => {
@@ -313,6 +344,21 @@ impl Component for App<'_> {
                     self.handle_command(&Command::Back, queue);
                     return;
                 }
+                // keys with a modifier aren't typed, so they can still be bound
+                // to commands (like testing a connection while editing it)
+                if key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+                {
+                    let command = self
+                        .config
+                        .key_map
+                        .command_for_key((*key).into(), &self.command_manager.groups());
+                    if let Some(command) = command {
+                        self.handle_command(&command, queue);
+                        return;
+                    }
+                }
                 let index = self.current_tab_idx();
                 if let Some(tab) = &mut self.tabs.get_mut(index) {
                     tab.handle_raw_event(event, queue);
@@ -350,6 +396,15 @@ impl Component for App<'_> {
             self.force_clear = true;
         }
 
+        // open referenced documents in a copy of the current tab, so that
+        // closing it goes back to where the reference was followed from
+        if let Event::ReferenceResolved(target) = event {
+            self.handle_command(&Command::DuplicateTab, queue);
+            queue.push(Message::to_prim_scr(PrimScreenAction::ShowReferencedDoc(
+                target.clone(),
+            )));
+        }
+
         self.tab_bar.handle_event(event, queue);
 
         let index = self.current_tab_idx();
@@ -364,11 +419,20 @@ impl Component for App<'_> {
     fn handle_message(&mut self, message: &Message, queue: &mut SignalQueue) {
         match message.read_as_app() {
             Some(AppAction::EnterRawMode) => self.mode = Mode::Raw,
-            Some(AppAction::ExitRawMode) => self.mode = Mode::Normal,
+            Some(AppAction::ExitRawMode | AppAction::CloseCompareModal) => self.mode = Mode::Normal,
             Some(AppAction::CloseHelpModal) => {
                 self.mode = Mode::Normal;
                 queue.push(Event::HelpModalToggled);
             }
+            Some(AppAction::MarkDocForComparison(doc)) => {
+                if let Some(marked_doc) = self.marked_doc.take() {
+                    self.compare_modal.show_with(marked_doc, doc.clone());
+                    self.mode = Mode::CompareModal;
+                } else {
+                    self.marked_doc = Some(doc.clone());
+                    queue.push(Event::DocMarkedForComparison);
+                }
+            }
             Some(AppAction::DoCommand(command)) => {
                 self.handle_command(command, queue);
             }
@@ -410,7 +474,24 @@ impl Component for App<'_> {
             tab.render(frame, main_area);
         }
 
-        // render status bar
+        // tint the tab's panel borders by the environment of its connection,
+        // so that it can be seen at a glance
+        if let Some(environment) = self.tab_bar.current_environment() {
+            let color_map = &self.config.color_map;
+            tint_borders(
+                frame.buffer_mut(),
+                main_area,
+                color_map.get(&ColorKey::PanelInactiveBorder),
+                color_map.get(&environment.into()),
+            );
+        }
+
+        // render status bar, with the health of the current tab's connection
+        if let Some(tab) = self.tabs.get(index) {
+            self.status_bar
+                .set_server_status(tab.server_status().clone());
+            self.status_bar.set_concerns(tab.concerns().clone());
+        }
         self.status_bar.render(frame, status_bar_area);
 
         // show the cursor if we're editing something, or the help modal if
@@ -423,6 +504,9 @@ impl Component for App<'_> {
             Mode::HelpModal => {
                 self.help_modal.render(frame, area);
             }
+            Mode::CompareModal => {
+                self.compare_modal.render(frame, main_area);
+            }
             Mode::Normal => {}
         }
     }
@@ -459,3 +543,22 @@ impl PersistedComponent for App<'_> {
         self.update_commands();
     }
 }
+
+/// Recolors the box-drawing characters in `area` that are drawn in `from`.
+fn tint_borders(buf: &mut Buffer, area: Rect, from: Color, to: Color) {
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let Some(cell) = buf.cell_mut((x, y)) else {
+                continue;
+            };
+            let is_border = cell
+                .symbol()
+                .chars()
+                .next()
+                .is_some_and(|c| ('\u{2500}'..='\u{257f}').contains(&c));
+            if is_border && cell.fg == from {
+                cell.fg = to;
+            }
+        }
+    }
+}
@@ -0,0 +1,326 @@
+use crate::{
+    client::client_options,
+    model::connection::Connection,
+    utils::{
+        edit_doc::parse_query_doc,
+        secrets::{decrypt, expand_placeholders, is_encrypted, needs_password},
+    },
+};
+use anyhow::{bail, Context, Result};
+use crossterm::event::{Event as CrosstermEvent, KeyCode, KeyEventKind, KeyModifiers};
+use futures::TryStreamExt;
+use mongodb::{
+    bson::{Bson, Document},
+    options::FindOptions,
+    Client as MongoClient,
+};
+use serde_json::Value;
+use std::io::{BufWriter, Write};
+
+/// How `tongo query` prints the documents it finds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// One document per line
+    #[default]
+    Ndjson,
+
+    /// An indented array of documents
+    Json,
+
+    /// One row per document, with a column for every field
+    Csv,
+}
+
+/// The arguments of `tongo query`.
+#[derive(Debug, Clone, clap::Args)]
+pub struct QueryArgs {
+    /// A (previously stored) connection to query
+    #[arg(long, short, conflicts_with = "url", required_unless_present = "url")]
+    connection: Option<String>,
+
+    /// A connection string to query
+    #[arg(long, short)]
+    url: Option<String>,
+
+    /// The database to query
+    #[arg(long)]
+    db: String,
+
+    /// The collection to query
+    #[arg(long)]
+    coll: String,
+
+    /// The filter, written like in the query input, eg. `{ age: { $gt: 30 } }`
+    #[arg(long, short, default_value = "{}")]
+    filter: String,
+
+    /// The projection, eg. `{ name: 1 }`
+    #[arg(long, short)]
+    projection: Option<String>,
+
+    /// The sort, eg. `{ createdAt: -1 }`
+    #[arg(long, short)]
+    sort: Option<String>,
+
+    /// The most documents to print
+    #[arg(long, short)]
+    limit: Option<u64>,
+
+    /// How to print the documents
+    #[arg(long, value_enum, default_value_t)]
+    format: OutputFormat,
+}
+
+impl QueryArgs {
+    /// The connection to query: either a stored one or one made from the
+    /// connection string.
+    fn connection(&self, stored_connections: &[Connection]) -> Result<Connection> {
+        match (&self.url, &self.connection) {
+            (Some(url), _) => Ok(Connection::new(
+                "Unnamed Connection".to_string(),
+                url.clone(),
+            )),
+            (_, Some(name)) => stored_connections
+                .iter()
+                .find(|c| c.name.to_lowercase() == name.to_lowercase())
+                .cloned()
+                .with_context(|| format!("There's no stored connection named \"{name}\"")),
+            _ => bail!("Either a connection or a connection string is needed"),
+        }
+    }
+
+    fn find_options(&self) -> Result<FindOptions> {
+        let parse = |text: &Option<String>, name: &str| {
+            text.as_deref()
+                .map(parse_query_doc)
+                .transpose()
+                .with_context(|| format!("Invalid {name}"))
+        };
+
+        #[expect(clippy::cast_possible_wrap)]
+        let options = FindOptions::builder()
+            .projection(parse(&self.projection, "projection")?)
+            .sort(parse(&self.sort, "sort")?)
+            .limit(self.limit.map(|limit| limit as i64))
+            .build();
+        Ok(options)
+    }
+}
+
+/// Connects with a connection (asking for its passphrase or password, if it
+/// needs one), runs the query, and prints the documents to stdout.
+///
+/// # Errors
+/// If the query can't be parsed, connecting fails, or the query fails.
+pub async fn run_query(args: QueryArgs, stored_connections: &[Connection]) -> Result<()> {
+    let conn = args.connection(stored_connections)?;
+    let filter = parse_query_doc(&args.filter).context("Invalid filter")?;
+    let options = args.find_options()?;
+
+    let mut conn_str = conn.connection_str.clone();
+    if is_encrypted(&conn_str) {
+        let passphrase = read_secret(&format!("Passphrase for {}: ", conn.name))?;
+        conn_str = decrypt(&conn_str, &passphrase)?;
+    }
+    let password = if needs_password(&conn_str) {
+        Some(read_secret(&format!("Password for {}: ", conn.name))?)
+    } else {
+        None
+    };
+    let url = expand_placeholders(&conn_str, password.as_deref())?;
+
+    // the tunnel closes when it's dropped, after the query is done
+    let (_tunnel, client_options) = client_options(url, conn.ssh_tunnel, conn.tls).await?;
+    let client = MongoClient::with_options(client_options)?;
+    let mut cursor = client
+        .database(&args.db)
+        .collection::<Document>(&args.coll)
+        .find(filter)
+        .with_options(options)
+        .await?;
+
+    let mut out = BufWriter::new(std::io::stdout());
+    if args.format == OutputFormat::Ndjson {
+        while let Some(doc) = cursor.try_next().await? {
+            writeln!(out, "{}", to_json(doc))?;
+        }
+    } else {
+        let docs = cursor.try_collect::<Vec<_>>().await?;
+        if args.format == OutputFormat::Json {
+            let docs = docs.into_iter().map(to_json).collect::<Vec<_>>();
+            writeln!(out, "{}", serde_json::to_string_pretty(&docs)?)?;
+        } else {
+            write!(out, "{}", to_csv(&docs))?;
+        }
+    }
+    out.flush()?;
+
+    Ok(())
+}
+
+fn to_json(doc: Document) -> Value {
+    Bson::Document(doc).into_relaxed_extjson()
+}
+
+/// Writes documents as CSV, with a column for each path to a value that isn't
+/// a subdocument (in the order they're first found). Arrays are written as
+/// JSON, and fields that a document doesn't have are left empty.
+fn to_csv(docs: &[Document]) -> String {
+    let rows = docs
+        .iter()
+        .map(|doc| {
+            let mut row = vec![];
+            flatten(doc, "", &mut row);
+            row
+        })
+        .collect::<Vec<_>>();
+
+    let mut columns: Vec<&str> = vec![];
+    for (path, _) in rows.iter().flatten() {
+        if !columns.contains(&path.as_str()) {
+            columns.push(path);
+        }
+    }
+
+    let mut csv = csv_line(columns.iter().copied());
+    for row in &rows {
+        csv.push_str(&csv_line(columns.iter().map(|column| {
+            row.iter()
+                .find(|(path, _)| path == column)
+                .map_or("", |(_, value)| value.as_str())
+        })));
+    }
+    csv
+}
+
+/// Collects the paths and values of the fields in a document, going into
+/// subdocuments.
+fn flatten(doc: &Document, prefix: &str, row: &mut Vec<(String, String)>) {
+    for (key, value) in doc {
+        let path = format!("{prefix}{key}");
+        match value {
+            Bson::Document(subdoc) => flatten(subdoc, &format!("{path}."), row),
+            Bson::String(s) => row.push((path, s.clone())),
+            Bson::Null => row.push((path, String::new())),
+            value => {
+                let value = match value.clone().into_relaxed_extjson() {
+                    Value::String(s) => s,
+                    json => json.to_string(),
+                };
+                row.push((path, value));
+            }
+        }
+    }
+}
+
+fn csv_line<'a>(fields: impl Iterator<Item = &'a str>) -> String {
+    let fields = fields
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect::<Vec<_>>();
+    format!("{}\n", fields.join(","))
+}
+
+/// Asks for a password or passphrase on the terminal, without showing what's
+/// typed.
+fn read_secret(prompt: &str) -> Result<String> {
+    eprint!("{prompt}");
+    crossterm::terminal::enable_raw_mode()?;
+    let secret = read_secret_keys();
+    crossterm::terminal::disable_raw_mode()?;
+    eprintln!();
+    secret
+}
+
+fn read_secret_keys() -> Result<String> {
+    let mut secret = String::new();
+    loop {
+        let CrosstermEvent::Key(key) = crossterm::event::read()? else {
+            continue;
+        };
+        if key.kind == KeyEventKind::Release {
+            continue;
+        }
+        match key.code {
+            KeyCode::Enter => return Ok(secret),
+            KeyCode::Esc => bail!("Cancelled"),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                bail!("Cancelled")
+            }
+            KeyCode::Char(c) => secret.push(c),
+            KeyCode::Backspace => {
+                secret.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[expect(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use mongodb::bson::doc;
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(flatten)]
+        query: QueryArgs,
+    }
+
+    #[test]
+    fn parse_query_args() {
+        let args = TestCli::try_parse_from([
+            "tongo",
+            "--connection",
+            "Local",
+            "--db",
+            "shop",
+            "--coll",
+            "orders",
+            "--sort",
+            "{ total: -1 }",
+            "--limit",
+            "5",
+            "--format",
+            "csv",
+        ])
+        .unwrap()
+        .query;
+
+        let stored = vec![Connection::new(
+            "local".into(),
+            "mongodb://localhost".into(),
+        )];
+        assert_eq!(args.connection(&stored).unwrap().name, "local");
+        assert_eq!(args.format, OutputFormat::Csv);
+
+        let options = args.find_options().unwrap();
+        assert_eq!(options.sort, Some(doc! { "total": -1_i64 }));
+        assert_eq!(options.limit, Some(5));
+        assert!(options.projection.is_none());
+
+        assert!(TestCli::try_parse_from(["tongo", "--db", "shop", "--coll", "orders"]).is_err());
+    }
+
+    #[test]
+    fn write_docs_as_csv() {
+        let docs = vec![
+            doc! { "_id": 1, "name": "Ann, Jr.", "address": { "city": "Oslo" } },
+            doc! { "_id": 2, "tags": ["a", "b"], "name": "Bo \"B\"" },
+        ];
+
+        assert_eq!(
+            to_csv(&docs),
+            "_id,name,address.city,tags\n\
+             1,\"Ann, Jr.\",Oslo,\n\
+             2,\"Bo \"\"B\"\"\",,\"[\"\"a\"\",\"\"b\"\"]\"\n"
+        );
+    }
+}
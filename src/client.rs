@@ -1,26 +1,56 @@
 use crate::{
-    components::{input::input_modal::InputKind, Component},
+    components::{confirm_modal::ConfirmKind, input::input_modal::InputKind, Component},
     config::Config,
-    model::{collection::Collection, database::Database},
+    model::{
+        collection::Collection,
+        concerns::Concerns,
+        conn_str::{split_unknown_options, ConnStrInfo},
+        connection::{Connection, SshTunnel, TlsSettings},
+        database::Database,
+        query_options::QueryOptions,
+        reference::{Reference, ReferenceTarget},
+        server::{ServerInfo, ServerStatus},
+    },
     persistence::PersistedComponent,
     system::{
+        command::{Command, CommandCategory, CommandGroup},
         event::Event,
-        message::{ClientAction, Message},
+        message::{ClientAction, Message, TabAction},
         signal::SignalQueue,
     },
+    utils::{
+        field_edit::remove_element_update,
+        secrets::{decrypt, expand_placeholders, is_encrypted, needs_password},
+    },
 };
+use audit_log::{AuditEntry, AuditLog};
 use futures::{Future, TryStreamExt};
+use journal::{Journal, WriteKind, WriteRecord};
 use mongodb::{
     bson::{doc, Bson, Document},
     error::Error as MongoError,
-    options::{ClientOptions, FindOptions},
+    options::{ClientOptions, FindOptions, UpdateModifications},
     Client as MongoClient, Collection as MongoCollection, Database as MongoDatabase,
 };
+use search::{
+    field_paths, first_match, search_filter, string_paths, SearchResult, MAX_SEARCH_RESULTS,
+    SCHEMA_SAMPLE_SIZE,
+};
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashSet,
-    sync::mpsc::{self, Receiver, Sender},
+    collections::{HashMap, HashSet},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    time::{Duration, Instant},
 };
+use tunnel::Tunnel;
+
+pub mod audit_log;
+pub mod journal;
+pub mod search;
+pub mod tunnel;
 
 /// The types of async queries that `Client` can do.
 #[derive(Debug, Hash, Eq, PartialEq)]
@@ -29,12 +59,26 @@ enum Operation {
     QueryCollections,
     QueryDatabases,
     Count,
+    SampleFields,
+    QueryIndexes,
     CreateCollection(String),
     DropCollection(String),
     CreateDatabase(String),
     DropDatabase(String),
+    QueryServerInfo,
+    Ping,
 }
 
+/// How often the server is pinged to measure latency and notice when the
+/// connection drops.
+const PING_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long a ping can take before the connection is considered lost.
+const PING_TIMEOUT: Duration = Duration::from_secs(4);
+
+/// How long testing a connection can take to find a server.
+const TEST_CONNECTION_TIMEOUT: Duration = Duration::from_secs(10);
+
 #[derive(Debug)]
 pub struct Client {
     #[expect(clippy::struct_field_names)]
@@ -46,6 +90,7 @@ pub struct Client {
     filter: Document,
     projection: Document,
     sort: Document,
+    options: Document,
     page: usize,
 
     response_send: Sender<Event>,
@@ -55,6 +100,135 @@ pub struct Client {
 
     /// Used to queue operations and avoid duplicate async calls.
     queued_ops: HashSet<Operation>,
+
+    /// Remembers recent document writes so that they can be undone.
+    journal: Journal,
+
+    /// Keeps a permanent record of every write.
+    audit_log: AuditLog,
+
+    /// The name of the connection that the client is connected to.
+    connection_name: Option<String>,
+
+    /// The connection that the client last connected with, kept so that it
+    /// can reconnect.
+    connection: Option<Connection>,
+
+    /// The server's details, latency, and whether it's still reachable.
+    server_status: ServerStatus,
+
+    /// When the server was last pinged.
+    last_ping: Option<Instant>,
+
+    /// A write from the audit log that the user has asked to revert, but
+    /// hasn't confirmed yet.
+    pending_revert: Option<WriteRecord>,
+
+    /// A connection that's waiting for the user to type in a password or
+    /// passphrase.
+    pending_conn: Option<PendingConnection>,
+
+    /// Whether writes are blocked, because the connection is read-only or the
+    /// app is in safe mode.
+    read_only: bool,
+
+    /// Whether everything on the connection is protected, so that dropping
+    /// anything means typing in its name.
+    protected: bool,
+
+    /// The read preference, read concern, and write concern that the tab's
+    /// queries and writes use.
+    concerns: Concerns,
+
+    /// The SSH tunnel that the client connects through, if any. Clones of the
+    /// client hold on to the same tunnel until they connect again, and it's
+    /// closed once none of them do.
+    tunnel: Option<Arc<Tunnel>>,
+}
+
+/// The parts of a connection that are needed to connect, while the secrets in
+/// its connection string are filled in.
+#[derive(Debug, Clone)]
+struct PendingConnection {
+    conn_str: String,
+    ssh_tunnel: Option<SshTunnel>,
+    tls: Option<TlsSettings>,
+    password: Option<String>,
+}
+
+/// Opens the SSH tunnel of a connection (if it has one), then parses its
+/// connection string (rewritten to go through the tunnel) and adds its TLS
+/// settings. The tunnel has to be kept open for as long as the client is used.
+///
+/// # Errors
+/// If the tunnel can't be opened or the connection string can't be parsed.
+pub async fn client_options(
+    url: String,
+    ssh_tunnel: Option<SshTunnel>,
+    tls: Option<TlsSettings>,
+) -> Result<(Option<Tunnel>, ClientOptions), MongoError> {
+    let (tunnel, url) = match ssh_tunnel {
+        Some(settings) => {
+            let (tunnel, url) = Tunnel::open(&settings, &url).await?;
+            (Some(tunnel), url)
+        }
+        None => (None, url),
+    };
+
+    let mut options = ClientOptions::parse(url).await?;
+    if let Some(tls) = tls {
+        tls.apply(&mut options);
+    }
+    Ok((tunnel, options))
+}
+
+/// The document that was stored by an insert, with the `_id` that the driver
+/// generated for it (if it didn't have one) first.
+fn with_id(doc: Document, id: Bson) -> Document {
+    if doc.contains_key("_id") {
+        return doc;
+    }
+    let mut with_id = doc! { "_id": id };
+    with_id.extend(doc);
+    with_id
+}
+
+/// The write that reverts a recorded one, and the filter for the document that
+/// it applies to.
+fn revert_plan(record: &WriteRecord) -> Result<(WriteRecord, Document), &'static str> {
+    let inverse = record
+        .inverse()
+        .ok_or("Dropped collections and databases can't be restored.")?;
+    let id = record
+        .doc_id()
+        .ok_or("The written document has no `_id`, so the write can't be undone.")?;
+    Ok((inverse, doc! { "_id": id.clone() }))
+}
+
+/// Everything needed to record a completed write from inside an async task.
+#[derive(Debug, Clone)]
+struct WriteRecorder {
+    journal: Journal,
+    audit_log: AuditLog,
+    connection_name: String,
+    sender: Sender<Event>,
+}
+
+impl WriteRecorder {
+    /// Records a write in the audit log, and also in the undo journal if
+    /// `undoable` is set.
+    fn record(&self, filter: Option<Document>, record: WriteRecord, undoable: bool) {
+        let entry = AuditEntry::now(self.connection_name.clone(), filter, record);
+        if let Err(err) = self.audit_log.append(&entry) {
+            let _ = self.sender.send(Event::ErrorOccurred(
+                format!("Could not write to the audit log: {err}").into(),
+            ));
+        }
+
+        if undoable {
+            self.journal.push(entry.record);
+        }
+    }
 }
 
 impl Default for Client {
@@ -67,11 +241,24 @@ impl Default for Client {
             filter: Document::default(),
             projection: Document::default(),
             sort: Document::default(),
+            options: Document::default(),
             page: 0,
             response_send,
             response_recv,
             config: Config::default(),
             queued_ops: HashSet::default(),
+            journal: Journal::default(),
+            audit_log: AuditLog::default(),
+            connection_name: None,
+            connection: None,
+            server_status: ServerStatus::default(),
+            last_ping: None,
+            pending_revert: None,
+            pending_conn: None,
+            read_only: false,
+            protected: false,
+            concerns: Concerns::default(),
+            tunnel: None,
         }
     }
 }
@@ -86,11 +273,24 @@ impl Clone for Client {
             filter: self.filter.clone(),
             projection: self.projection.clone(),
             sort: self.sort.clone(),
+            options: self.options.clone(),
             page: self.page,
             response_send,
             response_recv,
             config: self.config.clone(),
             queued_ops: HashSet::default(),
+            journal: Journal::default(),
+            audit_log: self.audit_log.clone(),
+            connection_name: self.connection_name.clone(),
+            connection: self.connection.clone(),
+            server_status: self.server_status.clone(),
+            last_ping: self.last_ping,
+            pending_revert: None,
+            pending_conn: None,
+            read_only: self.read_only,
+            protected: self.protected,
+            concerns: self.concerns.clone(),
+            tunnel: self.tunnel.clone(),
         }
     }
 }
@@ -99,10 +299,15 @@ impl Client {
     pub fn new(config: Config) -> Self {
         Self {
             config,
+            audit_log: AuditLog::in_data_dir(),
             ..Default::default()
         }
     }
 
+    pub const fn audit_log(&self) -> &AuditLog {
+        &self.audit_log
+    }
+
     /// Executes an asynchronous operation and sends the result through a channel.
     ///
     /// # Arguments
@@ -126,18 +331,238 @@ impl Client {
         });
     }
 
-    pub fn connect(&self, url: String) {
+    pub const fn server_status(&self) -> &ServerStatus {
+        &self.server_status
+    }
+
+    pub fn connect(&mut self, conn: &Connection) {
+        self.set_connection(conn);
+
+        self.pending_conn = Some(PendingConnection {
+            conn_str: conn.connection_str.clone(),
+            ssh_tunnel: conn.ssh_tunnel.clone(),
+            tls: conn.tls.clone(),
+            password: None,
+        });
+        self.continue_connecting();
+    }
+
+    /// Remembers a connection (or changes to it), and which writes and drops
+    /// it allows.
+    fn set_connection(&mut self, conn: &Connection) {
+        self.connection_name = Some(conn.name.clone());
+        self.connection = Some(conn.clone());
+        self.set_read_only(conn.read_only);
+        self.protected = self.config.protection.protects_conn(conn);
+    }
+
+    /// Connects again with the last connection, reopening its SSH tunnel (if
+    /// any) and creating a new Mongo client.
+    pub fn reconnect(&mut self) {
+        if let Some(conn) = self.connection.clone() {
+            self.mongo_client = None;
+            self.connect(&conn);
+        }
+    }
+
+    /// Blocks (or unblocks) writes, unless the whole app is in safe mode.
+    fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only || self.config.read_only;
+        let _ = self
+            .response_send
+            .send(Event::ReadOnlyModeChanged(self.read_only));
+    }
+
+    /// The name that the user has to type in to confirm dropping the current
+    /// database or collection, if it's protected.
+    pub fn protected_name(&self, kind: ConfirmKind) -> Option<String> {
+        let protection = &self.config.protection;
+        let db = self.db.as_ref()?;
+        match kind {
+            ConfirmKind::DropDatabase => {
+                (self.protected || protection.protects_db(&db.name)).then(|| db.name.clone())
+            }
+            ConfirmKind::DropCollection => {
+                let coll = self.coll.as_ref()?;
+                (self.protected || protection.protects_coll(&db.name, &coll.name))
+                    .then(|| coll.name.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether handling the event writes to the database.
+    const fn is_write(event: &Event) -> bool {
+        matches!(
+            event,
+            Event::InputConfirmed(InputKind::NewCollectionName | InputKind::NewDatabaseName, _)
+                | Event::ConfirmYes(Command::Undo)
+        )
+    }
+
+    /// Shows an error if writes are blocked, returning whether they are.
+    fn blocks_writes(&self, queue: &mut SignalQueue) -> bool {
+        if self.read_only {
+            queue.push(Event::ErrorOccurred(
+                "This connection is read-only, so it can't be written to.".into(),
+            ));
+        }
+        self.read_only
+    }
+
+    /// Fills in the password or passphrase that the pending connection needs,
+    /// then keeps connecting.
+    fn receive_secret(&mut self, kind: InputKind, secret: &str, queue: &mut SignalQueue) {
+        let Some(pending) = &mut self.pending_conn else {
+            return;
+        };
+
+        if matches!(kind, InputKind::Password) {
+            pending.password = Some(secret.to_string());
+        } else {
+            match decrypt(&pending.conn_str, secret) {
+                Ok(conn_str) => pending.conn_str = conn_str,
+                // the passphrase is asked for again
+                Err(err) => queue.push(Event::ErrorOccurred(err.to_string().into())),
+            }
+        }
+
+        self.continue_connecting();
+    }
+
+    /// Connects using the pending connection, unless the user needs to type
+    /// in a passphrase (to decrypt the connection string) or a password first.
+    fn continue_connecting(&mut self) {
+        let Some(pending) = self.pending_conn.take() else {
+            return;
+        };
+
+        let secret = if is_encrypted(&pending.conn_str) {
+            Some(InputKind::Passphrase)
+        } else if needs_password(&pending.conn_str) && pending.password.is_none() {
+            Some(InputKind::Password)
+        } else {
+            None
+        };
+        if let Some(kind) = secret {
+            self.pending_conn = Some(pending);
+            let _ = self.response_send.send(Event::SecretRequested(kind));
+            return;
+        }
+
+        let url = match expand_placeholders(&pending.conn_str, pending.password.as_deref()) {
+            Ok(url) => url,
+            Err(err) => {
+                let _ = self
+                    .response_send
+                    .send(Event::ErrorOccurred(err.to_string().into()));
+                return;
+            }
+        };
+        let ssh_tunnel = pending.ssh_tunnel;
+        let tls = pending.tls;
         self.exec(async move {
-            let options = ClientOptions::parse(url).await?;
+            let (tunnel, options) = client_options(url, ssh_tunnel, tls).await?;
             let client = MongoClient::with_options(options)?;
-            Ok(Event::ClientCreated(client))
+            Ok(Event::ClientCreated(client, tunnel.map(Arc::new)))
+        });
+    }
+
+    /// Parses a connection string in the background (resolving SRV records,
+    /// if it has any) so its options can be previewed. Placeholders are
+    /// filled in with a made-up password, since nothing connects with it.
+    fn inspect_conn_str(&self, conn_str: String) {
+        let url = if is_encrypted(&conn_str) {
+            Err("The connection string is encrypted, so it can't be previewed.".to_string())
+        } else {
+            expand_placeholders(&conn_str, Some("password")).map_err(|err| err.to_string())
+        };
+        let url = match url {
+            Ok(url) => url,
+            Err(err) => {
+                let _ = self.response_send.send(Event::ConnStrInspected {
+                    conn_str,
+                    result: Err(err),
+                });
+                return;
+            }
+        };
+
+        self.exec(async move {
+            let (url, unknown_options) = split_unknown_options(&url);
+            let result = ClientOptions::parse(url)
+                .await
+                .map(|options| ConnStrInfo::new(&options, unknown_options))
+                .map_err(|err| err.to_string());
+            Ok(Event::ConnStrInspected { conn_str, result })
         });
     }
 
+    /// Pings the server of a connection (through its SSH tunnel, if it has
+    /// one) without connecting to it.
+    fn test_connection(&self, conn: &Connection) {
+        if is_encrypted(&conn.connection_str) || needs_password(&conn.connection_str) {
+            let _ = self.response_send.send(Event::ErrorOccurred(
+                "Connections with a password prompt or an encrypted connection string can only be tested by connecting."
+                    .into(),
+            ));
+            return;
+        }
+        let url = match expand_placeholders(&conn.connection_str, None) {
+            Ok(url) => url,
+            Err(err) => {
+                let _ = self
+                    .response_send
+                    .send(Event::ErrorOccurred(err.to_string().into()));
+                return;
+            }
+        };
+
+        let ssh_tunnel = conn.ssh_tunnel.clone();
+        let tls = conn.tls.clone();
+        self.exec(async move {
+            let (tunnel, mut options) = client_options(url, ssh_tunnel, tls).await?;
+            options.server_selection_timeout = Some(TEST_CONNECTION_TIMEOUT);
+            let client = MongoClient::with_options(options)?;
+
+            let start = Instant::now();
+            client
+                .database("admin")
+                .run_command(doc! { "ping": 1 })
+                .await?;
+            let latency = start.elapsed();
+
+            drop(tunnel);
+            Ok(Event::ConnectionTested(latency))
+        });
+    }
+
+    pub const fn concerns(&self) -> &Concerns {
+        &self.concerns
+    }
+
+    /// Changes the tab's concerns to the ones written in the input, then runs
+    /// the query again with them.
+    fn set_concerns(&mut self, value: &str, queue: &mut SignalQueue) {
+        match value.parse() {
+            Ok(concerns) => {
+                self.concerns = concerns;
+                self.queue(Operation::Query(false));
+                self.queue(Operation::Count);
+            }
+            Err(err) => queue.push(Event::ErrorOccurred(err.to_string().into())),
+        }
+    }
+
+    /// Opens a database with the tab's concerns.
+    fn database(&self, client: &MongoClient, name: &str) -> MongoDatabase {
+        client.database_with_options(name, self.concerns.database_options())
+    }
+
     fn get_database(&self) -> Option<MongoDatabase> {
         let client = self.mongo_client.as_ref()?;
         let db_spec = self.db.as_ref()?;
-        Some(client.database(&db_spec.name))
+        Some(self.database(client, &db_spec.name))
     }
 
     fn get_collection<T>(&self) -> Option<MongoCollection<T>>
@@ -149,6 +574,23 @@ impl Client {
         Some(db.collection::<T>(&coll.name))
     }
 
+    /// The names of the currently-selected database and collection.
+    fn namespace(&self) -> Option<(String, String)> {
+        Some((
+            self.db.as_ref()?.name.clone(),
+            self.coll.as_ref()?.name.clone(),
+        ))
+    }
+
+    fn recorder(&self) -> WriteRecorder {
+        WriteRecorder {
+            journal: self.journal.clone(),
+            audit_log: self.audit_log.clone(),
+            connection_name: self.connection_name.clone().unwrap_or_default(),
+            sender: self.response_send.clone(),
+        }
+    }
+
     fn query_dbs(&self) -> Option<()> {
         let client = self.mongo_client.clone()?;
 
@@ -162,6 +604,95 @@ impl Client {
         Some(())
     }
 
+    fn query_server_info(&self) -> Option<()> {
+        let client = self.mongo_client.clone()?;
+
+        self.exec(async move {
+            let admin = client.database("admin");
+            let hello = admin.run_command(doc! { "hello": 1 }).await?;
+            let build_info = admin.run_command(doc! { "buildInfo": 1 }).await?;
+            Ok(Event::ServerInfoLoaded(ServerInfo::new(
+                &hello,
+                &build_info,
+            )))
+        });
+
+        Some(())
+    }
+
+    fn ping(&self) -> Option<()> {
+        let client = self.mongo_client.clone()?;
+
+        self.exec(async move {
+            let admin = client.database("admin");
+            let start = Instant::now();
+            let ping = admin.run_command(doc! { "ping": 1 });
+            let latency = match tokio::time::timeout(PING_TIMEOUT, ping).await {
+                Ok(Ok(_)) => Some(start.elapsed()),
+                _ => None,
+            };
+            Ok(Event::ServerPinged(latency))
+        });
+
+        Some(())
+    }
+
+    /// Keeps the server's status up to date, pinging it every so often.
+    fn handle_health_event(&mut self, event: &Event, queue: &mut SignalQueue) {
+        match event {
+            Event::ClientCreated(..) => {
+                self.server_status.info = None;
+                self.last_ping = Some(Instant::now());
+                self.queue(Operation::QueryServerInfo);
+                self.queue(Operation::Ping);
+            }
+            Event::Tick
+                if self.mongo_client.is_some()
+                    && self
+                        .last_ping
+                        .is_none_or(|last| last.elapsed() >= PING_INTERVAL) =>
+            {
+                self.last_ping = Some(Instant::now());
+                self.queue(Operation::Ping);
+            }
+            Event::ServerInfoLoaded(info) => self.server_status.info = Some(info.clone()),
+            Event::ServerPinged(latency) => self.handle_ping(*latency, queue),
+            _ => {}
+        }
+    }
+
+    /// Keeps the filter, projection, sort, and other options of the query in
+    /// sync with the query input, querying again when one of them changes.
+    fn handle_query_event(&mut self, event: &Event) {
+        let (part, doc) = match event {
+            Event::DocFilterUpdated(doc) => (&mut self.filter, doc),
+            Event::DocProjectionUpdated(doc) => (&mut self.projection, doc),
+            Event::DocSortUpdated(doc) => (&mut self.sort, doc),
+            Event::DocOptionsUpdated(doc) => (&mut self.options, doc),
+            _ => return,
+        };
+        part.clone_from(doc);
+        self.queue(Operation::Query(true));
+        self.queue(Operation::Count);
+    }
+
+    /// Keeps track of the latency of a ping, and of whether the connection
+    /// was lost or restored.
+    fn handle_ping(&mut self, latency: Option<Duration>, queue: &mut SignalQueue) {
+        let was_lost = self.server_status.lost;
+        self.server_status.latency = latency;
+        self.server_status.lost = latency.is_none();
+
+        if self.server_status.lost && !was_lost {
+            queue.push(Event::ConnectionLost);
+        } else if !self.server_status.lost && was_lost {
+            queue.push(Event::ConnectionRestored);
+
+            // the primary might have changed in the meantime
+            self.queue(Operation::QueryServerInfo);
+        }
+    }
+
     fn query_collections(&self) -> Option<()> {
         let db = self.get_database()?;
 
@@ -181,18 +712,30 @@ impl Client {
         let filter = self.filter.clone();
         let projection = self.projection.clone();
         let sort = self.sort.clone();
+        let query_options = self.query_options();
         let page_size = self.config.page_size;
         let skip = self.page * page_size;
+        let limit = query_options.page_limit(skip as u64, page_size as u64);
 
         #[expect(clippy::cast_possible_wrap)]
-        let options = FindOptions::builder()
+        let mut options = FindOptions::builder()
             .skip(skip as u64)
             .projection(projection)
             .sort(sort)
-            .limit(page_size as i64)
+            .limit(limit as i64)
             .build();
+        query_options.apply_to_find(&mut options);
 
         self.exec(async move {
+            // a limit of 0 means no limit, so pages past the hard limit have
+            // to be skipped here
+            if limit == 0 {
+                return Ok(Event::DocumentsUpdated {
+                    docs: vec![],
+                    reset_state,
+                });
+            }
+
             let cursor = coll.find(filter).with_options(options).await?;
             let docs = cursor.try_collect::<Vec<_>>().await?;
             Ok(Event::DocumentsUpdated { docs, reset_state })
@@ -201,52 +744,316 @@ impl Client {
         Some(())
     }
 
+    /// Searches the string fields of every document that matches the query,
+    /// rather than just the ones on the current page. The fields to search
+    /// are found by sampling the collection.
+    fn search_all(&self, text: String) -> Option<()> {
+        let coll = self.get_collection::<Document>()?;
+        let filter = self.filter.clone();
+        let sort = self.sort.clone();
+        let page_size = self.config.page_size;
+
+        self.exec(async move {
+            let sample_pipeline = vec![
+                doc! { "$match": filter.clone() },
+                doc! { "$sample": { "size": SCHEMA_SAMPLE_SIZE } },
+            ];
+            let sample = coll
+                .aggregate(sample_pipeline)
+                .await?
+                .try_collect::<Vec<_>>()
+                .await?;
+            let paths = string_paths(&sample);
+            if paths.is_empty() {
+                return Ok(Event::DocSearchAllComplete {
+                    text,
+                    results: vec![],
+                });
+            }
+
+            let options = FindOptions::builder()
+                .sort(sort.clone())
+                .limit(MAX_SEARCH_RESULTS)
+                .build();
+            let mut matches: HashMap<String, Document> = coll
+                .find(doc! { "$and": [filter.clone(), search_filter(&text, &paths)] })
+                .with_options(options)
+                .await?
+                .try_collect::<Vec<_>>()
+                .await?
+                .into_iter()
+                .filter_map(|doc| Some((doc.get("_id")?.to_string(), doc)))
+                .collect();
+
+            // find the page that each match is on by going through the ids of
+            // every document in the query's results, in order
+            let options = FindOptions::builder()
+                .sort(sort)
+                .projection(doc! { "_id": 1 })
+                .build();
+            let mut ids = coll.find(filter).with_options(options).await?;
+            let mut results = vec![];
+            let mut idx = 0;
+            while !matches.is_empty() {
+                let Some(doc) = ids.try_next().await? else {
+                    break;
+                };
+                let Some(id) = doc.get("_id") else {
+                    continue;
+                };
+
+                if let Some(matched) = matches.remove(&id.to_string()) {
+                    if let Some((path, value)) = first_match(&matched, &text) {
+                        results.push(SearchResult {
+                            page: idx / page_size,
+                            id: id.clone(),
+                            path,
+                            value,
+                        });
+                    }
+                }
+                idx += 1;
+            }
+
+            Ok(Event::DocSearchAllComplete { text, results })
+        });
+
+        Some(())
+    }
+
     fn count(&self) -> Option<()> {
         let coll = self.get_collection::<Bson>()?;
         let filter = self.filter.clone();
+        let options = self.query_options().count_options();
 
         self.exec(async move {
-            let count = coll.count_documents(filter).await?;
+            let count = coll.count_documents(filter).with_options(options).await?;
             Ok(Event::CountUpdated(count))
         });
 
         Some(())
     }
 
+    /// Finds the paths of the fields in a sample of the collection's documents,
+    /// to suggest while typing a query.
+    fn sample_fields(&self) -> Option<()> {
+        let coll = self.get_collection::<Document>()?;
+
+        self.exec(async move {
+            let pipeline = vec![doc! { "$sample": { "size": SCHEMA_SAMPLE_SIZE } }];
+            let sample = coll
+                .aggregate(pipeline)
+                .await?
+                .try_collect::<Vec<_>>()
+                .await?;
+            Ok(Event::FieldPathsSampled(
+                field_paths(&sample).into_iter().collect(),
+            ))
+        });
+
+        Some(())
+    }
+
+    /// Lists the names of the collection's indexes, to suggest as hints.
+    fn query_indexes(&self) -> Option<()> {
+        let coll = self.get_collection::<Document>()?;
+
+        self.exec(async move {
+            let names = coll.list_index_names().await?;
+            Ok(Event::IndexesListed(names))
+        });
+
+        Some(())
+    }
+
+    /// The query's options other than its filter, projection, and sort. The
+    /// input checks them before they're set, so they're only invalid if
+    /// they were saved by an older version.
+    fn query_options(&self) -> QueryOptions {
+        QueryOptions::try_from(&self.options).unwrap_or_default()
+    }
+
     fn insert_doc(&self, doc: Document) -> Option<()> {
         let coll = self.get_collection::<Document>()?;
+        let (db_name, coll_name) = self.namespace()?;
+        let recorder = self.recorder();
 
         self.exec(async move {
-            coll.insert_one(doc).await?;
+            let result = coll.insert_one(&doc).await?;
+            recorder.record(
+                None,
+                WriteRecord {
+                    db: db_name,
+                    coll: Some(coll_name),
+                    kind: WriteKind::Insert,
+                    before: None,
+                    after: Some(with_id(doc, result.inserted_id)),
+                },
+                true,
+            );
             Ok(Event::DocInsertComplete)
         });
 
         Some(())
     }
 
-    fn update_doc(&self, filter: Document, update: Document) -> Option<()> {
-        let coll = self.get_collection::<Bson>()?;
-        let update = doc! { "$set": update };
+    /// Applies an update (either with operators or a pipeline) to a single
+    /// document, like setting or removing one of its fields, without replacing
+    /// the whole document.
+    fn update_fields(&self, filter: Document, update: UpdateModifications) -> Option<()> {
+        let coll = self.get_collection::<Document>()?;
+        let (db_name, coll_name) = self.namespace()?;
+        let recorder = self.recorder();
 
         self.exec(async move {
-            coll.update_one(filter, update).await?;
+            let before = coll.find_one_and_update(filter.clone(), update).await?;
+            if let Some(before) = before {
+                let after = coll.find_one(filter.clone()).await?;
+                recorder.record(
+                    Some(filter),
+                    WriteRecord {
+                        db: db_name,
+                        coll: Some(coll_name),
+                        kind: WriteKind::Update,
+                        before: Some(before),
+                        after,
+                    },
+                    true,
+                );
+            }
             Ok(Event::DocUpdateComplete)
         });
 
         Some(())
     }
 
+    /// Finds the document that a reference points to. If the reference doesn't
+    /// say which collection the document is in, each collection in the
+    /// database is searched in turn.
+    fn follow_reference(&self, reference: Reference) -> Option<()> {
+        let client = self.mongo_client.clone()?;
+        let db_name = reference
+            .db
+            .or_else(|| self.db.as_ref().map(|db| db.name.clone()))?;
+        let db = self.database(&client, &db_name);
+
+        self.exec(async move {
+            let colls: Vec<Collection> = db
+                .list_collections()
+                .await?
+                .try_collect::<Vec<_>>()
+                .await?
+                .into_iter()
+                .map(Collection::from)
+                .collect();
+
+            let candidates = reference.coll.map_or_else(
+                || colls.iter().map(|coll| coll.name.clone()).collect(),
+                |coll| vec![coll],
+            );
+
+            let filter = doc! { reference.field: reference.value };
+            for coll_name in candidates {
+                let coll = db.collection::<Document>(&coll_name);
+                if coll.find_one(filter.clone()).await?.is_some() {
+                    return Ok(Event::ReferenceResolved(Box::new(ReferenceTarget {
+                        db: Database::new(db_name),
+                        coll: Collection::new(coll_name),
+                        colls,
+                        filter,
+                    })));
+                }
+            }
+
+            Ok(Event::ErrorOccurred(
+                format!("Could not find a document matching {filter}").into(),
+            ))
+        });
+
+        Some(())
+    }
+
     fn delete_doc(&self, filter: Document) -> Option<()> {
         let coll = self.get_collection::<Document>()?;
+        let (db_name, coll_name) = self.namespace()?;
+        let recorder = self.recorder();
 
         self.exec(async move {
-            coll.delete_one(filter).await?;
+            let before = coll.find_one_and_delete(filter.clone()).await?;
+            if let Some(before) = before {
+                recorder.record(
+                    Some(filter),
+                    WriteRecord {
+                        db: db_name,
+                        coll: Some(coll_name),
+                        kind: WriteKind::Delete,
+                        before: Some(before),
+                        after: None,
+                    },
+                    true,
+                );
+            }
             Ok(Event::DocDeleteComplete)
         });
 
         Some(())
     }
 
+    /// Reverts a previously-recorded write by applying its inverse. A write
+    /// from the undo journal is only taken out of it once the revert has
+    /// started, and goes back in if reverting fails, so the user can try again.
+    fn revert_write(&self, record: WriteRecord, from_journal: bool, queue: &mut SignalQueue) {
+        let (inverse, filter) = match revert_plan(&record) {
+            Ok(plan) => plan,
+            Err(err) => {
+                queue.push(Event::ErrorOccurred(err.into()));
+                return;
+            }
+        };
+        let (Some(client), Some(coll_name)) = (&self.mongo_client, &inverse.coll) else {
+            queue.push(Event::ErrorOccurred(
+                "Can't undo the write without a connection.".into(),
+            ));
+            return;
+        };
+        let coll = self
+            .database(client, &inverse.db)
+            .collection::<Document>(coll_name);
+        let recorder = self.recorder();
+
+        if from_journal {
+            self.journal.pop();
+        }
+
+        self.exec(async move {
+            let result = match (inverse.kind, &inverse.after) {
+                (WriteKind::Delete, _) => coll.delete_one(filter.clone()).await.map(|_| ()),
+                (WriteKind::Update, Some(after)) => {
+                    coll.replace_one(filter.clone(), after).await.map(|_| ())
+                }
+                (WriteKind::Insert, Some(after)) => coll.insert_one(after).await.map(|_| ()),
+                _ => Ok(()),
+            };
+
+            if let Err(err) = result {
+                if from_journal {
+                    recorder.journal.push(record);
+                }
+                return Err(err);
+            }
+
+            let kind = record.kind;
+            let filter = (inverse.kind != WriteKind::Insert).then_some(filter);
+            recorder.record(filter, inverse, false);
+
+            if from_journal {
+                Ok(Event::WriteUndone(kind))
+            } else {
+                Ok(Event::WriteReverted(kind))
+            }
+        });
+    }
+
     fn drop_coll(&self, coll_name: &str) -> Option<()> {
         let db = self.get_database()?;
         let coll = db.collection::<Document>(coll_name);
@@ -254,9 +1061,18 @@ impl Client {
             .coll
             .as_ref()
             .is_some_and(|coll| coll.name == *coll_name);
+        let record = WriteRecord {
+            db: db.name().to_string(),
+            coll: Some(coll_name.to_string()),
+            kind: WriteKind::DropCollection,
+            before: None,
+            after: None,
+        };
+        let recorder = self.recorder();
 
         self.exec(async move {
             coll.drop().await?;
+            recorder.record(None, record, false);
             Ok(Event::CollectionDropConfirmed(dropping_selected_coll))
         });
 
@@ -277,9 +1093,18 @@ impl Client {
     fn drop_db(&self, db_name: &str) -> Option<()> {
         let db = self.get_database()?;
         let dropping_selected_db = self.db.as_ref().is_some_and(|db| db.name == *db_name);
+        let record = WriteRecord {
+            db: db.name().to_string(),
+            coll: None,
+            kind: WriteKind::DropDatabase,
+            before: None,
+            after: None,
+        };
+        let recorder = self.recorder();
 
         self.exec(async move {
             db.drop().await?;
+            recorder.record(None, record, false);
             Ok(Event::DatabaseDropConfirmed(dropping_selected_db))
         });
 
@@ -287,8 +1112,8 @@ impl Client {
     }
 
     fn create_db(&self, db_name: &str) -> Option<()> {
-        let client = self.mongo_client.clone()?;
-        let db = client.database(db_name);
+        let client = self.mongo_client.as_ref()?;
+        let db = self.database(client, db_name);
 
         self.exec(async move {
             // HACK: the only way to create a db on the server is to create a collection
@@ -313,10 +1138,14 @@ impl Client {
                 Operation::QueryCollections => self.query_collections(),
                 Operation::QueryDatabases => self.query_dbs(),
                 Operation::Count => self.count(),
+                Operation::SampleFields => self.sample_fields(),
+                Operation::QueryIndexes => self.query_indexes(),
                 Operation::CreateCollection(coll_name) => self.create_coll(coll_name.clone()),
                 Operation::DropCollection(coll_name) => self.drop_coll(coll_name),
                 Operation::CreateDatabase(db_name) => self.create_db(db_name),
                 Operation::DropDatabase(db_name) => self.drop_db(db_name),
+                Operation::QueryServerInfo => self.query_server_info(),
+                Operation::Ping => self.ping(),
             };
         }
         self.queued_ops = HashSet::default();
@@ -324,19 +1153,45 @@ impl Client {
 }
 
 impl Component for Client {
+    fn commands(&self) -> Vec<CommandGroup> {
+        if self.server_status.lost {
+            vec![CommandGroup::new(vec![Command::Reconnect], "reconnect")
+                .in_cat(CommandCategory::StatusBarOnly)]
+        } else {
+            vec![]
+        }
+    }
+
+    fn handle_command(&mut self, command: &Command, _queue: &mut SignalQueue) {
+        if matches!(command, Command::Reconnect) {
+            self.reconnect();
+        }
+    }
+
     fn handle_event(&mut self, event: &Event, queue: &mut SignalQueue) {
         // check for completed async operations
         while let Ok(content) = self.response_recv.try_recv() {
             queue.push(content);
         }
 
+        if Self::is_write(event) && self.blocks_writes(queue) {
+            return;
+        }
+
+        self.handle_health_event(event, queue);
+        self.handle_query_event(event);
+
         // handle the event as normal
         match event {
-            Event::ConnectionSelected(conn) => {
-                self.connect(conn.connection_str.clone());
+            Event::ConnectionSelected(conn) => self.connect(conn),
+            Event::ConnectionUpdated(conn) if self.connection_name.as_ref() == Some(&conn.name) => {
+                self.set_connection(conn);
             }
-            Event::ClientCreated(client) => {
+            Event::ClientCreated(client, tunnel) => {
                 self.mongo_client = Some(client.clone());
+                // this drops the client's hold on the tunnel of its previous
+                // connection, which other tabs might still be using
+                self.tunnel.clone_from(tunnel);
 
                 // TODO: should we query everything? if we're missing conn/db/coll
                 // then it just won't run, and if we just hydrated data we want to
@@ -345,6 +1200,8 @@ impl Component for Client {
                 self.queue(Operation::QueryDatabases);
                 self.queue(Operation::QueryCollections);
                 self.queue(Operation::Count);
+                self.queue(Operation::SampleFields);
+                self.queue(Operation::QueryIndexes);
             }
             Event::DatabaseHighlighted(db) => {
                 self.db = Some(db.clone());
@@ -357,28 +1214,18 @@ impl Component for Client {
                 self.coll = Some(coll.clone());
                 self.queue(Operation::Query(true));
                 self.queue(Operation::Count);
+                self.queue(Operation::SampleFields);
+                self.queue(Operation::QueryIndexes);
             }
             Event::DocumentPageChanged(page) => {
                 self.page = *page;
                 self.queue(Operation::Query(true));
             }
-            Event::DocFilterUpdated(doc) => {
-                self.filter.clone_from(doc);
-                self.queue(Operation::Query(true));
-                self.queue(Operation::Count);
-            }
-            Event::DocProjectionUpdated(doc) => {
-                self.projection.clone_from(doc);
-                self.queue(Operation::Query(true));
-                self.queue(Operation::Count);
-            }
-            Event::DocSortUpdated(doc) => {
-                self.sort.clone_from(doc);
-                self.queue(Operation::Query(true));
-                self.queue(Operation::Count);
-            }
             Event::DocUpdateComplete => self.queue(Operation::Query(false)),
-            Event::DocInsertComplete | Event::DocDeleteComplete => {
+            Event::DocInsertComplete
+            | Event::DocDeleteComplete
+            | Event::WriteReverted(_)
+            | Event::WriteUndone(_) => {
                 self.queue(Operation::Count);
                 self.queue(Operation::Query(false));
             }
@@ -407,27 +1254,48 @@ impl Component for Client {
             Event::InputConfirmed(InputKind::NewDatabaseName, coll_name) => {
                 self.queue(Operation::CreateDatabase(coll_name.to_string()));
             }
+            Event::ConfirmYes(Command::Undo) => {
+                if let Some(record) = self.pending_revert.take() {
+                    self.revert_write(record, false, queue);
+                } else if let Some(record) = self.journal.last() {
+                    self.revert_write(record, true, queue);
+                }
+            }
+            Event::ConfirmNo => self.pending_revert = None,
+            Event::InputConfirmed(kind @ (InputKind::Passphrase | InputKind::Password), secret) => {
+                self.receive_secret(*kind, secret, queue);
+            }
+            Event::InputConfirmed(InputKind::Concerns, value) => self.set_concerns(value, queue),
+            Event::InputCanceled => self.pending_conn = None,
             _ => (),
         }
     }
 
     fn handle_message(&mut self, message: &Message, queue: &mut SignalQueue) {
+        if message.read_as_client().is_some_and(ClientAction::is_write) && self.blocks_writes(queue)
+        {
+            return;
+        }
+
         match message.read_as_client() {
-            Some(ClientAction::Connect(conn)) => self.connect(conn.connection_str.clone()),
+            Some(ClientAction::Connect(conn)) => self.connect(conn),
             Some(ClientAction::DropDatabase(db)) => {
                 self.queue(Operation::DropDatabase(db.name.clone()));
             }
             Some(ClientAction::DropCollection(db)) => {
                 self.queue(Operation::DropCollection(db.name.clone()));
             }
-            Some(ClientAction::UpdateDoc(doc)) => {
-                if let Some(id) = doc.get("_id") {
-                    self.update_doc(doc! { "_id": id }, doc.clone());
-                } else {
-                    queue.push(Event::ErrorOccurred(
-                        "Document does not have an `_id` field.".into(),
-                    ));
-                }
+            Some(ClientAction::UpdateFields { id, update }) => {
+                self.update_fields(doc! { "_id": id }, update.clone().into());
+            }
+            Some(ClientAction::RemoveElement { id, path, idx }) => {
+                self.update_fields(doc! { "_id": id }, remove_element_update(path, *idx).into());
+            }
+            Some(ClientAction::FollowReference(reference)) => {
+                self.follow_reference(reference.clone());
+            }
+            Some(ClientAction::SearchAll(text)) => {
+                self.search_all(text.clone());
             }
             Some(ClientAction::InsertDoc(doc)) => {
                 self.insert_doc(doc.clone());
@@ -441,10 +1309,43 @@ impl Component for Client {
                     ));
                 }
             }
+            Some(ClientAction::InspectConnStr(conn_str)) => {
+                self.inspect_conn_str(conn_str.clone());
+            }
+            Some(ClientAction::TestConnection(conn)) => self.test_connection(conn),
             Some(ClientAction::RefreshQueries) => {
                 self.queue(Operation::Count);
                 self.queue(Operation::Query(false));
             }
+            Some(ClientAction::RevertWrite(entry)) => {
+                if self.connection_name.as_ref() != Some(&entry.connection) {
+                    queue.push(Event::ErrorOccurred(
+                        format!(
+                            "This write was made on connection \"{}\". Connect to it to revert the write.",
+                            entry.connection
+                        )
+                        .into(),
+                    ));
+                } else if entry.record.inverse().is_none() {
+                    queue.push(Event::ErrorOccurred(
+                        format!("A {} cannot be reverted.", entry.record.kind).into(),
+                    ));
+                } else {
+                    self.pending_revert = Some(entry.record.clone());
+                    queue.push(Message::to_tab(TabAction::RequestConfirmation(
+                        ConfirmKind::RevertWrite(entry.record.kind),
+                    )));
+                }
+            }
+            Some(ClientAction::UndoLastWrite) => {
+                if let Some(record) = self.journal.last() {
+                    queue.push(Message::to_tab(TabAction::RequestConfirmation(
+                        ConfirmKind::UndoWrite(record.kind),
+                    )));
+                } else {
+                    queue.push(Event::ErrorOccurred("There are no writes to undo.".into()));
+                }
+            }
             None => {}
         }
     }
@@ -457,7 +1358,12 @@ pub struct PersistedClient {
     filter: Document,
     projection: Document,
     sort: Document,
+    #[serde(default)]
+    options: Document,
     page: usize,
+
+    #[serde(default)]
+    concerns: Concerns,
 }
 
 impl PersistedComponent for Client {
@@ -470,7 +1376,9 @@ impl PersistedComponent for Client {
             filter: self.filter.clone(),
             projection: self.projection.clone(),
             sort: self.sort.clone(),
+            options: self.options.clone(),
             page: self.page,
+            concerns: self.concerns.clone(),
         }
     }
 
@@ -480,6 +1388,107 @@ impl PersistedComponent for Client {
         self.filter = storage.filter;
         self.projection = storage.projection;
         self.sort = storage.sort;
+        self.options = storage.options;
         self.page = storage.page;
+        self.concerns = storage.concerns;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{model::concerns::ReadMode, testing::ComponentTestHarness};
+
+    #[test]
+    fn block_writes_when_read_only() {
+        let mut test = ComponentTestHarness::new(Client {
+            read_only: true,
+            ..Default::default()
+        });
+
+        test.given_message(Message::to_client(ClientAction::InsertDoc(doc! { "a": 1 })));
+        test.expect_event(|e| matches!(e, Event::ErrorOccurred(..)));
+
+        test.given_event(Event::InputConfirmed(
+            InputKind::NewDatabaseName,
+            "db".to_string(),
+        ));
+        assert!(test.component().queued_ops.is_empty());
+    }
+
+    #[test]
+    fn undo_insert_without_id() {
+        let inserted = with_id(doc! { "a": 1 }, Bson::Int32(7));
+        assert_eq!(inserted, doc! { "_id": 7, "a": 1 });
+
+        let record = WriteRecord {
+            db: "db".to_string(),
+            coll: Some("coll".to_string()),
+            kind: WriteKind::Insert,
+            before: None,
+            after: Some(inserted),
+        };
+        let (inverse, filter) = revert_plan(&record).expect("should be revertible");
+        assert_eq!(inverse.kind, WriteKind::Delete);
+        assert_eq!(filter, doc! { "_id": 7 });
+
+        // a write that can't be undone is reported, and stays in the journal
+        let mut test = ComponentTestHarness::new(Client::default());
+        test.component().journal.push(WriteRecord {
+            after: Some(doc! { "a": 1 }),
+            ..record
+        });
+        test.given_event(Event::ConfirmYes(Command::Undo));
+        test.expect_event(|e| matches!(e, Event::ErrorOccurred(..)));
+        assert!(test.component().journal.last().is_some());
+    }
+
+    #[test]
+    fn notice_lost_connection() {
+        let mut test = ComponentTestHarness::new(Client::default());
+
+        test.given_event(Event::ServerPinged(None));
+        test.expect_event(|e| matches!(e, Event::ConnectionLost));
+        assert!(test.component().server_status().lost);
+
+        test.given_event(Event::ServerPinged(Some(Duration::from_millis(12))));
+        test.expect_event(|e| matches!(e, Event::ConnectionRestored));
+        assert!(!test.component().server_status().lost);
+        assert_eq!(
+            test.component().server_status().latency,
+            Some(Duration::from_millis(12))
+        );
+    }
+    #[test]
+    fn set_concerns_and_query_again() {
+        let mut test = ComponentTestHarness::new(Client::default());
+
+        test.given_event(Event::InputConfirmed(
+            InputKind::Concerns,
+            "readPreference=nearest&w=majority".to_string(),
+        ));
+        assert_eq!(
+            test.component().concerns().to_string(),
+            "readPreference=nearest&w=majority"
+        );
+        assert!(test
+            .component()
+            .queued_ops
+            .contains(&Operation::Query(false)));
+
+        test.given_event(Event::InputConfirmed(
+            InputKind::Concerns,
+            "readPreference=fastest".to_string(),
+        ));
+        test.expect_event(|e| matches!(e, Event::ErrorOccurred(..)));
+        assert_eq!(
+            test.component().concerns().read_preference,
+            Some(ReadMode::Nearest)
+        );
+
+        let persisted = test.component().persist();
+        let mut hydrated = Client::default();
+        hydrated.hydrate(persisted);
+        assert_eq!(hydrated.concerns(), test.component().concerns());
     }
 }
@@ -0,0 +1,163 @@
+use super::journal::{canonical_ext_json, WriteRecord};
+use crate::utils::storage::get_app_data_path;
+use anyhow::Result;
+use mongodb::bson::{DateTime, Document};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+};
+
+const AUDIT_LOG_FILE_NAME: &str = "audit-log.ndjson";
+
+/// A single line of the audit log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// When the write was completed, in RFC 3339 format.
+    pub timestamp: String,
+
+    /// The name of the connection that the write was made on.
+    pub connection: String,
+
+    /// The filter used to find the document that was written, if any.
+    #[serde(with = "canonical_ext_json")]
+    pub filter: Option<Document>,
+
+    #[serde(flatten)]
+    pub record: WriteRecord,
+}
+
+impl AuditEntry {
+    pub fn now(connection: String, filter: Option<Document>, record: WriteRecord) -> Self {
+        Self {
+            timestamp: DateTime::now().try_to_rfc3339_string().unwrap_or_default(),
+            connection,
+            filter,
+            record,
+        }
+    }
+}
+
+/// An append-only record of every write made through tongo, stored as
+/// newline-delimited JSON in the app's data directory.
+#[derive(Debug, Clone, Default)]
+pub struct AuditLog {
+    path: Option<PathBuf>,
+}
+
+impl AuditLog {
+    pub fn in_data_dir() -> Self {
+        Self {
+            path: get_app_data_path()
+                .ok()
+                .map(|dir| dir.join(AUDIT_LOG_FILE_NAME)),
+        }
+    }
+
+    /// # Errors
+    /// If something goes wrong while writing to the log file.
+    pub fn append(&self, entry: &AuditEntry) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        Ok(())
+    }
+
+    /// Reads every entry in the log, oldest first, along with how many lines
+    /// were skipped because they couldn't be parsed (like one that was cut off
+    /// by a crash while it was being written).
+    ///
+    /// # Errors
+    /// If something goes wrong while reading the log file.
+    pub fn read_entries(&self) -> Result<(Vec<AuditEntry>, usize)> {
+        let Some(path) = self.path.as_ref().filter(|path| path.exists()) else {
+            return Ok((vec![], 0));
+        };
+
+        let mut entries = vec![];
+        let mut skipped = 0;
+        for line in fs::read_to_string(path)?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+        {
+            match serde_json::from_str(line) {
+                Ok(entry) => entries.push(entry),
+                Err(_) => skipped += 1,
+            }
+        }
+        Ok((entries, skipped))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::journal::WriteKind;
+    use mongodb::bson::doc;
+
+    #[test]
+    fn appended_entries_can_be_read_back() {
+        let path =
+            std::env::temp_dir().join(format!("tongo-audit-{}.ndjson", uuid::Uuid::new_v4()));
+        let log = AuditLog {
+            path: Some(path.clone()),
+        };
+
+        let entry = AuditEntry::now(
+            "local".to_string(),
+            Some(doc! { "_id": 1 }),
+            WriteRecord {
+                db: "db".to_string(),
+                coll: Some("coll".to_string()),
+                kind: WriteKind::Delete,
+                before: Some(doc! { "_id": 1, "a": "b" }),
+                after: None,
+            },
+        );
+        log.append(&entry).expect("should append");
+        log.append(&entry).expect("should append");
+
+        let entries = log.read_entries().expect("should read");
+        fs::remove_file(path).expect("should clean up");
+
+        assert_eq!(entries, (vec![entry.clone(), entry], 0));
+    }
+
+    #[test]
+    fn skip_corrupt_lines() {
+        let path =
+            std::env::temp_dir().join(format!("tongo-audit-{}.ndjson", uuid::Uuid::new_v4()));
+        let log = AuditLog {
+            path: Some(path.clone()),
+        };
+
+        let entry = AuditEntry::now(
+            "local".to_string(),
+            None,
+            WriteRecord {
+                db: "db".to_string(),
+                coll: Some("coll".to_string()),
+                kind: WriteKind::Insert,
+                before: None,
+                after: Some(doc! { "_id": 1 }),
+            },
+        );
+        log.append(&entry).expect("should append");
+        // a line that was cut off partway through
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .expect("should open");
+        writeln!(file, "{{\"connection\":\"loc").expect("should write");
+        log.append(&entry).expect("should append");
+
+        let entries = log.read_entries().expect("should read");
+        fs::remove_file(path).expect("should clean up");
+
+        assert_eq!(entries, (vec![entry.clone(), entry], 1));
+    }
+}
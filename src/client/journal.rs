@@ -0,0 +1,223 @@
+use mongodb::bson::{Bson, Document};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+/// How many writes are remembered for undoing.
+const JOURNAL_CAPACITY: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, strum_macros::Display)]
+pub enum WriteKind {
+    #[strum(to_string = "insert")]
+    Insert,
+    #[strum(to_string = "update")]
+    Update,
+    #[strum(to_string = "delete")]
+    Delete,
+    #[strum(to_string = "collection drop")]
+    DropCollection,
+    #[strum(to_string = "database drop")]
+    DropDatabase,
+}
+
+/// A write that `Client` has completed, together with the images of the
+/// document before and after the write.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WriteRecord {
+    pub db: String,
+    /// Not set when a whole database was dropped.
+    pub coll: Option<String>,
+    pub kind: WriteKind,
+    #[serde(with = "canonical_ext_json")]
+    pub before: Option<Document>,
+    #[serde(with = "canonical_ext_json")]
+    pub after: Option<Document>,
+}
+
+impl WriteRecord {
+    /// The namespace that was written to, eg. `db.coll`.
+    #[must_use]
+    pub fn namespace(&self) -> String {
+        self.coll
+            .as_ref()
+            .map_or_else(|| self.db.clone(), |coll| format!("{}.{coll}", self.db))
+    }
+
+    /// The write that reverts this one, if there is one. Drops can't be
+    /// reverted since we don't keep a copy of what was dropped.
+    #[must_use]
+    pub fn inverse(&self) -> Option<Self> {
+        let kind = match self.kind {
+            WriteKind::Insert => WriteKind::Delete,
+            WriteKind::Update => WriteKind::Update,
+            WriteKind::Delete => WriteKind::Insert,
+            WriteKind::DropCollection | WriteKind::DropDatabase => return None,
+        };
+
+        Some(Self {
+            db: self.db.clone(),
+            coll: self.coll.clone(),
+            kind,
+            before: self.after.clone(),
+            after: self.before.clone(),
+        })
+    }
+
+    /// The `_id` of the document that was written.
+    #[must_use]
+    pub fn doc_id(&self) -> Option<&Bson> {
+        self.after
+            .as_ref()
+            .or(self.before.as_ref())
+            .and_then(|doc| doc.get("_id"))
+    }
+}
+
+/// Serializes documents as canonical extended JSON, so that BSON types survive
+/// a round trip through a JSON file.
+pub(super) mod canonical_ext_json {
+    use super::{Bson, Deserialize, Deserializer, Document, Serialize, Serializer};
+    use serde::de::Error;
+
+    #[expect(clippy::ref_option)]
+    pub fn serialize<S: Serializer>(doc: &Option<Document>, ser: S) -> Result<S::Ok, S::Error> {
+        doc.clone()
+            .map(|doc| Bson::Document(doc).into_canonical_extjson())
+            .serialize(ser)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(de: D) -> Result<Option<Document>, D::Error> {
+        Option::<serde_json::Value>::deserialize(de)?
+            .map(|value| match Bson::try_from(value) {
+                Ok(Bson::Document(doc)) => Ok(doc),
+                Ok(_) => Err(D::Error::custom("expected a document")),
+                Err(err) => Err(D::Error::custom(err)),
+            })
+            .transpose()
+    }
+}
+
+/// A bounded, in-memory record of the writes made by a `Client`. Shared
+/// between the client and the async tasks it spawns, so that writes are only
+/// recorded once the server has confirmed them.
+#[derive(Debug, Clone, Default)]
+pub struct Journal {
+    records: Arc<Mutex<VecDeque<WriteRecord>>>,
+}
+
+impl Journal {
+    /// # Panics
+    /// If the journal's lock has been poisoned.
+    pub fn push(&self, record: WriteRecord) {
+        let mut records = self
+            .records
+            .lock()
+            .expect("journal lock should not be poisoned");
+        if records.len() == JOURNAL_CAPACITY {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+
+    /// # Panics
+    /// If the journal's lock has been poisoned.
+    pub fn pop(&self) -> Option<WriteRecord> {
+        self.records
+            .lock()
+            .expect("journal lock should not be poisoned")
+            .pop_back()
+    }
+
+    /// # Panics
+    /// If the journal's lock has been poisoned.
+    #[must_use]
+    pub fn last(&self) -> Option<WriteRecord> {
+        self.records
+            .lock()
+            .expect("journal lock should not be poisoned")
+            .back()
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::bson::{doc, oid::ObjectId};
+
+    fn record(n: i32) -> WriteRecord {
+        WriteRecord {
+            db: "db".to_string(),
+            coll: Some("coll".to_string()),
+            kind: WriteKind::Insert,
+            before: None,
+            after: Some(doc! { "_id": n }),
+        }
+    }
+
+    #[test]
+    fn pops_most_recent_write_first() {
+        let journal = Journal::default();
+        journal.push(record(1));
+        journal.push(record(2));
+
+        assert_eq!(journal.pop(), Some(record(2)));
+        assert_eq!(journal.pop(), Some(record(1)));
+        assert_eq!(journal.pop(), None);
+    }
+
+    #[test]
+    fn forgets_oldest_writes_past_capacity() {
+        let journal = Journal::default();
+        for n in 0..=JOURNAL_CAPACITY {
+            #[expect(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+            journal.push(record(n as i32));
+        }
+
+        let mut num_records = 0;
+        let mut oldest = None;
+        while let Some(record) = journal.pop() {
+            num_records += 1;
+            oldest = Some(record);
+        }
+
+        assert_eq!(num_records, JOURNAL_CAPACITY);
+        assert_eq!(oldest, Some(record(1)));
+    }
+
+    #[test]
+    fn inverse_of_update_swaps_images() {
+        let update = WriteRecord {
+            kind: WriteKind::Update,
+            before: Some(doc! { "_id": 1, "a": 1 }),
+            after: Some(doc! { "_id": 1, "a": 2 }),
+            ..record(1)
+        };
+
+        let inverse = update.inverse();
+
+        assert_eq!(
+            inverse.map(|r| (r.kind, r.before, r.after)),
+            Some((
+                WriteKind::Update,
+                Some(doc! { "_id": 1, "a": 2 }),
+                Some(doc! { "_id": 1, "a": 1 }),
+            ))
+        );
+    }
+
+    #[test]
+    fn bson_types_survive_serialization() {
+        let record = WriteRecord {
+            after: Some(doc! { "_id": ObjectId::new(), "n": 1, "big": 1_i64 }),
+            ..record(1)
+        };
+
+        let json = serde_json::to_string(&record).expect("should serialize");
+        let parsed: WriteRecord = serde_json::from_str(&json).expect("should deserialize");
+
+        assert_eq!(parsed, record);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::utils::mongo_tree::MongoKey;
+use mongodb::bson::{doc, Bson, Document, Regex};
+use std::collections::BTreeSet;
+
+/// How many documents are sampled to find the string fields to search.
+pub const SCHEMA_SAMPLE_SIZE: i64 = 100;
+
+/// The most results a search across all pages returns.
+pub const MAX_SEARCH_RESULTS: i64 = 100;
+
+/// A document that matched a search across all pages of a query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    /// The page of the query's results that the document is on.
+    pub page: usize,
+
+    pub id: Bson,
+
+    /// The path to the first string in the document that matched, not
+    /// including the document's id.
+    pub path: Vec<MongoKey>,
+
+    /// The string that matched.
+    pub value: String,
+}
+
+/// Collects the dotted paths of the string fields in some documents. Array
+/// indices are left out, since querying an array's path matches any of its
+/// elements.
+#[must_use]
+pub fn string_paths(docs: &[Document]) -> BTreeSet<String> {
+    let mut paths = BTreeSet::new();
+    for doc in docs {
+        for (key, value) in doc {
+            collect_paths(key, value, true, &mut paths);
+        }
+    }
+    paths
+}
+
+/// Collects the dotted paths of every field in some documents, including the
+/// fields of sub-documents and the sub-documents themselves.
+#[must_use]
+pub fn field_paths(docs: &[Document]) -> BTreeSet<String> {
+    let mut paths = BTreeSet::new();
+    for doc in docs {
+        for (key, value) in doc {
+            collect_paths(key, value, false, &mut paths);
+        }
+    }
+    paths
+}
+
+fn collect_paths(path: &str, value: &Bson, strings_only: bool, paths: &mut BTreeSet<String>) {
+    match value {
+        Bson::Document(doc) => {
+            if !strings_only {
+                paths.insert(path.to_string());
+            }
+            for (key, value) in doc {
+                collect_paths(&format!("{path}.{key}"), value, strings_only, paths);
+            }
+        }
+        Bson::Array(values) => {
+            if !strings_only {
+                paths.insert(path.to_string());
+            }
+            for value in values {
+                collect_paths(path, value, strings_only, paths);
+            }
+        }
+        Bson::String(_) => {
+            paths.insert(path.to_string());
+        }
+        _ => {
+            if !strings_only {
+                paths.insert(path.to_string());
+            }
+        }
+    }
+}
+
+/// Builds a filter that matches documents where any of the given fields
+/// contains the text, ignoring case.
+#[must_use]
+pub fn search_filter(text: &str, paths: &BTreeSet<String>) -> Document {
+    let regex = Regex {
+        pattern: regex::escape(text),
+        options: "i".to_string(),
+    };
+    let conditions: Vec<_> = paths
+        .iter()
+        .map(|path| doc! { path: regex.clone() })
+        .collect();
+
+    doc! { "$or": conditions }
+}
+
+/// Finds the first string in a document that contains the text, ignoring
+/// case, and returns its path and value.
+#[must_use]
+pub fn first_match(doc: &Document, text: &str) -> Option<(Vec<MongoKey>, String)> {
+    let text = text.to_lowercase();
+    doc.iter()
+        .find_map(|(key, value)| find_in_value(vec![key.clone().into()], value, &text))
+}
+
+fn find_in_value(path: Vec<MongoKey>, value: &Bson, text: &str) -> Option<(Vec<MongoKey>, String)> {
+    let with_key = |key: MongoKey| {
+        let mut path = path.clone();
+        path.push(key);
+        path
+    };
+
+    match value {
+        Bson::String(s) if s.to_lowercase().contains(text) => Some((path, s.clone())),
+        Bson::Document(doc) => doc
+            .iter()
+            .find_map(|(key, value)| find_in_value(with_key(key.clone().into()), value, text)),
+        Bson::Array(values) => values
+            .iter()
+            .enumerate()
+            .find_map(|(idx, value)| find_in_value(with_key(idx.into()), value, text)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collect_string_paths() {
+        let docs = [
+            doc! { "_id": 1, "name": "a", "tags": ["x"], "address": { "city": "b", "zip": 1 } },
+            doc! { "_id": 2, "items": [{ "sku": "c" }] },
+        ];
+
+        assert_eq!(
+            string_paths(&docs).into_iter().collect::<Vec<_>>(),
+            vec!["address.city", "items.sku", "name", "tags"]
+        );
+    }
+
+    #[test]
+    fn collect_field_paths() {
+        let docs = [doc! { "_id": 1, "tags": ["x"], "address": { "zip": 1 } }];
+
+        assert_eq!(
+            field_paths(&docs).into_iter().collect::<Vec<_>>(),
+            vec!["_id", "address", "address.zip", "tags"]
+        );
+    }
+
+    #[test]
+    fn build_search_filter() {
+        let paths = BTreeSet::from(["a".to_string(), "b.c".to_string()]);
+        let regex = Bson::RegularExpression(Regex {
+            pattern: r"1\.5\$".to_string(),
+            options: "i".to_string(),
+        });
+
+        assert_eq!(
+            search_filter("1.5$", &paths),
+            doc! { "$or": [{ "a": regex.clone() }, { "b.c": regex }] }
+        );
+    }
+
+    #[test]
+    fn find_first_match() {
+        let doc = doc! { "_id": 1, "name": "Ann", "items": [{ "sku": "red-HAT" }] };
+
+        assert_eq!(
+            first_match(&doc, "hat"),
+            Some((
+                vec![
+                    "items".to_string().into(),
+                    MongoKey::Usize(0),
+                    "sku".to_string().into()
+                ],
+                "red-HAT".to_string()
+            ))
+        );
+        assert_eq!(first_match(&doc, "nope"), None);
+    }
+}
@@ -7,14 +7,18 @@ use crate::system::{
 use crossterm::event::Event as CrosstermEvent;
 use ratatui::{layout::Rect, Frame};
 
+pub mod audit_log_modal;
+pub mod compare_modal;
 pub mod confirm_modal;
 pub mod connection_screen;
+pub mod diff_modal;
 pub mod documents;
 pub mod help_modal;
 pub mod input;
 pub mod list;
 pub mod primary_screen;
 pub mod query_input;
+pub mod search_results_modal;
 pub mod status_bar;
 pub mod tab;
 pub mod tab_bar;
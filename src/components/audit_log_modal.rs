@@ -0,0 +1,278 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+use std::{cell::Cell, rc::Rc};
+
+use crate::{
+    client::audit_log::AuditEntry,
+    components::{diff_modal::change_to_line, tab::TabFocus, Component},
+    config::{color_map::ColorKey, Config},
+    system::{
+        command::{Command, CommandCategory, CommandGroup},
+        event::Event,
+        message::{ClientAction, Message},
+        signal::SignalQueue,
+    },
+    utils::doc_diff::{bson_preview, diff_docs},
+};
+
+use super::tab::CloneWithFocus;
+
+const AUDIT_LOG_MODAL_WIDTH: u16 = 100;
+const AUDIT_LOG_DETAILS_HEIGHT: u16 = 10;
+
+#[derive(Debug, Default, Clone)]
+pub struct AuditLogModal {
+    focus: Rc<Cell<TabFocus>>,
+    config: Config,
+
+    /// Most recent first.
+    entries: Vec<AuditEntry>,
+    state: ListState,
+
+    /// Hides the revert command, since reverting is a write.
+    read_only: bool,
+}
+
+impl CloneWithFocus for AuditLogModal {
+    fn clone_with_focus(&self, focus: Rc<Cell<TabFocus>>) -> Self {
+        Self {
+            focus,
+            ..self.clone()
+        }
+    }
+}
+
+impl AuditLogModal {
+    pub fn new(focus: Rc<Cell<TabFocus>>, config: Config) -> Self {
+        Self {
+            focus,
+            config,
+            ..Default::default()
+        }
+    }
+
+    /// Shows the given entries, which should be in the order that they were
+    /// written to the log.
+    pub fn show_with(&mut self, mut entries: Vec<AuditEntry>) {
+        entries.reverse();
+        self.state = ListState::default().with_selected((!entries.is_empty()).then_some(0));
+        self.entries = entries;
+        self.focus();
+    }
+
+    fn selected_entry(&self) -> Option<&AuditEntry> {
+        self.entries.get(self.state.selected()?)
+    }
+
+    fn entry_to_item(&self, entry: &AuditEntry) -> ListItem<'static> {
+        let secondary = self.config.color_map.get(&ColorKey::FgSecondary);
+        let id = entry.record.doc_id().map(bson_preview).unwrap_or_default();
+
+        ListItem::new(Line::from(vec![
+            Span::from(format!("{} ", entry.timestamp)).fg(secondary),
+            Span::from(format!("{:<16}", entry.record.kind.to_string()))
+                .fg(self.config.color_map.get(&ColorKey::Key)),
+            Span::from(format!("{} ", entry.record.namespace())),
+            Span::from(id).fg(secondary),
+            Span::from(format!(" ({})", entry.connection)).fg(secondary),
+        ]))
+    }
+
+    fn details(&self) -> Vec<Line<'static>> {
+        let Some(entry) = self.selected_entry() else {
+            return vec![];
+        };
+
+        let mut lines = vec![];
+        if let Some(filter) = &entry.filter {
+            lines.push(
+                Line::from(format!("filter: {}", bson_preview(&filter.clone().into())))
+                    .fg(self.config.color_map.get(&ColorKey::FgSecondary)),
+            );
+        }
+
+        let before = entry.record.before.clone().unwrap_or_default();
+        let after = entry.record.after.clone().unwrap_or_default();
+        lines.extend(
+            diff_docs(&before, &after)
+                .iter()
+                .map(|change| change_to_line(change, &self.config)),
+        );
+
+        lines
+    }
+}
+
+impl Component for AuditLogModal {
+    fn is_focused(&self) -> bool {
+        self.focus.get() == TabFocus::AuditLogModal
+    }
+
+    fn focus(&self) {
+        self.focus.set(TabFocus::AuditLogModal);
+    }
+
+    fn commands(&self) -> Vec<CommandGroup> {
+        let mut out = vec![];
+
+        if !self.read_only
+            && self
+                .selected_entry()
+                .is_some_and(|entry| entry.record.inverse().is_some())
+        {
+            out.push(
+                CommandGroup::new(vec![Command::Undo], "revert")
+                    .in_cat(CommandCategory::StatusBarOnly),
+            );
+        }
+
+        out.append(&mut vec![
+            CommandGroup::new(vec![Command::Back], "close").in_cat(CommandCategory::StatusBarOnly),
+            CommandGroup::new(vec![Command::NavUp, Command::NavDown], "navigate"),
+        ]);
+
+        out
+    }
+
+    fn handle_command(&mut self, command: &Command, queue: &mut SignalQueue) {
+        match command {
+            Command::Undo => {
+                if let Some(entry) = self.selected_entry() {
+                    queue.push(Event::AuditLogClosed);
+                    queue.push(Message::to_client(ClientAction::RevertWrite(Box::new(
+                        entry.clone(),
+                    ))));
+                }
+            }
+            Command::Back => queue.push(Event::AuditLogClosed),
+            Command::NavUp => self.state.select_previous(),
+            Command::NavDown => self.state.select_next(),
+            _ => {}
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event, _queue: &mut SignalQueue) {
+        if let Event::ReadOnlyModeChanged(read_only) = event {
+            self.read_only = *read_only;
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let layout = Layout::vertical(vec![
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(2),
+        ])
+        .split(area);
+        let layout = Layout::horizontal(vec![
+            Constraint::Fill(1),
+            Constraint::Length(AUDIT_LOG_MODAL_WIDTH + 2),
+            Constraint::Fill(1),
+        ])
+        .split(layout[1]);
+        let modal_area = layout[1];
+
+        let block = Block::bordered()
+            .border_style(self.config.color_map.get(&ColorKey::PopupBorder))
+            .title(" Audit Log ")
+            .title(Line::from(format!(" {} writes ", self.entries.len())).right_aligned())
+            .bg(self.config.color_map.get(&ColorKey::PopupBg));
+        let inner_area = block.inner(modal_area);
+
+        frame.render_widget(Clear, modal_area);
+        frame.render_widget(block, modal_area);
+
+        if self.entries.is_empty() {
+            let content = Paragraph::new("(no writes recorded yet)")
+                .fg(self.config.color_map.get(&ColorKey::FgSecondary));
+            frame.render_widget(content, inner_area);
+            return;
+        }
+
+        let layout = Layout::vertical(vec![
+            Constraint::Fill(1),
+            Constraint::Length(AUDIT_LOG_DETAILS_HEIGHT),
+        ])
+        .split(inner_area);
+
+        let items: Vec<_> = self
+            .entries
+            .iter()
+            .map(|entry| self.entry_to_item(entry))
+            .collect();
+        let list = List::new(items)
+            .fg(self.config.color_map.get(&ColorKey::FgPrimary))
+            .highlight_style(
+                Style::default()
+                    .fg(self.config.color_map.get(&ColorKey::SelectionFg))
+                    .bg(self.config.color_map.get(&ColorKey::SelectionBg)),
+            );
+        frame.render_stateful_widget(list, layout[0], &mut self.state);
+
+        let details = Paragraph::new(self.details()).block(
+            Block::new()
+                .borders(Borders::TOP)
+                .border_style(self.config.color_map.get(&ColorKey::PopupBorder)),
+        );
+        frame.render_widget(details, layout[1]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        client::journal::{WriteKind, WriteRecord},
+        testing::ComponentTestHarness,
+    };
+    use mongodb::bson::doc;
+
+    fn entry(kind: WriteKind) -> AuditEntry {
+        AuditEntry::now(
+            "local".to_string(),
+            None,
+            WriteRecord {
+                db: "db".to_string(),
+                coll: Some("coll".to_string()),
+                kind,
+                before: None,
+                after: Some(doc! { "_id": 1 }),
+            },
+        )
+    }
+
+    #[test]
+    fn revert_selected_entry() {
+        let mut test = ComponentTestHarness::new(AuditLogModal::default());
+        test.component_mut()
+            .show_with(vec![entry(WriteKind::Insert), entry(WriteKind::Update)]);
+
+        test.given_command(Command::Undo);
+
+        // the most recent entry is selected first
+        test.expect_event(|e| matches!(e, Event::AuditLogClosed));
+        test.expect_message(|m| {
+            matches!(
+                m.read_as_client(),
+                Some(ClientAction::RevertWrite(entry)) if entry.record.kind == WriteKind::Update
+            )
+        });
+    }
+
+    #[test]
+    fn drops_cannot_be_reverted() {
+        let mut test = ComponentTestHarness::new(AuditLogModal::default());
+        test.component_mut()
+            .show_with(vec![entry(WriteKind::DropCollection)]);
+
+        let can_revert = test
+            .component_mut()
+            .commands()
+            .iter()
+            .any(|group| group.commands.contains(&Command::Undo));
+
+        assert!(!can_revert);
+    }
+}
@@ -0,0 +1,233 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::{
+    components::Component,
+    config::{color_map::ColorKey, Config},
+    system::{
+        command::{Command, CommandCategory, CommandGroup},
+        message::{AppAction, Message},
+        signal::SignalQueue,
+    },
+    utils::doc_diff::{align_docs, bson_preview, AlignedField, FieldChangeKind},
+};
+use mongodb::bson::{Bson, Document};
+
+#[derive(Debug, Clone, Copy)]
+enum Side {
+    Left,
+    Right,
+}
+
+/// Shows two documents next to each other, with their fields lined up by key
+/// path and any differences highlighted.
+#[derive(Debug, Default, Clone)]
+pub struct CompareModal {
+    config: Config,
+
+    left: Document,
+    right: Document,
+    fields: Vec<AlignedField>,
+
+    scroll: usize,
+}
+
+impl CompareModal {
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            ..Default::default()
+        }
+    }
+
+    pub fn show_with(&mut self, left: Document, right: Document) {
+        self.fields = align_docs(&left, &right);
+        self.left = left;
+        self.right = right;
+        self.scroll = 0;
+    }
+
+    fn num_differences(&self) -> usize {
+        self.fields
+            .iter()
+            .filter(|field| field.change_kind().is_some())
+            .count()
+    }
+
+    /// Scrolls to the next (or previous) field that differs between the two
+    /// documents, if there is one.
+    fn jump_to_difference(&mut self, forward: bool) {
+        let is_difference = |(idx, field): &(usize, &AlignedField)| {
+            field.change_kind().is_some()
+                && if forward {
+                    *idx > self.scroll
+                } else {
+                    *idx < self.scroll
+                }
+        };
+
+        let mut fields = self.fields.iter().enumerate();
+        let target = if forward {
+            fields.find(is_difference)
+        } else {
+            fields.rev().find(is_difference)
+        };
+
+        if let Some((idx, _)) = target {
+            self.scroll = idx;
+        }
+    }
+
+    fn title(doc: &Document) -> String {
+        doc.get("_id").map_or_else(
+            || " (no _id) ".to_string(),
+            |id| format!(" _id: {} ", bson_preview(id)),
+        )
+    }
+
+    fn field_to_line(&self, field: &AlignedField, side: Side) -> Line<'static> {
+        let value = match side {
+            Side::Left => &field.left,
+            Side::Right => &field.right,
+        };
+        let Some(value) = value else {
+            return Line::default();
+        };
+
+        let value_color = match field.change_kind() {
+            Some(FieldChangeKind::Changed) => ColorKey::DiffChanged,
+            Some(FieldChangeKind::Added) => ColorKey::DiffAdded,
+            Some(FieldChangeKind::Removed) => ColorKey::DiffRemoved,
+            None => ColorKey::FgPrimary,
+        };
+        let value_text = match value {
+            Bson::Document(_) => "{…}".to_string(),
+            Bson::Array(_) => "[…]".to_string(),
+            value => bson_preview(value),
+        };
+
+        Line::from(vec![
+            Span::from("  ".repeat(field.depth)),
+            Span::from(field.key.clone()).fg(self.config.color_map.get(&ColorKey::Key)),
+            Span::from(": ").fg(self.config.color_map.get(&ColorKey::Punctuation)),
+            Span::from(value_text).fg(self.config.color_map.get(&value_color)),
+        ])
+    }
+
+    fn render_side(&self, frame: &mut Frame, area: Rect, side: Side) {
+        let doc = match side {
+            Side::Left => &self.left,
+            Side::Right => &self.right,
+        };
+        let lines: Vec<_> = self
+            .fields
+            .iter()
+            .map(|field| self.field_to_line(field, side))
+            .collect();
+
+        #[expect(clippy::cast_possible_truncation)]
+        let content = Paragraph::new(lines).scroll((self.scroll as u16, 0)).block(
+            Block::new()
+                .borders(Borders::TOP)
+                .border_style(self.config.color_map.get(&ColorKey::PopupBorder))
+                .title(Self::title(doc))
+                .fg(self.config.color_map.get(&ColorKey::FgPrimary)),
+        );
+
+        frame.render_widget(content, area);
+    }
+}
+
+impl Component for CompareModal {
+    fn commands(&self) -> Vec<CommandGroup> {
+        vec![
+            CommandGroup::new(
+                vec![Command::PreviousPage, Command::NextPage],
+                "previous/next difference",
+            )
+            .in_cat(CommandCategory::StatusBarOnly),
+            CommandGroup::new(vec![Command::Back], "close").in_cat(CommandCategory::StatusBarOnly),
+            CommandGroup::new(vec![Command::NavUp, Command::NavDown], "scroll"),
+        ]
+    }
+
+    fn handle_command(&mut self, command: &Command, queue: &mut SignalQueue) {
+        match command {
+            Command::NavUp => self.scroll = self.scroll.saturating_sub(1),
+            Command::NavDown => {
+                self.scroll = (self.scroll + 1).min(self.fields.len().saturating_sub(1));
+            }
+            Command::NextPage => self.jump_to_difference(true),
+            Command::PreviousPage => self.jump_to_difference(false),
+            Command::Back => queue.push(Message::to_app(AppAction::CloseCompareModal)),
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let layout = Layout::vertical(vec![
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .split(area);
+        let layout = Layout::horizontal(vec![
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(2),
+        ])
+        .split(layout[1]);
+        let modal_area = layout[1];
+
+        let num_differences = self.num_differences();
+        let difference_word = if num_differences == 1 {
+            "difference"
+        } else {
+            "differences"
+        };
+
+        let block = Block::bordered()
+            .border_style(self.config.color_map.get(&ColorKey::PopupBorder))
+            .title(" Compare Documents ")
+            .title(Line::from(format!(" {num_differences} {difference_word} ")).right_aligned())
+            .bg(self.config.color_map.get(&ColorKey::PopupBg));
+        let inner_area = block.inner(modal_area);
+
+        frame.render_widget(Clear, modal_area);
+        frame.render_widget(block, modal_area);
+
+        let layout = Layout::horizontal(vec![Constraint::Fill(1), Constraint::Fill(1)])
+            .spacing(2)
+            .split(inner_area);
+        self.render_side(frame, layout[0], Side::Left);
+        self.render_side(frame, layout[1], Side::Right);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::ComponentTestHarness;
+    use mongodb::bson::doc;
+
+    #[test]
+    fn jump_between_differences() {
+        let mut test = ComponentTestHarness::new(CompareModal::default());
+        test.component_mut().show_with(
+            doc! { "_id": 1, "a": 1, "b": 2, "c": 3 },
+            doc! { "_id": 2, "a": 1, "b": 2, "c": 4 },
+        );
+
+        test.given_command(Command::NextPage);
+        assert_eq!(test.component_mut().scroll, 3);
+
+        test.given_command(Command::PreviousPage);
+        assert_eq!(test.component_mut().scroll, 0);
+
+        // there are no more differences before the first one
+        test.given_command(Command::PreviousPage);
+        assert_eq!(test.component_mut().scroll, 0);
+    }
+}
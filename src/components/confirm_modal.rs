@@ -5,26 +5,35 @@ use ratatui::{
 use std::{cell::Cell, rc::Rc};
 
 use crate::{
+    client::journal::WriteKind,
     components::{tab::TabFocus, Component},
     config::{color_map::ColorKey, Config},
     system::{
         command::{Command, CommandCategory, CommandGroup},
         event::Event,
+        message::{AppAction, Message},
         signal::SignalQueue,
     },
 };
 
-use super::tab::CloneWithFocus;
+use super::{
+    input::{DefaultFormatter, InnerInput},
+    tab::CloneWithFocus,
+};
 
 const CONFIRM_MODAL_WIDTH: u16 = 40;
 const CONFIRM_MODAL_HEIGHT: u16 = 3;
+const NAME_INPUT_HEIGHT: u16 = 3;
 
 #[derive(Debug, Clone, Copy)]
 pub enum ConfirmKind {
     DeleteConnection,
     DeleteDoc,
     DropCollection,
+    RemoveField,
     DropDatabase,
+    UndoWrite(WriteKind),
+    RevertWrite(WriteKind),
 }
 
 impl ConfirmKind {
@@ -33,7 +42,9 @@ impl ConfirmKind {
             Self::DropDatabase
             | Self::DropCollection
             | Self::DeleteDoc
+            | Self::RemoveField
             | Self::DeleteConnection => Command::Delete,
+            Self::UndoWrite(_) | Self::RevertWrite(_) => Command::Undo,
         }
     }
 }
@@ -43,6 +54,11 @@ pub struct ConfirmModal {
     focus: Rc<Cell<TabFocus>>,
     kind: Option<ConfirmKind>,
     config: Config,
+
+    /// The name of the protected database or collection being dropped, which
+    /// has to be typed in before the drop is confirmed.
+    protected_name: Option<String>,
+    input: InnerInput<DefaultFormatter>,
 }
 
 impl CloneWithFocus for ConfirmModal {
@@ -55,16 +71,33 @@ impl CloneWithFocus for ConfirmModal {
 }
 
 impl ConfirmModal {
-    pub fn new(focus: Rc<Cell<TabFocus>>, config: Config) -> Self {
+    pub fn new(
+        focus: Rc<Cell<TabFocus>>,
+        cursor_pos: Rc<Cell<(u16, u16)>>,
+        config: Config,
+    ) -> Self {
+        let mut input = InnerInput::new(
+            "Name",
+            cursor_pos,
+            config.clone(),
+            DefaultFormatter::default(),
+        );
+        input.start_editing();
+
         Self {
             focus,
             config,
+            input,
             ..Default::default()
         }
     }
 
-    pub fn show_with(&mut self, kind: ConfirmKind) {
+    /// Shows the modal for an action. If a protected name is given, the user
+    /// has to type it in to confirm.
+    pub fn show_with(&mut self, kind: ConfirmKind, protected_name: Option<String>) {
         self.kind = Some(kind);
+        self.protected_name = protected_name;
+        self.input.set_value("");
         self.focus();
     }
 
@@ -78,6 +111,10 @@ impl ConfirmModal {
                 "Confirm Delete",
                 "Are you sure you want to delete this document? This cannot be undone.",
             )),
+            Some(ConfirmKind::RemoveField) => Some((
+                "Confirm Remove",
+                "Remove this field from the document? Use undo to restore it.",
+            )),
             Some(ConfirmKind::DropCollection) => Some((
                 "Confirm Drop",
                 "Are you sure you want to drop this collection? This cannot be undone.",
@@ -86,8 +123,37 @@ impl ConfirmModal {
                 "Confirm Drop",
                 "Are you sure? If you drop the database in tongo, you drop it in real life.",
             )),
+            Some(ConfirmKind::UndoWrite(WriteKind::Insert)) => Some((
+                "Confirm Undo",
+                "Undo the last insert? The inserted document will be deleted.",
+            )),
+            Some(ConfirmKind::UndoWrite(WriteKind::Update)) => Some((
+                "Confirm Undo",
+                "Undo the last update? The document will be restored to its previous version.",
+            )),
+            Some(ConfirmKind::UndoWrite(WriteKind::Delete)) => Some((
+                "Confirm Undo",
+                "Undo the last delete? The deleted document will be reinserted.",
+            )),
+            Some(ConfirmKind::RevertWrite(WriteKind::Insert)) => Some((
+                "Confirm Revert",
+                "Revert this insert? The inserted document will be deleted.",
+            )),
+            Some(ConfirmKind::RevertWrite(WriteKind::Update)) => Some((
+                "Confirm Revert",
+                "Revert this update? The document will be restored to its earlier version.",
+            )),
+            Some(ConfirmKind::RevertWrite(WriteKind::Delete)) => Some((
+                "Confirm Revert",
+                "Revert this delete? The deleted document will be reinserted.",
+            )),
 
-            None => None,
+            // drops are never undone or reverted
+            Some(
+                ConfirmKind::UndoWrite(WriteKind::DropCollection | WriteKind::DropDatabase)
+                | ConfirmKind::RevertWrite(WriteKind::DropCollection | WriteKind::DropDatabase),
+            )
+            | None => None,
         }
     }
 }
@@ -106,9 +172,14 @@ impl Component for ConfirmModal {
             return;
         };
 
+        let input_height = if self.protected_name.is_some() {
+            NAME_INPUT_HEIGHT
+        } else {
+            0
+        };
         let layout = Layout::vertical(vec![
             Constraint::Fill(1),
-            Constraint::Length(CONFIRM_MODAL_HEIGHT + 2),
+            Constraint::Length(CONFIRM_MODAL_HEIGHT + 2 + input_height),
             Constraint::Fill(1),
         ])
         .split(area);
@@ -118,17 +189,36 @@ impl Component for ConfirmModal {
             Constraint::Fill(1),
         ])
         .split(layout[1]);
+        let layout = Layout::vertical(vec![
+            Constraint::Length(CONFIRM_MODAL_HEIGHT + 2),
+            Constraint::Length(input_height),
+        ])
+        .split(layout[1]);
 
+        let (title, message) = self.protected_name.as_ref().map_or_else(
+            || (format!(" {title} "), message.to_string()),
+            |name| {
+                (
+                    format!(" {title} (Protected) "),
+                    format!("\"{name}\" is protected. Type in its name to drop it for good."),
+                )
+            },
+        );
         let content = Paragraph::new(message).wrap(Wrap { trim: true }).block(
             Block::bordered()
                 .border_style(self.config.color_map.get(&ColorKey::PopupBorder))
-                .title(format!(" {title} "))
+                .title(title)
                 .fg(self.config.color_map.get(&ColorKey::FgPrimary))
                 .bg(self.config.color_map.get(&ColorKey::PopupBg)),
         );
 
-        frame.render_widget(Clear, layout[1]);
-        frame.render_widget(content, layout[1]);
+        frame.render_widget(Clear, layout[0]);
+        frame.render_widget(content, layout[0]);
+
+        if self.protected_name.is_some() {
+            frame.render_widget(Clear, layout[1]);
+            self.input.render(frame, layout[1], true);
+        }
     }
 
     fn commands(&self) -> Vec<CommandGroup> {
@@ -139,15 +229,81 @@ impl Component for ConfirmModal {
         ]
     }
 
+    fn handle_raw_event(&mut self, event: &crossterm::event::Event, queue: &mut SignalQueue) {
+        if self.protected_name.is_some() {
+            self.input.handle_raw_event(event, queue);
+        }
+    }
+
     fn handle_command(&mut self, command: &Command, queue: &mut SignalQueue) {
         let Some(confirm_kind) = &self.kind else {
             return;
         };
 
         match command {
-            Command::Confirm => queue.push(Event::ConfirmYes(confirm_kind.command())),
-            Command::Back => queue.push(Event::ConfirmNo),
+            Command::Confirm => {
+                if let Some(name) = self.protected_name.take() {
+                    if self.input.value() != name {
+                        queue.push(Event::ErrorOccurred(
+                            format!("Type in \"{name}\" exactly to confirm.").into(),
+                        ));
+                        self.protected_name = Some(name);
+                        return;
+                    }
+                    self.input.set_value("");
+                    queue.push(Message::to_app(AppAction::ExitRawMode));
+                }
+                queue.push(Event::ConfirmYes(confirm_kind.command()));
+            }
+            Command::Back => {
+                if self.protected_name.take().is_some() {
+                    self.input.set_value("");
+                    queue.push(Message::to_app(AppAction::ExitRawMode));
+                }
+                queue.push(Event::ConfirmNo);
+            }
             _ => {}
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::ComponentTestHarness;
+
+    fn protected_modal(name: &str) -> ComponentTestHarness<ConfirmModal> {
+        let mut test = ComponentTestHarness::new(ConfirmModal::default());
+        test.component_mut().input.start_editing();
+        test.component_mut()
+            .show_with(ConfirmKind::DropDatabase, Some(name.to_string()));
+        test
+    }
+
+    #[test]
+    fn require_protected_name() {
+        let mut test = protected_modal("admin");
+
+        test.given_string("admi");
+        test.given_command(Command::Confirm);
+        test.expect_event(|e| matches!(e, Event::ErrorOccurred(..)));
+        test.expect_no_messages();
+
+        test.given_string("n");
+        test.given_command(Command::Confirm);
+        test.expect_event(|e| matches!(e, Event::ConfirmYes(Command::Delete)));
+        test.expect_message(|m| matches!(m.read_as_app(), Some(AppAction::ExitRawMode)));
+    }
+
+    #[test]
+    fn cancel_protected_drop() {
+        let mut test = protected_modal("admin");
+
+        test.given_string("admin");
+        test.given_command(Command::Back);
+
+        test.expect_event(|e| matches!(e, Event::ConfirmNo));
+        test.expect_message(|m| matches!(m.read_as_app(), Some(AppAction::ExitRawMode)));
+        assert_eq!(test.component().input.value(), "");
+    }
+}
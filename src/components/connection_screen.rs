@@ -1,15 +1,21 @@
 use super::{
-    input::{conn_name_input::ConnNameInput, conn_str_input::ConnStrInput},
+    input::{
+        conn_details_input::{ConnDetail, ConnDetailsInput},
+        conn_name_input::ConnNameInput,
+        conn_ssh_input::ConnSshInput,
+        conn_str_input::ConnStrInput,
+        conn_tls_input::{ConnTlsInput, TlsField},
+    },
     list::connections::{Connections, PersistedConnections},
     tab::{CloneWithFocus, TabFocus},
     Component,
 };
 use crate::{
     config::{color_map::ColorKey, Config},
-    model::connection::{Connection, ConnectionManager},
+    model::connection::{Connection, ConnectionManager, TlsSettings},
     persistence::PersistedComponent,
     system::{
-        command::{Command, CommandGroup},
+        command::{Command, CommandCategory, CommandGroup},
         event::Event,
         message::{AppAction, ClientAction, ConnScreenAction, Message},
         signal::SignalQueue,
@@ -25,7 +31,7 @@ use serde::{Deserialize, Serialize};
 use std::{cell::Cell, rc::Rc};
 
 const CONN_EDITOR_WIDTH: u16 = 60;
-const CONN_EDITOR_HEIGHT: u16 = 12;
+const CONN_EDITOR_HEIGHT: u16 = 32;
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ConnScrFocus {
@@ -33,6 +39,9 @@ pub enum ConnScrFocus {
     ConnList,
     NameIn,
     StringIn,
+    SshIn,
+    TlsIn(TlsField),
+    DetailIn(ConnDetail),
 }
 
 #[derive(Debug, Clone)]
@@ -42,6 +51,13 @@ pub struct ConnectionScreen {
     conn_list: Connections,
     conn_name_input: ConnNameInput,
     conn_str_input: ConnStrInput,
+    conn_ssh_input: ConnSshInput,
+    conn_ca_input: ConnTlsInput,
+    conn_cert_input: ConnTlsInput,
+    conn_tls_options_input: ConnTlsInput,
+    conn_folder_input: ConnDetailsInput,
+    conn_tags_input: ConnDetailsInput,
+    conn_env_input: ConnDetailsInput,
     editing_conn: Option<Connection>,
     config: Config,
 }
@@ -57,7 +73,12 @@ impl Default for ConnectionScreen {
         let connection_manager = ConnectionManager::new(vec![], storage);
         let conn_list = Connections::new(focus.clone(), config.clone(), connection_manager.clone());
         let conn_name_input = ConnNameInput::new(focus.clone(), cursor_pos.clone(), config.clone());
-        let conn_str_input = ConnStrInput::new(focus.clone(), cursor_pos, config.clone());
+        let conn_str_input = ConnStrInput::new(focus.clone(), cursor_pos.clone(), config.clone());
+        let [conn_ca_input, conn_cert_input, conn_tls_options_input] =
+            Self::tls_inputs(&focus, &cursor_pos, &config);
+        let [conn_folder_input, conn_tags_input, conn_env_input] =
+            Self::details_inputs(&focus, &cursor_pos, &config);
+        let conn_ssh_input = ConnSshInput::new(focus.clone(), cursor_pos, config.clone());
 
         Self {
             focus,
@@ -65,6 +86,13 @@ impl Default for ConnectionScreen {
             conn_list,
             conn_name_input,
             conn_str_input,
+            conn_ssh_input,
+            conn_ca_input,
+            conn_cert_input,
+            conn_tls_options_input,
+            conn_folder_input,
+            conn_tags_input,
+            conn_env_input,
             editing_conn: None,
             config,
         }
@@ -76,6 +104,13 @@ impl CloneWithFocus for ConnectionScreen {
         Self {
             conn_name_input: self.conn_name_input.clone_with_focus(focus.clone()),
             conn_str_input: self.conn_str_input.clone_with_focus(focus.clone()),
+            conn_ssh_input: self.conn_ssh_input.clone_with_focus(focus.clone()),
+            conn_ca_input: self.conn_ca_input.clone_with_focus(focus.clone()),
+            conn_cert_input: self.conn_cert_input.clone_with_focus(focus.clone()),
+            conn_tls_options_input: self.conn_tls_options_input.clone_with_focus(focus.clone()),
+            conn_folder_input: self.conn_folder_input.clone_with_focus(focus.clone()),
+            conn_tags_input: self.conn_tags_input.clone_with_focus(focus.clone()),
+            conn_env_input: self.conn_env_input.clone_with_focus(focus.clone()),
             conn_list: self.conn_list.clone_with_focus(focus.clone()),
             focus,
             ..self.clone()
@@ -93,19 +128,107 @@ impl ConnectionScreen {
     ) -> Self {
         let conn_name_input =
             ConnNameInput::new(app_focus.clone(), cursor_pos.clone(), config.clone());
-        let conn_str_input = ConnStrInput::new(app_focus.clone(), cursor_pos, config.clone());
+        let conn_str_input =
+            ConnStrInput::new(app_focus.clone(), cursor_pos.clone(), config.clone());
+        let [conn_ca_input, conn_cert_input, conn_tls_options_input] =
+            Self::tls_inputs(&app_focus, &cursor_pos, &config);
+        let [conn_folder_input, conn_tags_input, conn_env_input] =
+            Self::details_inputs(&app_focus, &cursor_pos, &config);
+        let conn_ssh_input = ConnSshInput::new(app_focus.clone(), cursor_pos, config.clone());
 
         Self {
             focus: app_focus,
             conn_list: connection_list,
             conn_name_input,
             conn_str_input,
+            conn_ssh_input,
+            conn_ca_input,
+            conn_cert_input,
+            conn_tls_options_input,
+            conn_folder_input,
+            conn_tags_input,
+            conn_env_input,
             connection_manager,
             editing_conn: None,
             config,
         }
     }
 
+    fn tls_inputs(
+        focus: &Rc<Cell<TabFocus>>,
+        cursor_pos: &Rc<Cell<(u16, u16)>>,
+        config: &Config,
+    ) -> [ConnTlsInput; 3] {
+        [TlsField::CaFile, TlsField::CertKeyFile, TlsField::Options]
+            .map(|field| ConnTlsInput::new(field, focus.clone(), cursor_pos.clone(), config.clone()))
+    }
+
+    const fn tls_input(&mut self, field: TlsField) -> &mut ConnTlsInput {
+        match field {
+            TlsField::CaFile => &mut self.conn_ca_input,
+            TlsField::CertKeyFile => &mut self.conn_cert_input,
+            TlsField::Options => &mut self.conn_tls_options_input,
+        }
+    }
+
+    /// The TLS settings in the inputs, or `None` if they're all empty.
+    fn tls_settings(&self) -> Option<TlsSettings> {
+        let tls = TlsSettings {
+            ca_file: self.conn_ca_input.path(),
+            cert_key_file: self.conn_cert_input.path(),
+            ..self.conn_tls_options_input.options().unwrap_or_default()
+        };
+        (!tls.is_empty()).then_some(tls)
+    }
+
+    fn details_inputs(
+        focus: &Rc<Cell<TabFocus>>,
+        cursor_pos: &Rc<Cell<(u16, u16)>>,
+        config: &Config,
+    ) -> [ConnDetailsInput; 3] {
+        [
+            ConnDetail::Folder,
+            ConnDetail::Tags,
+            ConnDetail::Environment,
+        ]
+        .map(|detail| {
+            ConnDetailsInput::new(detail, focus.clone(), cursor_pos.clone(), config.clone())
+        })
+    }
+
+    const fn details_input(&mut self, detail: ConnDetail) -> &mut ConnDetailsInput {
+        match detail {
+            ConnDetail::Folder => &mut self.conn_folder_input,
+            ConnDetail::Tags => &mut self.conn_tags_input,
+            ConnDetail::Environment => &mut self.conn_env_input,
+        }
+    }
+
+    /// Whether one of the inputs for editing a connection is focused.
+    fn is_editing(&self) -> bool {
+        matches!(
+            self.internal_focus(),
+            Some(
+                ConnScrFocus::NameIn
+                    | ConnScrFocus::StringIn
+                    | ConnScrFocus::SshIn
+                    | ConnScrFocus::TlsIn(..)
+                    | ConnScrFocus::DetailIn(..)
+            )
+        )
+    }
+
+    /// The connection as it's been filled in so far, for testing it.
+    fn connection_to_test(&self) -> Connection {
+        let mut conn = Connection::new(
+            self.conn_name_input.value().to_string(),
+            self.conn_str_input.value().to_string(),
+        );
+        conn.ssh_tunnel = self.conn_ssh_input.tunnel().ok().flatten();
+        conn.tls = self.tls_settings();
+        conn
+    }
+
     /// Narrows the shared `AppFocus` variable into the focus enum for this componenent
     fn internal_focus(&self) -> Option<ConnScrFocus> {
         match self.focus.get() {
@@ -117,19 +240,52 @@ impl ConnectionScreen {
 
 impl Component for ConnectionScreen {
     fn commands(&self) -> Vec<CommandGroup> {
-        match self.internal_focus() {
+        let mut out = if self.is_editing() {
+            vec![
+                CommandGroup::new(vec![Command::TestConnection], "test connection")
+                    .in_cat(CommandCategory::ConnActions),
+            ]
+        } else {
+            vec![]
+        };
+        out.append(&mut match self.internal_focus() {
             Some(ConnScrFocus::ConnList) => self.conn_list.commands(),
             Some(ConnScrFocus::NameIn) => self.conn_name_input.commands(),
             Some(ConnScrFocus::StringIn) => self.conn_str_input.commands(),
+            Some(ConnScrFocus::SshIn) => self.conn_ssh_input.commands(),
+            Some(ConnScrFocus::TlsIn(field)) => match field {
+                TlsField::CaFile => self.conn_ca_input.commands(),
+                TlsField::CertKeyFile => self.conn_cert_input.commands(),
+                TlsField::Options => self.conn_tls_options_input.commands(),
+            },
+            Some(ConnScrFocus::DetailIn(detail)) => match detail {
+                ConnDetail::Folder => self.conn_folder_input.commands(),
+                ConnDetail::Tags => self.conn_tags_input.commands(),
+                ConnDetail::Environment => self.conn_env_input.commands(),
+            },
             None => vec![],
-        }
+        });
+        out
     }
 
     fn handle_command(&mut self, command: &Command, queue: &mut SignalQueue) {
+        if *command == Command::TestConnection && self.is_editing() {
+            let conn = self.connection_to_test();
+            queue.push(Message::to_client(ClientAction::TestConnection(conn)));
+            return;
+        }
+
         match self.internal_focus() {
             Some(ConnScrFocus::ConnList) => self.conn_list.handle_command(command, queue),
             Some(ConnScrFocus::NameIn) => self.conn_name_input.handle_command(command, queue),
             Some(ConnScrFocus::StringIn) => self.conn_str_input.handle_command(command, queue),
+            Some(ConnScrFocus::SshIn) => self.conn_ssh_input.handle_command(command, queue),
+            Some(ConnScrFocus::TlsIn(field)) => {
+                self.tls_input(field).handle_command(command, queue);
+            }
+            Some(ConnScrFocus::DetailIn(detail)) => {
+                self.details_input(detail).handle_command(command, queue);
+            }
             None => {},
         }
     }
@@ -139,6 +295,13 @@ impl Component for ConnectionScreen {
             Some(ConnScrFocus::ConnList) => self.conn_list.handle_raw_event(event, queue),
             Some(ConnScrFocus::NameIn) => self.conn_name_input.handle_raw_event(event, queue),
             Some(ConnScrFocus::StringIn) => self.conn_str_input.handle_raw_event(event, queue),
+            Some(ConnScrFocus::SshIn) => self.conn_ssh_input.handle_raw_event(event, queue),
+            Some(ConnScrFocus::TlsIn(field)) => {
+                self.tls_input(field).handle_raw_event(event, queue);
+            }
+            Some(ConnScrFocus::DetailIn(detail)) => {
+                self.details_input(detail).handle_raw_event(event, queue);
+            }
             None => {},
         }
     }
@@ -154,6 +317,13 @@ impl Component for ConnectionScreen {
         self.conn_list.handle_event(event, queue);
         self.conn_name_input.handle_event(event, queue);
         self.conn_str_input.handle_event(event, queue);
+        self.conn_ssh_input.handle_event(event, queue);
+        self.conn_ca_input.handle_event(event, queue);
+        self.conn_cert_input.handle_event(event, queue);
+        self.conn_tls_options_input.handle_event(event, queue);
+        self.conn_folder_input.handle_event(event, queue);
+        self.conn_tags_input.handle_event(event, queue);
+        self.conn_env_input.handle_event(event, queue);
     }
 
     fn handle_message(&mut self, message: &Message, queue: &mut SignalQueue) {
@@ -166,9 +336,9 @@ impl Component for ConnectionScreen {
             Some(ConnScreenAction::StartEditingConn(conn)) => {
                 self.conn_name_input.focus();
                 self.conn_name_input.start_editing();
-                self.editing_conn = Some(conn.clone());
+                self.editing_conn = Some(conn.as_ref().clone());
                 queue.push(Message::to_app(AppAction::EnterRawMode));
-                queue.push(Event::EditConnectionStarted(conn.clone()));
+                queue.push(Event::EditConnectionStarted(conn.as_ref().clone()));
             }
             Some(ConnScreenAction::FocusConnNameInput) => {
                 self.conn_name_input.focus();
@@ -178,14 +348,59 @@ impl Component for ConnectionScreen {
             }
             Some(ConnScreenAction::FocusConnStrInput) => {
                 self.conn_name_input.stop_editing();
+                self.conn_ssh_input.stop_editing();
                 self.conn_str_input.focus();
                 self.conn_str_input.start_editing();
                 queue.push(Event::FocusedChanged);
             }
+            Some(ConnScreenAction::FocusConnSshInput) => {
+                self.conn_str_input.stop_editing();
+                self.conn_ca_input.stop_editing();
+                self.conn_ssh_input.focus();
+                self.conn_ssh_input.start_editing();
+                queue.push(Event::FocusedChanged);
+            }
+            Some(ConnScreenAction::FocusConnTlsInput(field)) => {
+                self.conn_ssh_input.stop_editing();
+                self.conn_ca_input.stop_editing();
+                self.conn_cert_input.stop_editing();
+                self.conn_tls_options_input.stop_editing();
+                self.conn_folder_input.stop_editing();
+
+                let input = self.tls_input(*field);
+                input.focus();
+                input.start_editing();
+                queue.push(Event::FocusedChanged);
+            }
+            Some(ConnScreenAction::FocusConnDetailInput(detail)) => {
+                self.conn_tls_options_input.stop_editing();
+                self.conn_folder_input.stop_editing();
+                self.conn_tags_input.stop_editing();
+                self.conn_env_input.stop_editing();
+
+                let input = self.details_input(*detail);
+                input.focus();
+                input.start_editing();
+                queue.push(Event::FocusedChanged);
+            }
             Some(ConnScreenAction::FinishEditingConn) => {
+                // the inputs check the tunnel, TLS options, and environment before
+                // asking to finish
+                let ssh_tunnel = self.conn_ssh_input.tunnel().ok().flatten();
+                let tls = self.tls_settings();
+                let folder = self.conn_folder_input.folder();
+                let tags = self.conn_tags_input.tags();
+                let environment = self.conn_env_input.environment().ok().flatten();
+                self.conn_env_input.stop_editing();
+
                 if let Some(mut editing_conn) = self.editing_conn.take() {
                     editing_conn.name = self.conn_name_input.value().to_string();
                     editing_conn.connection_str = self.conn_str_input.value().to_string();
+                    editing_conn.ssh_tunnel = ssh_tunnel;
+                    editing_conn.tls = tls;
+                    editing_conn.folder = folder;
+                    editing_conn.tags = tags;
+                    editing_conn.environment = environment;
 
                     // update the connection in storage
                     self.connection_manager
@@ -197,10 +412,15 @@ impl Component for ConnectionScreen {
                     self.conn_list.focus();
                     queue.push(Event::ConnectionUpdated(editing_conn));
                 } else {
-                    let conn = Connection::new(
+                    let mut conn = Connection::new(
                         self.conn_name_input.value().to_string(),
                         self.conn_str_input.value().to_string(),
                     );
+                    conn.ssh_tunnel = ssh_tunnel;
+                    conn.tls = tls;
+                    conn.folder = folder;
+                    conn.tags = tags;
+                    conn.environment = environment;
                     queue.push(Event::ConnectionCreated(conn.clone()));
                     queue.push(Message::to_client(ClientAction::Connect(conn)));
                 }
@@ -219,9 +439,7 @@ impl Component for ConnectionScreen {
         self.conn_list.render(frame, area);
 
         // render new connection inputs in an overlay
-        if self.internal_focus() == Some(ConnScrFocus::NameIn)
-            || self.internal_focus() == Some(ConnScrFocus::StringIn)
-        {
+        if self.is_editing() {
             let horiz_layout = Layout::horizontal([CONN_EDITOR_WIDTH])
                 .flex(Flex::Center)
                 .split(area);
@@ -230,7 +448,7 @@ impl Component for ConnectionScreen {
                 .split(horiz_layout[0]);
             let overlay = vert_layout[0];
 
-            let inputs_layout = Layout::vertical(vec![3, 2, 3])
+            let inputs_layout = Layout::vertical(vec![3, 1, 3, 1, 3, 1, 3, 1, 3, 1, 8])
                 .flex(Flex::Center)
                 .split(overlay.inner(Margin::new(2, 2)));
 
@@ -250,6 +468,27 @@ impl Component for ConnectionScreen {
 
             self.conn_name_input.render(frame, inputs_layout[0]);
             self.conn_str_input.render(frame, inputs_layout[2]);
+            self.conn_ssh_input.render(frame, inputs_layout[4]);
+
+            let tls_layout = Layout::horizontal([Constraint::Fill(1); 3])
+                .spacing(1)
+                .split(inputs_layout[6]);
+            self.conn_ca_input.render(frame, tls_layout[0]);
+            self.conn_cert_input.render(frame, tls_layout[1]);
+            self.conn_tls_options_input.render(frame, tls_layout[2]);
+
+            let details_layout = Layout::horizontal([
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Length(15),
+            ])
+            .spacing(1)
+            .split(inputs_layout[8]);
+            self.conn_folder_input.render(frame, details_layout[0]);
+            self.conn_tags_input.render(frame, details_layout[1]);
+            self.conn_env_input.render(frame, details_layout[2]);
+
+            self.conn_str_input.render_preview(frame, inputs_layout[10]);
         }
     }
 
@@ -285,6 +524,7 @@ impl PersistedComponent for ConnectionScreen {
 mod tests {
     use super::*;
     use crate::{
+        model::connection::Environment,
         system::command::Command,
         testing::{mock_storage::MockStorage, ComponentTestHarness},
     };
@@ -304,6 +544,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_conn_while_editing() {
+        let mut test = ComponentTestHarness::new(ConnectionScreen::new_mock(vec![]));
+        assert!(!test
+            .component()
+            .commands()
+            .iter()
+            .any(|group| group.commands.contains(&Command::TestConnection)));
+
+        test.given_command(Command::CreateNew);
+        test.given_string("local");
+        test.given_command(Command::Confirm);
+        test.given_string("mongodb://localhost");
+        assert!(test
+            .component()
+            .commands()
+            .iter()
+            .any(|group| group.commands.contains(&Command::TestConnection)));
+
+        test.given_command(Command::TestConnection);
+        test.expect_message(|m| {
+            matches!(
+                m.read_as_client(),
+                Some(ClientAction::TestConnection(conn))
+                    if conn.connection_str == "mongodb://localhost"
+            )
+        });
+    }
+
     #[test]
     fn create_new_conn() {
         let mut test = ComponentTestHarness::new(ConnectionScreen::new_mock(vec![]));
@@ -317,7 +586,37 @@ mod tests {
         test.given_string("url");
         test.given_command(Command::Confirm);
 
-        test.expect_event(|e| matches!(e, Event::ConnectionCreated(c) if c.name == "local"));
+        // ssh tunnel
+        test.given_string("ssh://me@bastion");
+        test.given_command(Command::Confirm);
+
+        // CA file, client certificate, and TLS options
+        test.given_string("~/certs/ca.pem");
+        test.given_command(Command::Confirm);
+        test.given_string("~/certs/client.pem");
+        test.given_command(Command::Confirm);
+        test.given_string("x509");
+        test.given_command(Command::Confirm);
+
+        // folder, tags, and environment
+        test.given_string("work/");
+        test.given_command(Command::Confirm);
+        test.given_string("analytics, replica");
+        test.given_command(Command::Confirm);
+        test.given_string("prod");
+        test.given_command(Command::Confirm);
+
+        test.expect_event(|e| {
+            matches!(e, Event::ConnectionCreated(c)
+                if c.name == "local"
+                    && c.ssh_tunnel.is_some()
+                    && c.tls.as_ref().is_some_and(|tls| tls.x509
+                        && tls.ca_file.as_deref() == Some("~/certs/ca.pem")
+                        && tls.cert_key_file.as_deref() == Some("~/certs/client.pem"))
+                    && c.folder.as_deref() == Some("work")
+                    && c.tags == ["analytics", "replica"]
+                    && c.environment == Some(Environment::Prod))
+        });
         test.expect_message(|m| {
             matches!(
                 m.read_as_client(),
@@ -349,8 +648,17 @@ mod tests {
         test.given_string("new_url");
         test.given_command(Command::Confirm);
 
+        // leave the ssh tunnel, TLS settings, folder, tags, and environment empty
+        test.given_command(Command::Confirm);
+        test.given_command(Command::Confirm);
+        test.given_command(Command::Confirm);
+        test.given_command(Command::Confirm);
+        test.given_command(Command::Confirm);
+        test.given_command(Command::Confirm);
+        test.given_command(Command::Confirm);
+
         test.expect_event(
-            |e| matches!(e, Event::ConnectionUpdated(c) if c.connection_str == "new_url" && c.id() == connection.id()),
+            |e| matches!(e, Event::ConnectionUpdated(c) if c.connection_str == "new_url" && c.id() == connection.id() && c.tls.is_none()),
         );
     }
 }
@@ -0,0 +1,267 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Clear, Paragraph},
+};
+use std::{cell::Cell, rc::Rc};
+
+use crate::{
+    components::{tab::TabFocus, Component},
+    config::{color_map::ColorKey, Config},
+    system::{
+        command::{Command, CommandCategory, CommandGroup},
+        event::Event,
+        message::{ClientAction, Message},
+        signal::SignalQueue,
+    },
+    utils::{
+        doc_diff::{
+            bson_preview, bson_type_name, diff_docs, update_for_changes, FieldChange,
+            FieldChangeKind,
+        },
+        edit_doc::edit_doc,
+    },
+};
+use mongodb::bson::Document;
+
+use super::tab::CloneWithFocus;
+
+const DIFF_MODAL_WIDTH: u16 = 80;
+const DIFF_MODAL_MAX_HEIGHT: u16 = 20;
+
+#[derive(Debug, Default, Clone)]
+pub struct DiffModal {
+    focus: Rc<Cell<TabFocus>>,
+    config: Config,
+
+    original: Document,
+    edited: Document,
+    changes: Vec<FieldChange>,
+
+    scroll: u16,
+}
+
+impl CloneWithFocus for DiffModal {
+    fn clone_with_focus(&self, focus: Rc<Cell<TabFocus>>) -> Self {
+        Self {
+            focus,
+            ..self.clone()
+        }
+    }
+}
+
+impl DiffModal {
+    pub fn new(focus: Rc<Cell<TabFocus>>, config: Config) -> Self {
+        Self {
+            focus,
+            config,
+            ..Default::default()
+        }
+    }
+
+    pub fn show_with(&mut self, original: Document, edited: Document) {
+        self.original = original;
+        self.set_edited(edited);
+        self.focus();
+    }
+
+    fn set_edited(&mut self, edited: Document) {
+        self.changes = diff_docs(&self.original, &edited);
+        self.edited = edited;
+        self.scroll = 0;
+    }
+}
+
+/// Renders a single field change as a line, colored by the kind of change.
+pub fn change_to_line(change: &FieldChange, config: &Config) -> Line<'static> {
+    let (symbol, color_key) = match change.kind() {
+        FieldChangeKind::Added => ("+", ColorKey::DiffAdded),
+        FieldChangeKind::Removed => ("-", ColorKey::DiffRemoved),
+        FieldChangeKind::Changed => ("~", ColorKey::DiffChanged),
+    };
+    let color = config.color_map.get(&color_key);
+    let secondary = config.color_map.get(&ColorKey::FgSecondary);
+
+    let value_spans = |bson: &mongodb::bson::Bson| {
+        vec![
+            Span::from(bson_preview(bson)).fg(config.color_map.get(&ColorKey::FgPrimary)),
+            Span::from(format!(" ({})", bson_type_name(bson))).fg(secondary),
+        ]
+    };
+
+    let mut spans = vec![
+        Span::from(format!("{symbol} ")).fg(color).bold(),
+        Span::from(change.path.clone()).fg(config.color_map.get(&ColorKey::Key)),
+        Span::from(": ").fg(config.color_map.get(&ColorKey::Punctuation)),
+    ];
+
+    match (&change.old, &change.new) {
+        (Some(old), Some(new)) => {
+            spans.append(&mut value_spans(old));
+            spans.push(Span::from(" → ").fg(color));
+            spans.append(&mut value_spans(new));
+        }
+        (Some(value), None) | (None, Some(value)) => spans.append(&mut value_spans(value)),
+        (None, None) => {}
+    }
+
+    Line::from(spans)
+}
+
+impl Component for DiffModal {
+    fn is_focused(&self) -> bool {
+        self.focus.get() == TabFocus::DiffModal
+    }
+
+    fn focus(&self) {
+        self.focus.set(TabFocus::DiffModal);
+    }
+
+    fn commands(&self) -> Vec<CommandGroup> {
+        let mut out = vec![];
+
+        if !self.changes.is_empty() {
+            out.push(
+                CommandGroup::new(vec![Command::Confirm], "apply changes")
+                    .in_cat(CommandCategory::StatusBarOnly),
+            );
+        }
+
+        out.append(&mut vec![
+            CommandGroup::new(vec![Command::Edit], "edit again")
+                .in_cat(CommandCategory::StatusBarOnly),
+            CommandGroup::new(vec![Command::Back], "discard")
+                .in_cat(CommandCategory::StatusBarOnly),
+            CommandGroup::new(vec![Command::NavUp, Command::NavDown], "scroll"),
+        ]);
+
+        out
+    }
+
+    fn handle_command(&mut self, command: &Command, queue: &mut SignalQueue) {
+        match command {
+            Command::Confirm if !self.changes.is_empty() => {
+                let Some(id) = self.original.get("_id") else {
+                    queue.push(Event::ErrorOccurred(
+                        "Document does not have an `_id` field.".into(),
+                    ));
+                    return;
+                };
+                // only the changed fields are written, since the original may
+                // be missing fields because of a projection
+                queue.push(Message::to_client(ClientAction::UpdateFields {
+                    id: id.clone(),
+                    update: update_for_changes(&self.edited, &self.changes),
+                }));
+                queue.push(Event::EditReviewApplied);
+            }
+            Command::Edit => {
+                queue.push(Event::ReturnedFromAltScreen);
+                match edit_doc(self.edited.clone()) {
+                    Ok(new_doc) => self.set_edited(new_doc),
+                    Err(err) => queue.push(Event::ErrorOccurred(err.into())),
+                }
+            }
+            Command::Back => queue.push(Event::EditReviewDiscarded),
+            Command::NavUp => self.scroll = self.scroll.saturating_sub(1),
+            Command::NavDown => {
+                #[expect(clippy::cast_possible_truncation)]
+                let max_scroll = self.changes.len().saturating_sub(1) as u16;
+                self.scroll = (self.scroll + 1).min(max_scroll);
+            }
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let lines: Vec<_> = if self.changes.is_empty() {
+            vec![Line::from("(no changes)").fg(self.config.color_map.get(&ColorKey::FgSecondary))]
+        } else {
+            self.changes
+                .iter()
+                .map(|change| change_to_line(change, &self.config))
+                .collect()
+        };
+
+        #[expect(clippy::cast_possible_truncation)]
+        let height = (lines.len() as u16).clamp(1, DIFF_MODAL_MAX_HEIGHT);
+
+        let layout = Layout::vertical(vec![
+            Constraint::Fill(1),
+            Constraint::Length(height + 2),
+            Constraint::Fill(1),
+        ])
+        .split(area);
+        let layout = Layout::horizontal(vec![
+            Constraint::Fill(1),
+            Constraint::Length(DIFF_MODAL_WIDTH + 2),
+            Constraint::Fill(1),
+        ])
+        .split(layout[1]);
+
+        let num_changes = self.changes.len();
+        let change_word = if num_changes == 1 {
+            "change"
+        } else {
+            "changes"
+        };
+
+        let content = Paragraph::new(lines).scroll((self.scroll, 0)).block(
+            Block::bordered()
+                .border_style(self.config.color_map.get(&ColorKey::PopupBorder))
+                .title(" Review Changes ")
+                .title(Line::from(format!(" {num_changes} {change_word} ")).right_aligned())
+                .bg(self.config.color_map.get(&ColorKey::PopupBg)),
+        );
+
+        frame.render_widget(Clear, layout[1]);
+        frame.render_widget(content, layout[1]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::ComponentTestHarness;
+    use mongodb::bson::{doc, Bson};
+
+    #[test]
+    fn apply_changes() {
+        let mut test = ComponentTestHarness::new(DiffModal::default());
+        test.component_mut()
+            .show_with(doc! { "_id": 1, "a": 1 }, doc! { "_id": 1, "a": 2 });
+
+        test.given_command(Command::Confirm);
+
+        test.expect_message(|m| {
+            matches!(
+                m.read_as_client(),
+                Some(ClientAction::UpdateFields { id, update })
+                    if *id == Bson::Int32(1) && *update == doc! { "$set": { "a": 2 } }
+            )
+        });
+        test.expect_event(|e| matches!(e, Event::EditReviewApplied));
+    }
+
+    #[test]
+    fn discard_changes() {
+        let mut test = ComponentTestHarness::new(DiffModal::default());
+        test.component_mut()
+            .show_with(doc! { "_id": 1, "a": 1 }, doc! { "_id": 1, "a": 2 });
+
+        test.given_command(Command::Back);
+
+        test.expect_event(|e| matches!(e, Event::EditReviewDiscarded));
+        test.expect_no_messages();
+    }
+
+    #[test]
+    fn nothing_to_apply_without_changes() {
+        let mut test = ComponentTestHarness::new(DiffModal::default());
+        test.component_mut()
+            .show_with(doc! { "_id": 1, "a": 1 }, doc! { "_id": 1, "a": 1 });
+
+        test.given_command(Command::Confirm);
+
+        test.expect_no_messages();
+    }
+}
@@ -1,12 +1,14 @@
 use super::{
     confirm_modal::ConfirmKind,
+    input::input_modal::InputKind,
     primary_screen::PrimScrFocus,
+    query_input::QueryUpdate,
     tab::{CloneWithFocus, TabFocus},
     Component,
 };
 use crate::{
     config::{color_map::ColorKey, Config},
-    model::collection::Collection,
+    model::{collection::Collection, reference::Reference},
     persistence::PersistedComponent,
     system::{
         command::{Command, CommandCategory, CommandGroup},
@@ -16,11 +18,13 @@ use crate::{
     },
     utils::{
         clipboard::send_bson_to_clipboard,
-        doc_searcher::DocSearcher,
+        doc_searcher::{DocSearcher, SearchScope},
         edit_doc::edit_doc,
+        field_edit::{dotted_path, parse_field, parse_value, update_path, value_to_text},
         mongo_tree::{MongoKey, MongoTreeBuilder},
     },
 };
+use json::DocJson;
 use layout::Flex;
 use mongodb::bson::{doc, oid::ObjectId, Bson, Document};
 use ratatui::{
@@ -29,9 +33,13 @@ use ratatui::{
 };
 use serde::{Deserialize, Serialize};
 use std::{cell::Cell, rc::Rc};
+use table::{value_at_path, ColumnSettings, DocTable};
 use tui_input::{backend::crossterm::EventHandler, Input};
 use tui_tree_widget::{Tree, TreeItem, TreeState};
 
+mod json;
+mod table;
+
 #[derive(Debug, Default)]
 enum Mode {
     #[default]
@@ -40,6 +48,55 @@ enum Mode {
     SearchReview,
 }
 
+/// A change to part of a document that is waiting for the user to enter a
+/// value or confirm. Paths don't include the document's id.
+#[derive(Debug, Clone)]
+enum PendingEdit {
+    /// Replacing the value at a path.
+    Value {
+        id: Bson,
+        path: Vec<MongoKey>,
+        original: Bson,
+    },
+
+    /// Adding a field to the sub-document at a path.
+    Field { id: Bson, path: Vec<MongoKey> },
+
+    /// Adding an element to the array at a path.
+    Element { id: Bson, path: Vec<MongoKey> },
+
+    /// Removing a field or an array element, once it's confirmed.
+    Remove(ClientAction),
+}
+
+/// How the documents on the current page are displayed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum DocsView {
+    #[default]
+    Tree,
+    Table,
+    Json,
+}
+
+impl DocsView {
+    const fn next(self) -> Self {
+        match self {
+            Self::Tree => Self::Table,
+            Self::Table => Self::Json,
+            Self::Json => Self::Tree,
+        }
+    }
+
+    /// Describes the view that `ToggleView` switches to.
+    const fn toggle_label(self) -> &'static str {
+        match self.next() {
+            Self::Tree => "tree view",
+            Self::Table => "table view",
+            Self::Json => "json view",
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct Documents<'a> {
     focus: Rc<Cell<TabFocus>>,
@@ -49,6 +106,11 @@ pub struct Documents<'a> {
     items: Vec<TreeItem<'a, MongoKey>>,
     mongo_tree_builder: MongoTreeBuilder<'a>,
 
+    view: DocsView,
+    table: DocTable,
+    json: DocJson,
+    projection: Document,
+
     #[expect(clippy::struct_field_names)]
     documents: Vec<Bson>,
     collection: Option<Collection>,
@@ -56,10 +118,19 @@ pub struct Documents<'a> {
     page: usize,
     count: u64,
 
+    pending_edit: Option<PendingEdit>,
+
     // search things
     mode: Mode,
     search_input: Input,
     searcher: DocSearcher,
+
+    /// The match to select once the page with a result of a search across
+    /// every page has loaded.
+    pending_search_result: Option<Vec<MongoKey>>,
+
+    /// Hides the commands that write documents.
+    read_only: bool,
 }
 
 impl Clone for Documents<'_> {
@@ -74,13 +145,20 @@ impl Clone for Documents<'_> {
             state: TreeState::default(),
             items: self.items.clone(),
             mongo_tree_builder: self.mongo_tree_builder.clone(),
+            view: self.view,
+            table: self.table.clone(),
+            json: self.json.clone(),
+            projection: self.projection.clone(),
+            pending_edit: None,
             documents,
             collection: self.collection.clone(),
             mode: Mode::Normal,
             search_input: Input::default(),
             searcher,
+            pending_search_result: None,
             page: self.page,
             count: self.count,
+            read_only: self.read_only,
         };
         documents.reset_state();
         documents
@@ -124,23 +202,37 @@ impl Documents<'_> {
     fn set_docs(&mut self, docs: &Vec<Bson>, reset_state: bool) {
         self.documents.clone_from(docs);
         self.searcher.load_docs(docs);
+        self.build_items();
+
+        self.table.set_docs(docs, &self.projection, reset_state);
+        self.json.set_docs(docs, reset_state);
+
+        if reset_state {
+            self.reset_state();
+        }
+    }
 
-        let items: Vec<_> = docs
+    /// Builds the tree's items from the documents, highlighting the
+    /// characters that match the current search.
+    fn build_items(&mut self) {
+        let highlights = self.searcher.highlights();
+        self.items = self
+            .documents
             .iter()
             .filter_map(|bson| {
                 bson.as_document()
-                    .map(|doc| self.mongo_tree_builder.build_tree_item(doc))
+                    .map(|doc| self.mongo_tree_builder.build_tree_item(doc, &highlights))
             })
             .collect();
-
-        self.items = items;
-
-        if reset_state {
-            self.reset_state();
-        }
     }
 
     fn selected_doc_as_bson(&self) -> Option<&Bson> {
+        match self.view {
+            DocsView::Table => return self.documents.get(self.table.selected_doc_idx()?),
+            DocsView::Json => return self.documents.get(self.json.selected_doc_idx()?),
+            DocsView::Tree => {}
+        }
+
         let id = self.state.selected().first()?;
 
         self.items
@@ -157,10 +249,23 @@ impl Documents<'_> {
     // TODO: this definitely needs tests
     // TODO: ... and a better name
     fn selected_bson(&self) -> Option<&Bson> {
+        if self.view == DocsView::Table {
+            let doc = self.selected_doc()?;
+            return self
+                .table
+                .selected_path()
+                .and_then(|path| value_at_path(doc, path))
+                .or_else(|| self.selected_doc_as_bson());
+        }
+
         let mut bson = self.selected_doc_as_bson()?;
 
         // ignore the first element, which is always the doc id
-        let path = &self.state.selected()[1..];
+        let path = if self.view == DocsView::Json {
+            self.json.selected_path().get(1..)?
+        } else {
+            &self.state.selected()[1..]
+        };
 
         for key in path {
             match (bson, key) {
@@ -177,29 +282,474 @@ impl Documents<'_> {
         Some(bson)
     }
 
-    fn set_selection_to_search_match(&mut self) {
-        if let Some(keys) = self.searcher.current_match() {
-            tracing::trace!("selecting {keys:?}");
-            self.state.select(keys.clone());
+    /// Commands for the selected document, or the value selected in it.
+    fn selected_doc_commands(&self) -> Vec<CommandGroup> {
+        let Some(doc) = self.selected_doc_as_bson() else {
+            return vec![];
+        };
+
+        let path = self.selected_field_path();
+        let selected = value_at_keys(doc, &path).filter(|_| !path.is_empty());
+        let edit_label = match selected {
+            Some(Bson::Document(_) | Bson::Array(_)) | None => "edit document",
+            Some(_) => "edit value",
+        };
+        let delete_label = match (selected, path.last()) {
+            (None, _) => "delete document",
+            (Some(_), Some(MongoKey::Usize(_))) => "remove element",
+            (Some(_), _) => "remove field",
+        };
+        let add_label = match self.add_target() {
+            Some((_, true)) => "add element",
+            _ => "add field",
+        };
+
+        let mut groups = vec![];
+        if !self.read_only {
+            groups.append(&mut vec![
+                CommandGroup::new(vec![Command::DuplicateDoc], "duplicate document")
+                    .in_cat(CommandCategory::DocActions),
+                CommandGroup::new(vec![Command::Delete], delete_label)
+                    .in_cat(CommandCategory::DocActions),
+                CommandGroup::new(vec![Command::Edit], edit_label)
+                    .in_cat(CommandCategory::DocActions),
+                CommandGroup::new(vec![Command::AddField], add_label)
+                    .in_cat(CommandCategory::DocActions),
+            ]);
+        }
+        groups.append(&mut vec![
+            CommandGroup::new(vec![Command::Yank], "copy to clipboard")
+                .in_cat(CommandCategory::DocActions),
+            CommandGroup::new(vec![Command::Compare], "mark for comparison")
+                .in_cat(CommandCategory::DocActions),
+        ]);
+
+        if let Some(selected) = selected {
+            if !matches!(selected, Bson::Document(_) | Bson::Array(_)) {
+                groups.push(
+                    CommandGroup::new(vec![Command::FilterByValue], "filter by value")
+                        .in_cat(CommandCategory::DocActions),
+                );
+                groups.push(
+                    CommandGroup::new(vec![Command::FilterOutValue], "filter out value")
+                        .in_cat(CommandCategory::DocActions),
+                );
+            }
+            groups.push(
+                CommandGroup::new(vec![Command::SortByField], "sort by field")
+                    .in_cat(CommandCategory::DocActions),
+            );
+            groups.push(
+                CommandGroup::new(
+                    vec![Command::IncludeField, Command::ExcludeField],
+                    "include/exclude field",
+                )
+                .in_cat(CommandCategory::DocActions),
+            );
+        }
+
+        if self.selected_reference().is_some() {
+            groups.push(
+                CommandGroup::new(vec![Command::FollowReference], "follow reference")
+                    .in_cat(CommandCategory::DocActions),
+            );
+        }
+
+        groups
+    }
+
+    /// The reference that the selected value makes to another document: either
+    /// a field from the configured reference map, or a `DBRef` or `ObjectId`.
+    fn selected_reference(&self) -> Option<Reference> {
+        let keys = self.selected_field_path();
+        let value =
+            value_at_keys(self.selected_doc_as_bson()?, &keys).filter(|_| !keys.is_empty())?;
+
+        // array indices aren't part of the paths in the reference map
+        let field = keys
+            .iter()
+            .filter(|key| !matches!(key, MongoKey::Usize(_)))
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(".");
+
+        let target = self
+            .collection
+            .as_ref()
+            .and_then(|coll| self.config.reference_map.target(&coll.name, &field));
+        if let Some(target) = target {
+            return Some(Reference {
+                db: None,
+                coll: Some(target.coll.clone()),
+                field: target.field.clone(),
+                value: value.clone(),
+            });
+        }
+
+        // a document's own id doesn't point anywhere else
+        if field == "_id" {
+            return None;
+        }
+        Reference::from_value(value)
+    }
+
+    /// Builds a change to the query from the selected field, or `None` if no
+    /// field is selected.
+    fn query_update(&self, command: &Command) -> Option<QueryUpdate> {
+        let keys = self.selected_field_path();
+        let value =
+            value_at_keys(self.selected_doc_as_bson()?, &keys).filter(|_| !keys.is_empty())?;
+        let path = dotted_path(&keys);
+
+        let update = match command {
+            Command::FilterByValue | Command::FilterOutValue => {
+                if matches!(value, Bson::Document(_) | Bson::Array(_)) {
+                    return None;
+                }
+                QueryUpdate::Filter {
+                    path,
+                    value: value.clone(),
+                    negate: matches!(command, Command::FilterOutValue),
+                }
+            }
+            Command::SortByField => QueryUpdate::ToggleSort(path),
+            Command::IncludeField => QueryUpdate::Project {
+                path,
+                include: true,
+            },
+            Command::ExcludeField => QueryUpdate::Project {
+                path,
+                include: false,
+            },
+            _ => return None,
+        };
+
+        Some(update)
+    }
+
+    /// The path to the selected value within its document, not including the
+    /// document's id.
+    fn selected_field_path(&self) -> Vec<MongoKey> {
+        match self.view {
+            DocsView::Tree => self.state.selected().get(1..).unwrap_or_default().to_vec(),
+            DocsView::Json => self
+                .json
+                .selected_path()
+                .get(1..)
+                .unwrap_or_default()
+                .to_vec(),
+            DocsView::Table => self.table.selected_path().map_or_else(Vec::new, |path| {
+                path.split('.').map(|key| key.to_string().into()).collect()
+            }),
+        }
+    }
+
+    /// The document or array that a new field or element would be added to:
+    /// either the selected value, or the closest one that contains it.
+    fn add_target(&self) -> Option<(Vec<MongoKey>, bool)> {
+        let doc = self.selected_doc_as_bson()?;
+        let mut path = self.selected_field_path();
+        loop {
+            match value_at_keys(doc, &path) {
+                Some(Bson::Array(_)) => return Some((path, true)),
+                Some(Bson::Document(_)) => return Some((path, false)),
+                _ => {
+                    path.pop()?;
+                }
+            }
+        }
+    }
+
+    /// Starts editing the selected value in place, if it isn't a whole
+    /// document or array. Returns `true` if editing started.
+    fn start_value_edit(&mut self, queue: &mut SignalQueue) -> bool {
+        let path = self.selected_field_path();
+        let Some(doc) = self.selected_doc_as_bson() else {
+            return false;
+        };
+        let (Some(id), Some(value)) = (doc_id(doc), value_at_keys(doc, &path)) else {
+            return false;
+        };
+        if path.is_empty() || matches!(value, Bson::Document(_) | Bson::Array(_)) {
+            return false;
+        }
+
+        let text = value_to_text(value);
+        self.pending_edit = Some(PendingEdit::Value {
+            id,
+            path,
+            original: value.clone(),
+        });
+        queue.push(Message::to_tab(TabAction::RequestInputWithValue(
+            InputKind::EditValue,
+            text,
+        )));
+        true
+    }
 
-            // open the selected key and every parent so that the selected
-            // item is visible
-            for idx in (0..keys.len()).rev() {
-                let suffix = keys[0..idx].to_vec();
-                tracing::trace!("opening {suffix:?}");
+    /// The action that removes the selected field or array element, or an
+    /// error if it can't be removed.
+    fn removal_action(&self) -> Option<anyhow::Result<ClientAction>> {
+        let doc = self.selected_doc_as_bson()?;
+        let id = doc_id(doc)?;
+        let mut path = self.selected_field_path();
+        value_at_keys(doc, &path)?;
 
-                let not_already_open = self.state.open(suffix);
-                if !not_already_open {
-                    break;
+        if let Err(err) = update_path(&path) {
+            return Some(Err(err));
+        }
+
+        let last_key = path.pop()?;
+        let action = match (value_at_keys(doc, &path), last_key) {
+            (Some(Bson::Array(_)), MongoKey::Usize(_))
+                if path.iter().any(|key| matches!(key, MongoKey::Usize(_))) =>
+            {
+                return Some(Err(anyhow::anyhow!(
+                    "Elements can't be removed from an array that's inside another array"
+                )));
+            }
+            (Some(Bson::Array(_)), MongoKey::Usize(idx)) => ClientAction::RemoveElement {
+                id,
+                path: dotted_path(&path),
+                idx,
+            },
+            (_, last_key) => {
+                path.push(last_key);
+                let path = dotted_path(&path);
+                ClientAction::UpdateFields {
+                    id,
+                    update: doc! { "$unset": { path: "" } },
                 }
             }
+        };
+        Some(Ok(action))
+    }
+
+    /// Turns a pending edit and the value the user entered into an update.
+    fn pending_edit_update(edit: PendingEdit, text: &str) -> anyhow::Result<(Bson, Document)> {
+        match edit {
+            PendingEdit::Value { id, path, original } => {
+                let value = parse_value(text, Some(&original))?;
+                let path = update_path(&path)?;
+                Ok((id, doc! { "$set": { path: value } }))
+            }
+            PendingEdit::Field { id, mut path } => {
+                let (name, value) = parse_field(text)?;
+                path.push(name.into());
+                let path = update_path(&path)?;
+                Ok((id, doc! { "$set": { path: value } }))
+            }
+            PendingEdit::Element { id, path } => {
+                let value = parse_value(text, None)?;
+                let path = update_path(&path)?;
+                Ok((id, doc! { "$push": { path: value } }))
+            }
+            PendingEdit::Remove(_) => Err(anyhow::anyhow!("Removals are confirmed, not entered")),
+        }
+    }
 
-            self.state.open(keys.clone());
+    fn set_selection_to_search_match(&mut self) {
+        if let Some(keys) = self.searcher.current_match() {
+            let keys = keys.clone();
+            self.select_keys(&keys);
+        }
+    }
+
+    /// Selects the item at a path of keys, starting with a document's id.
+    fn select_keys(&mut self, keys: &[MongoKey]) {
+        if self.view == DocsView::Json {
+            self.json.select_path(keys);
+            return;
         }
+
+        tracing::trace!("selecting {keys:?}");
+        self.state.select(keys.to_vec());
+
+        // open the selected key and every parent so that the selected
+        // item is visible
+        for idx in (0..keys.len()).rev() {
+            let suffix = keys[0..idx].to_vec();
+            tracing::trace!("opening {suffix:?}");
+
+            let not_already_open = self.state.open(suffix);
+            if !not_already_open {
+                break;
+            }
+        }
+
+        self.state.open(keys.to_vec());
     }
 
     fn reset_search(&mut self) {
         self.search_input = Input::default();
+        self.searcher.update_pattern("");
+        self.build_items();
+    }
+
+    /// Shows the results of the search after its text, mode or scope changed.
+    fn refresh_search(&mut self, queue: &mut SignalQueue) {
+        self.set_selection_to_search_match();
+        self.build_items();
+        queue.push(Event::DocSearchUpdated);
+    }
+
+    /// The scope that `CycleSearchScope` switches to. Searching under a path
+    /// uses the selected value's path.
+    fn next_search_scope(&self) -> SearchScope {
+        match self.searcher.scope() {
+            SearchScope::All => SearchScope::Keys,
+            SearchScope::Keys => SearchScope::Values,
+            SearchScope::Values => {
+                let path = self.selected_field_path();
+                if path.is_empty() {
+                    SearchScope::All
+                } else {
+                    SearchScope::Path(path)
+                }
+            }
+            SearchScope::Path(_) => SearchScope::All,
+        }
+    }
+
+    /// Adds the search text and the number of matches to the bottom of the
+    /// documents view's border while searching.
+    fn with_search_titles<'b>(&self, block: Block<'b>) -> Block<'b> {
+        let match_n = self.searcher.match_idx() + 1;
+        let num_matches = self.searcher.num_matches();
+        let match_word = if num_matches == 1 { "match" } else { "matches" };
+        let search_label = format!(
+            "{} search of {}",
+            self.searcher.mode(),
+            self.searcher.scope()
+        );
+        match self.mode {
+            Mode::Normal => block,
+            Mode::SearchInput => block
+                .title_bottom(
+                    Line::from(format!(
+                        " {search_label}: \"{}\" ",
+                        self.search_input.value()
+                    ))
+                    .left_aligned()
+                    .fg(self.config.color_map.get(&ColorKey::DocumentsSearch)),
+                )
+                .title_bottom(
+                    Line::from(format!(" {num_matches} {match_word} "))
+                        .right_aligned()
+                        .fg(self.config.color_map.get(&ColorKey::DocumentsSearch)),
+                ),
+            Mode::SearchReview => block
+                .title_bottom(
+                    Line::from(format!(
+                        " {search_label}: \"{}\" ",
+                        self.search_input.value()
+                    ))
+                    .left_aligned()
+                    .fg(self.config.color_map.get(&ColorKey::DocumentsSearch)),
+                )
+                .title_bottom(
+                    Line::from(format!(" match {match_n} of {num_matches} "))
+                        .right_aligned()
+                        .fg(self.config.color_map.get(&ColorKey::DocumentsSearch)),
+                ),
+        }
+    }
+
+    fn table_view_commands() -> Vec<CommandGroup> {
+        vec![
+            CommandGroup::new(
+                vec![
+                    Command::NavLeft,
+                    Command::NavDown,
+                    Command::NavUp,
+                    Command::NavRight,
+                ],
+                "navigate",
+            )
+            .in_cat(CommandCategory::DocNav),
+            CommandGroup::new(
+                vec![Command::PreviousPage, Command::NextPage],
+                "previous/next page",
+            )
+            .in_cat(CommandCategory::DocNav),
+            CommandGroup::new(
+                vec![Command::FirstPage, Command::LastPage],
+                "first/last page",
+            )
+            .in_cat(CommandCategory::DocNav),
+            CommandGroup::new(vec![Command::ToggleView], DocsView::Table.toggle_label())
+                .in_cat(CommandCategory::DocNav),
+            CommandGroup::new(vec![Command::SortColumn], "sort by column")
+                .in_cat(CommandCategory::DocNav),
+            CommandGroup::new(vec![Command::HideColumn], "hide column")
+                .in_cat(CommandCategory::DocNav),
+            CommandGroup::new(vec![Command::Reset], "show hidden columns")
+                .in_cat(CommandCategory::DocNav),
+            CommandGroup::new(
+                vec![Command::ShrinkColumn, Command::GrowColumn],
+                "resize column",
+            )
+            .in_cat(CommandCategory::DocNav),
+            CommandGroup::new(vec![Command::Refresh], "refresh")
+                .in_cat(CommandCategory::DocActions),
+        ]
+    }
+
+    /// Handles the commands that behave differently in the JSON view.
+    /// Returns `true` if the command was handled.
+    fn handle_json_command(&mut self, command: &Command, queue: &mut SignalQueue) -> bool {
+        let selection_changed = match command {
+            Command::NavUp => self.json.nav_up(),
+            Command::NavDown => self.json.nav_down(),
+            Command::NavLeft => self.json.nav_left(),
+            Command::NavRight => self.json.nav_right(),
+            Command::ExpandCollapse => self.json.toggle_fold(),
+            _ => return false,
+        };
+
+        if selection_changed {
+            queue.push(Event::ListSelectionChanged);
+        }
+        true
+    }
+
+    /// Handles the commands that behave differently in the table view.
+    /// Returns `true` if the command was handled.
+    fn handle_table_command(&mut self, command: &Command, queue: &mut SignalQueue) -> bool {
+        let selection_changed = match command {
+            Command::NavUp => self.table.nav_up(),
+            Command::NavDown => self.table.nav_down(),
+            Command::NavLeft => self.table.nav_left(),
+            Command::NavRight => self.table.nav_right(),
+            Command::SortColumn => {
+                self.table.cycle_sort(&self.documents);
+                true
+            }
+            Command::HideColumn => {
+                self.table.hide_selected_column();
+                true
+            }
+            Command::Reset => {
+                self.table.show_all_columns();
+                false
+            }
+            Command::ShrinkColumn => {
+                self.table.resize_selected_column(false);
+                false
+            }
+            Command::GrowColumn => {
+                self.table.resize_selected_column(true);
+                false
+            }
+            // the tree's expand/collapse and search don't apply to the table
+            Command::ExpandCollapse | Command::Search => false,
+            _ => return false,
+        };
+
+        if selection_changed {
+            queue.push(Event::ListSelectionChanged);
+        }
+        true
     }
 }
 
@@ -223,7 +773,9 @@ impl Component for Documents<'_> {
             ];
         }
 
-        let mut out = if matches!(self.mode, Mode::Normal) {
+        let mut out = if self.view == DocsView::Table {
+            Self::table_view_commands()
+        } else if matches!(self.mode, Mode::Normal) {
             vec![
                 CommandGroup::new(
                     vec![
@@ -249,7 +801,8 @@ impl Component for Documents<'_> {
                 .in_cat(CommandCategory::DocNav),
                 CommandGroup::new(vec![Command::Refresh], "refresh")
                     .in_cat(CommandCategory::DocActions),
-                CommandGroup::new(vec![Command::Search], "fuzzy search")
+                CommandGroup::new(vec![Command::Search], "search").in_cat(CommandCategory::DocNav),
+                CommandGroup::new(vec![Command::ToggleView], self.view.toggle_label())
                     .in_cat(CommandCategory::DocNav),
             ]
         } else {
@@ -276,36 +829,43 @@ impl Component for Documents<'_> {
                     .in_cat(CommandCategory::StatusBarOnly),
                 CommandGroup::new(vec![Command::Back], "exit search")
                     .in_cat(CommandCategory::DocNav),
+                CommandGroup::new(vec![Command::Confirm], "search all pages")
+                    .in_cat(CommandCategory::DocNav),
+                CommandGroup::new(vec![Command::CycleSearchMode], "change search mode")
+                    .in_cat(CommandCategory::DocNav),
+                CommandGroup::new(vec![Command::CycleSearchScope], "change search scope")
+                    .in_cat(CommandCategory::DocNav),
                 CommandGroup::new(vec![Command::Refresh], "refresh")
                     .in_cat(CommandCategory::DocActions),
-                CommandGroup::new(vec![Command::Search], "fuzzy search")
-                    .in_cat(CommandCategory::DocNav),
+                CommandGroup::new(vec![Command::Search], "search").in_cat(CommandCategory::DocNav),
             ]
         };
 
-        out.push(
-            CommandGroup::new(vec![Command::CreateNew], "insert document")
-                .in_cat(CommandCategory::DocActions),
-        );
-
-        if self.selected_doc().is_some() {
+        if !self.read_only {
             out.append(&mut vec![
-                CommandGroup::new(vec![Command::DuplicateDoc], "duplicate document")
-                    .in_cat(CommandCategory::DocActions),
-                CommandGroup::new(vec![Command::Delete], "delete document")
+                CommandGroup::new(vec![Command::CreateNew], "insert document")
                     .in_cat(CommandCategory::DocActions),
-                CommandGroup::new(vec![Command::Edit], "edit document")
-                    .in_cat(CommandCategory::DocActions),
-                CommandGroup::new(vec![Command::Yank], "copy to clipboard")
+                CommandGroup::new(vec![Command::Undo], "undo last write")
                     .in_cat(CommandCategory::DocActions),
             ]);
         }
 
+        out.append(&mut self.selected_doc_commands());
+
         out
     }
 
     #[expect(clippy::too_many_lines)]
     fn handle_command(&mut self, command: &Command, queue: &mut SignalQueue) {
+        let handled = match self.view {
+            DocsView::Tree => false,
+            DocsView::Table => self.handle_table_command(command, queue),
+            DocsView::Json => self.handle_json_command(command, queue),
+        };
+        if handled {
+            return;
+        }
+
         match command {
             Command::NavLeft => {
                 if self.state.key_left() {
@@ -377,6 +937,10 @@ impl Component for Documents<'_> {
                 queue.push(Message::to_client(ClientAction::RefreshQueries));
             }
             Command::Edit => {
+                if self.start_value_edit(queue) {
+                    return;
+                }
+
                 let Some(doc) = self.selected_doc() else {
                     return;
                 };
@@ -384,7 +948,10 @@ impl Component for Documents<'_> {
                 queue.push(Event::ReturnedFromAltScreen);
                 match edit_doc(doc.clone()) {
                     Ok(new_doc) => {
-                        queue.push(Message::to_client(ClientAction::UpdateDoc(new_doc)));
+                        queue.push(Message::to_tab(TabAction::RequestEditReview {
+                            original: doc.clone(),
+                            edited: new_doc,
+                        }));
                     }
                     Err(err) => queue.push(Event::ErrorOccurred(err.into())),
                 }
@@ -418,7 +985,56 @@ impl Component for Documents<'_> {
                 }
             }
             Command::Delete => {
-                queue.push(Message::to_tab(TabAction::RequestConfirmation(ConfirmKind::DeleteDoc)));
+                let kind = match self.removal_action() {
+                    Some(Ok(action)) => {
+                        self.pending_edit = Some(PendingEdit::Remove(action));
+                        ConfirmKind::RemoveField
+                    }
+                    Some(Err(err)) => {
+                        queue.push(Event::ErrorOccurred(err.to_string().into()));
+                        return;
+                    }
+                    None => ConfirmKind::DeleteDoc,
+                };
+                queue.push(Message::to_tab(TabAction::RequestConfirmation(kind)));
+            }
+            Command::AddField => {
+                let id = self.selected_doc_as_bson().and_then(doc_id);
+                if let (Some(id), Some((path, is_array))) = (id, self.add_target()) {
+                    let (edit, kind) = if is_array {
+                        (
+                            PendingEdit::Element { id, path },
+                            InputKind::NewArrayElement,
+                        )
+                    } else {
+                        (PendingEdit::Field { id, path }, InputKind::NewField)
+                    };
+                    self.pending_edit = Some(edit);
+                    queue.push(Message::to_tab(TabAction::RequestInput(kind)));
+                }
+            }
+            Command::FilterByValue
+            | Command::FilterOutValue
+            | Command::SortByField
+            | Command::IncludeField
+            | Command::ExcludeField => {
+                if let Some(update) = self.query_update(command) {
+                    queue.push(Message::to_prim_scr(PrimScreenAction::UpdateQuery(update)));
+                }
+            }
+            Command::FollowReference => {
+                if let Some(reference) = self.selected_reference() {
+                    queue.push(Message::to_client(ClientAction::FollowReference(reference)));
+                }
+            }
+            Command::Undo => {
+                queue.push(Message::to_client(ClientAction::UndoLastWrite));
+            }
+            Command::Compare => {
+                if let Some(doc) = self.selected_doc() {
+                    let action = AppAction::MarkDocForComparison(doc.clone());
+                    queue.push(Message::to_app(action));
+                }
             }
             Command::Yank => {
                 if let Some(bson) = self.selected_bson() {
@@ -427,18 +1043,41 @@ impl Component for Documents<'_> {
                     }
                 }
             }
+            Command::ToggleView => {
+                if matches!(self.mode, Mode::Normal) {
+                    self.view = self.view.next();
+                    queue.push(Event::ListSelectionChanged);
+                }
+            }
             Command::Search => {
                 self.mode = Mode::SearchInput;
                 queue.push(Message::to_app(AppAction::EnterRawMode));
             }
+            Command::CycleSearchMode => {
+                self.searcher.set_mode(self.searcher.mode().next());
+                self.refresh_search(queue);
+            }
+            Command::CycleSearchScope => {
+                self.searcher.set_scope(self.next_search_scope());
+                self.refresh_search(queue);
+            }
 
-            // only in search input mode
-            Command::Confirm => {
-                if matches!(self.mode, Mode::SearchInput) {
+            // search input mode or search review mode
+            Command::Confirm => match self.mode {
+                Mode::SearchInput => {
                     self.mode = Mode::SearchReview;
                     queue.push(Message::to_app(AppAction::ExitRawMode));
                 }
-            }
+                Mode::SearchReview => {
+                    let text = self.search_input.value();
+                    if !text.is_empty() {
+                        queue.push(Message::to_client(ClientAction::SearchAll(
+                            text.to_string(),
+                        )));
+                    }
+                }
+                Mode::Normal => {}
+            },
 
             // search input mode or search review mode
             Command::Back => match self.mode {
@@ -454,7 +1093,9 @@ impl Component for Documents<'_> {
                     queue.push(Event::DocSearchUpdated);
                 }
                 Mode::Normal => {
-                    queue.push(Message::to_prim_scr(PrimScreenAction::SetFocus(PrimScrFocus::CollList)));
+                    queue.push(Message::to_prim_scr(PrimScreenAction::SetFocus(
+                        PrimScrFocus::CollList,
+                    )));
                 }
             },
             _ => {}
@@ -465,27 +1106,62 @@ impl Component for Documents<'_> {
         if matches!(self.mode, Mode::SearchInput) {
             self.search_input.handle_event(event);
             self.searcher.update_pattern(self.search_input.value());
-            self.set_selection_to_search_match();
-            queue.push(Event::DocSearchUpdated);
+            self.refresh_search(queue);
         }
     }
 
     fn handle_event(&mut self, event: &Event, queue: &mut SignalQueue) {
         match event {
+            Event::ReadOnlyModeChanged(read_only) => self.read_only = *read_only,
             Event::DocumentsUpdated { docs, reset_state } => {
                 self.set_docs(docs, *reset_state);
+                if let Some(keys) = self.pending_search_result.take() {
+                    self.select_keys(&keys);
+                }
                 queue.push(Event::ListSelectionChanged);
             }
+            Event::SearchResultOpened(result) => {
+                self.mode = Mode::Normal;
+                self.reset_search();
+                queue.push(Event::DocSearchUpdated);
+
+                let mut keys = vec![MongoKey::from(&result.id)];
+                keys.extend(result.path.iter().cloned());
+                self.pending_search_result = Some(keys);
+                queue.push(Event::DocumentPageChanged(result.page));
+            }
             Event::CountUpdated(count) => {
                 self.count = *count;
             }
+            Event::DocProjectionUpdated(projection) => {
+                self.projection.clone_from(projection);
+            }
             Event::ConfirmYes(Command::Delete) => {
                 if self.is_focused() {
-                    if let Some(doc) = self.selected_doc() {
+                    if let Some(PendingEdit::Remove(action)) = self.pending_edit.take() {
+                        queue.push(Message::to_client(action));
+                    } else if let Some(doc) = self.selected_doc() {
                         queue.push(Message::to_client(ClientAction::DeleteDoc(doc.clone())));
                     }
                 }
             }
+            Event::InputConfirmed(
+                InputKind::EditValue | InputKind::NewField | InputKind::NewArrayElement,
+                text,
+            ) => {
+                if let Some(edit) = self.pending_edit.take() {
+                    match Self::pending_edit_update(edit, text) {
+                        Ok((id, update)) => {
+                            queue.push(Message::to_client(ClientAction::UpdateFields {
+                                id,
+                                update,
+                            }));
+                        }
+                        Err(err) => queue.push(Event::ErrorOccurred(err.to_string().into())),
+                    }
+                }
+            }
+            Event::InputCanceled | Event::ConfirmNo => self.pending_edit = None,
             Event::DocumentPageChanged(page) => {
                 self.page = *page;
             }
@@ -548,40 +1224,25 @@ impl Component for Documents<'_> {
         let title_left = format!("Documents in '{}'", coll.name);
         let title_right = format!("{start}-{end} of {}", self.count);
 
-        let mut block = Block::bordered()
+        let block = Block::bordered()
             .bg(bg_color)
             .title(Line::from(format!(" {title_left} ")).left_aligned())
             .title(Line::from(format!(" {title_right} ")).right_aligned())
             .border_style(Style::default().fg(border_color));
+        let block = self.with_search_titles(block);
 
-        let match_n = self.searcher.match_idx() + 1;
-        let num_matches = self.searcher.num_matches();
-        let match_word = if num_matches == 1 { "match" } else { "matches" };
-        block = match self.mode {
-            Mode::Normal => block,
-            Mode::SearchInput => block
-                .title_bottom(
-                    Line::from(format!(" search: \"{}\" ", self.search_input.value()))
-                        .left_aligned()
-                        .fg(self.config.color_map.get(&ColorKey::DocumentsSearch)),
-                )
-                .title_bottom(
-                    Line::from(format!(" {num_matches} {match_word} "))
-                        .right_aligned()
-                        .fg(self.config.color_map.get(&ColorKey::DocumentsSearch)),
-                ),
-            Mode::SearchReview => block
-                .title_bottom(
-                    Line::from(format!(" search: \"{}\" ", self.search_input.value()))
-                        .left_aligned()
-                        .fg(self.config.color_map.get(&ColorKey::DocumentsSearch)),
-                )
-                .title_bottom(
-                    Line::from(format!(" match {match_n} of {num_matches} "))
-                        .right_aligned()
-                        .fg(self.config.color_map.get(&ColorKey::DocumentsSearch)),
-                ),
-        };
+        match self.view {
+            DocsView::Table => {
+                self.table
+                    .render(frame, area, block, &self.documents, &self.config);
+                return;
+            }
+            DocsView::Json => {
+                self.json.render(frame, area, block, &self.config);
+                return;
+            }
+            DocsView::Tree => {}
+        }
 
         let widget = Tree::new(&self.items)
             .expect("all item identifiers are unique")
@@ -604,6 +1265,22 @@ impl Component for Documents<'_> {
     }
 }
 
+fn doc_id(doc: &Bson) -> Option<Bson> {
+    doc.as_document()?.get("_id").cloned()
+}
+
+/// Gets the value at a path of keys within a document, if there is one.
+fn value_at_keys<'b>(mut bson: &'b Bson, path: &[MongoKey]) -> Option<&'b Bson> {
+    for key in path {
+        bson = match (bson, key) {
+            (Bson::Document(doc), MongoKey::String(key)) => doc.get(key)?,
+            (Bson::Array(array), MongoKey::Usize(idx)) => array.get(*idx)?,
+            _ => return None,
+        };
+    }
+    Some(bson)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PersistedDocuments {
     selection: Vec<MongoKey>,
@@ -611,6 +1288,10 @@ pub struct PersistedDocuments {
     docs: Vec<Bson>,
     collection: Option<Collection>,
     count: u64,
+    #[serde(default)]
+    view: DocsView,
+    #[serde(default)]
+    columns: ColumnSettings,
 }
 
 impl PersistedComponent for Documents<'_> {
@@ -623,12 +1304,16 @@ impl PersistedComponent for Documents<'_> {
             docs: self.documents.clone(),
             collection: self.collection.clone(),
             count: self.count,
+            view: self.view,
+            columns: self.table.settings(),
         }
     }
 
     fn hydrate(&mut self, storage: Self::StorageType) {
         self.page = storage.page;
         self.count = storage.count;
+        self.view = storage.view;
+        self.table.set_settings(storage.columns);
         self.set_docs(&storage.docs, true);
         self.collection = storage.collection;
 
@@ -644,10 +1329,13 @@ mod tests {
 
     use super::*;
     use crate::{
+        client::search::SearchResult,
+        config::RawConfig,
         model::{connection::Connection, database::Database},
         testing::ComponentTestHarness,
     };
     use mongodb::bson::bson;
+    use std::collections::HashMap;
 
     #[test]
     fn select_first_item_on_new_data() {
@@ -667,6 +1355,266 @@ mod tests {
         );
     }
 
+    #[test]
+    fn yank_selected_cell_in_table_view() {
+        let mut test = ComponentTestHarness::new(Documents::default());
+        test.given_event(Event::DocumentsUpdated {
+            docs: vec![
+                bson!({ "_id": 1, "name": "a" }),
+                bson!({ "_id": 2, "name": "b" }),
+            ],
+            reset_state: true,
+        });
+
+        test.given_command(Command::ToggleView);
+        test.given_command(Command::NavDown);
+        test.given_command(Command::NavRight);
+
+        assert_eq!(test.component().selected_bson(), Some(&Bson::from("b")));
+        assert_eq!(
+            test.component().selected_doc(),
+            Some(&doc! { "_id": 2, "name": "b" })
+        );
+    }
+
+    #[test]
+    fn json_view_selects_value_under_cursor() {
+        let mut test = ComponentTestHarness::new(Documents::default());
+        test.given_event(Event::DocumentsUpdated {
+            docs: vec![bson!({ "_id": 1, "a": { "b": "c" } })],
+            reset_state: true,
+        });
+
+        test.given_command(Command::ToggleView);
+        test.given_command(Command::ToggleView);
+        for _ in 0..3 {
+            test.given_command(Command::NavDown);
+        }
+
+        assert_eq!(test.component().selected_bson(), Some(&Bson::from("c")));
+        assert_eq!(
+            test.component().selected_doc(),
+            Some(&doc! { "_id": 1, "a": { "b": "c" } })
+        );
+    }
+
+    fn tree_with_selection(
+        doc: Bson,
+        path: Vec<MongoKey>,
+    ) -> ComponentTestHarness<Documents<'static>> {
+        let mut test = ComponentTestHarness::new(Documents::default());
+        test.given_event(Event::DocumentsUpdated {
+            docs: vec![doc],
+            reset_state: true,
+        });
+        test.component_mut().state.select(path);
+        test.component().focus();
+        test
+    }
+
+    #[test]
+    fn edit_value_in_place() {
+        let mut test = tree_with_selection(
+            bson!({ "_id": 1, "a": { "b": Bson::Int64(5) } }),
+            vec![
+                MongoKey::Usize(1),
+                "a".to_string().into(),
+                "b".to_string().into(),
+            ],
+        );
+
+        test.given_command(Command::Edit);
+        test.expect_message(|m| {
+            matches!(
+                m.read_as_tab(),
+                Some(TabAction::RequestInputWithValue(InputKind::EditValue, value))
+                    if value == "NumberLong(5)"
+            )
+        });
+
+        test.given_event(Event::InputConfirmed(InputKind::EditValue, "6".to_string()));
+        test.expect_message(|m| {
+            matches!(
+                m.read_as_client(),
+                Some(ClientAction::UpdateFields { id, update })
+                    if *id == Bson::Int32(1)
+                        && *update == doc! { "$set": { "a.b": Bson::Int64(6) } }
+            )
+        });
+    }
+
+    #[test]
+    fn follow_dbref() {
+        let mut test = tree_with_selection(
+            bson!({ "_id": 1, "owner": { "$ref": "users", "$id": 7 } }),
+            vec![MongoKey::Usize(1), "owner".to_string().into()],
+        );
+
+        test.given_command(Command::FollowReference);
+        test.expect_message(|m| {
+            matches!(
+                m.read_as_client(),
+                Some(ClientAction::FollowReference(Reference { db: None, coll: Some(coll), field, value }))
+                    if coll == "users" && field == "_id" && *value == Bson::Int32(7)
+            )
+        });
+    }
+
+    #[test]
+    fn follow_configured_reference() {
+        let raw_config = RawConfig {
+            references: HashMap::from([(
+                "orders.items.sku".to_string(),
+                "products.sku".to_string(),
+            )]),
+            ..Default::default()
+        };
+        let config = Config::try_from(raw_config).expect("references should be valid");
+        let mut test = ComponentTestHarness::new(Documents::new(Rc::default(), config));
+        test.given_event(Event::CollectionSelected(Collection::new("orders".into())));
+        test.given_event(Event::DocumentsUpdated {
+            docs: vec![bson!({ "_id": 1, "items": [{ "sku": "abc" }] })],
+            reset_state: true,
+        });
+        test.component_mut().state.select(vec![
+            MongoKey::Usize(1),
+            "items".to_string().into(),
+            MongoKey::Usize(0),
+            "sku".to_string().into(),
+        ]);
+        test.component().focus();
+
+        test.given_command(Command::FollowReference);
+        test.expect_message(|m| {
+            matches!(
+                m.read_as_client(),
+                Some(ClientAction::FollowReference(Reference { coll: Some(coll), field, .. }))
+                    if coll == "products" && field == "sku"
+            )
+        });
+    }
+
+    #[test]
+    fn filter_by_selected_value() {
+        let mut test = tree_with_selection(
+            bson!({ "_id": 1, "a": { "b": "x" } }),
+            vec![
+                MongoKey::Usize(1),
+                "a".to_string().into(),
+                "b".to_string().into(),
+            ],
+        );
+
+        test.given_command(Command::FilterOutValue);
+        test.expect_message(|m| {
+            matches!(
+                m.read_as_prim_scr(),
+                Some(PrimScreenAction::UpdateQuery(QueryUpdate::Filter { path, value, negate: true }))
+                    if path == "a.b" && *value == Bson::String("x".into())
+            )
+        });
+    }
+
+    #[test]
+    fn open_search_result_on_another_page() {
+        let mut test = ComponentTestHarness::new(Documents::default());
+
+        test.given_event(Event::SearchResultOpened(SearchResult {
+            page: 2,
+            id: Bson::Int32(7),
+            path: vec!["a".to_string().into(), "b".to_string().into()],
+            value: "x".to_string(),
+        }));
+        test.expect_event(|e| matches!(e, Event::DocumentPageChanged(2)));
+
+        test.given_event(Event::DocumentsUpdated {
+            docs: vec![bson!({ "_id": 6 }), bson!({ "_id": 7, "a": { "b": "x" } })],
+            reset_state: true,
+        });
+        assert_eq!(
+            test.component().state.selected(),
+            vec![
+                MongoKey::Usize(7),
+                "a".to_string().into(),
+                "b".to_string().into()
+            ]
+        );
+    }
+
+    #[test]
+    fn remove_array_element() {
+        let mut test = tree_with_selection(
+            bson!({ "_id": 1, "a": [1, null, 3] }),
+            vec![
+                MongoKey::Usize(1),
+                "a".to_string().into(),
+                MongoKey::Usize(2),
+            ],
+        );
+
+        test.given_command(Command::Delete);
+        test.expect_message(|m| {
+            matches!(
+                m.read_as_tab(),
+                Some(TabAction::RequestConfirmation(ConfirmKind::RemoveField))
+            )
+        });
+
+        test.given_event(Event::ConfirmYes(Command::Delete));
+        test.expect_message(|m| {
+            matches!(
+                m.read_as_client(),
+                Some(ClientAction::RemoveElement { id, path, idx })
+                    if *id == Bson::Int32(1) && path == "a" && *idx == 2
+            )
+        });
+
+        // a pipeline can't reach into an array by index
+        let mut test = tree_with_selection(
+            bson!({ "_id": 1, "a": [[1, 2]] }),
+            vec![
+                MongoKey::Usize(1),
+                "a".to_string().into(),
+                MongoKey::Usize(0),
+                MongoKey::Usize(1),
+            ],
+        );
+        test.given_command(Command::Delete);
+        test.expect_event(|e| matches!(e, Event::ErrorOccurred(..)));
+    }
+
+    #[test]
+    fn add_element_to_array() {
+        let mut test = tree_with_selection(
+            bson!({ "_id": 1, "a": [1, 2, 3] }),
+            vec![
+                MongoKey::Usize(1),
+                "a".to_string().into(),
+                MongoKey::Usize(2),
+            ],
+        );
+
+        test.given_command(Command::AddField);
+        test.expect_message(|m| {
+            matches!(
+                m.read_as_tab(),
+                Some(TabAction::RequestInput(InputKind::NewArrayElement))
+            )
+        });
+
+        test.given_event(Event::InputConfirmed(
+            InputKind::NewArrayElement,
+            "'x'".to_string(),
+        ));
+        test.expect_message(|m| {
+            matches!(
+                m.read_as_client(),
+                Some(ClientAction::UpdateFields { update, .. })
+                    if *update == doc! { "$push": { "a": "x" } }
+            )
+        });
+    }
+
     #[test]
     fn record_collection_changes() {
         let mut test = ComponentTestHarness::new(Documents::default());
@@ -698,6 +1646,7 @@ mod tests {
         let mut component = Documents {
             documents: docs,
             collection: Some(coll),
+            view: DocsView::Table,
             ..Default::default()
         };
         component
@@ -711,6 +1660,7 @@ mod tests {
 
         assert_eq!(component.documents, new_component.documents);
         assert_eq!(component.collection, new_component.collection);
+        assert_eq!(new_component.view, DocsView::Table);
 
         // FIXME: restore this
         // assert_eq!(component.state.selected(), new_component.state.selected());
@@ -0,0 +1,456 @@
+use crate::{
+    config::{color_map::ColorKey, Config},
+    utils::{
+        json_labeler::{JsonLabel, JsonLabeler, JsonLabels},
+        mongo_tree::MongoKey,
+    },
+};
+use mongodb::bson::Bson;
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
+};
+use std::collections::BTreeSet;
+
+const INDENT: &str = "  ";
+
+#[derive(Debug, Clone)]
+struct JsonLine {
+    /// Index of the document (in the page of documents) that this line is part of.
+    doc_idx: usize,
+
+    /// Path to the value on this line, starting with the document's id.
+    path: Vec<MongoKey>,
+
+    text: String,
+    labels: JsonLabels,
+
+    /// For lines that open a document or an array, the index of the line
+    /// that closes it.
+    fold_end: Option<usize>,
+}
+
+/// Shows a page of documents as pretty-printed, syntax-highlighted Extended
+/// JSON, with documents and arrays that can be folded.
+#[derive(Debug, Clone, Default)]
+pub struct DocJson {
+    labeler: JsonLabeler,
+    lines: Vec<JsonLine>,
+
+    /// Indices of the lines whose contents are folded away.
+    folded: BTreeSet<usize>,
+    cursor: usize,
+    scroll: usize,
+}
+
+impl DocJson {
+    pub fn set_docs(&mut self, docs: &[Bson], reset_state: bool) {
+        self.lines = vec![];
+        for (doc_idx, doc) in docs.iter().enumerate() {
+            let Some(doc) = doc.as_document() else {
+                continue;
+            };
+
+            let start = self.lines.len();
+            let id = doc
+                .get("_id")
+                .map_or(MongoKey::Usize(doc_idx), MongoKey::from);
+            push_lines(
+                &mut self.lines,
+                doc_idx,
+                vec![id],
+                None,
+                &Bson::Document(doc.clone()),
+                0,
+                false,
+            );
+
+            // label each document separately so that a parse error in one
+            // doesn't affect the others
+            let text = self.lines[start..]
+                .iter()
+                .map(|line| line.text.as_str())
+                .collect::<Vec<_>>()
+                .join("\n");
+            let labels = self.labeler.label_lines(&text).unwrap_or_default();
+            for (line, labels) in self.lines[start..].iter_mut().zip(labels) {
+                line.labels = labels;
+            }
+        }
+
+        if reset_state {
+            self.folded.clear();
+            self.cursor = 0;
+            self.scroll = 0;
+        } else {
+            let lines = &self.lines;
+            self.folded
+                .retain(|idx| lines.get(*idx).is_some_and(|line| line.fold_end.is_some()));
+            self.cursor = self.cursor.min(self.lines.len().saturating_sub(1));
+            if let Some(start) = self.enclosing_fold(self.cursor, true) {
+                self.cursor = start;
+            }
+        }
+    }
+
+    /// The index (in the page of documents) of the document under the cursor.
+    pub fn selected_doc_idx(&self) -> Option<usize> {
+        self.lines.get(self.cursor).map(|line| line.doc_idx)
+    }
+
+    /// The path of the value under the cursor, starting with the document's id.
+    pub fn selected_path(&self) -> &[MongoKey] {
+        self.lines
+            .get(self.cursor)
+            .map_or(&[], |line| line.path.as_slice())
+    }
+
+    /// Moves the cursor to the (first) line with the given path, unfolding
+    /// anything that would hide it.
+    pub fn select_path(&mut self, path: &[MongoKey]) {
+        let Some(target) = self.lines.iter().position(|line| line.path == path) else {
+            return;
+        };
+
+        let lines = &self.lines;
+        self.folded.retain(|start| {
+            lines[*start]
+                .fold_end
+                .is_none_or(|end| !(*start < target && target <= end))
+        });
+        self.cursor = target;
+    }
+
+    /// The indices of every line that isn't folded away.
+    fn visible_lines(&self) -> Vec<usize> {
+        let mut visible = vec![];
+        let mut idx = 0;
+        while let Some(line) = self.lines.get(idx) {
+            visible.push(idx);
+            idx = match line.fold_end {
+                Some(end) if self.folded.contains(&idx) => end + 1,
+                _ => idx + 1,
+            };
+        }
+        visible
+    }
+
+    /// The start of the innermost foldable region that contains the given
+    /// line (not counting a region that starts on the line itself). If
+    /// `folded_only` is set, only folded regions are considered.
+    fn enclosing_fold(&self, idx: usize, folded_only: bool) -> Option<usize> {
+        let mut enclosing = None;
+        for (start, line) in self.lines.iter().enumerate().take(idx) {
+            let contains = line.fold_end.is_some_and(|end| idx <= end);
+            if contains && (!folded_only || self.folded.contains(&start)) {
+                if folded_only {
+                    // the outermost folded region is the one that hides the line
+                    return Some(start);
+                }
+                enclosing = Some(start);
+            }
+        }
+        enclosing
+    }
+
+    fn is_foldable(&self, idx: usize) -> bool {
+        self.lines
+            .get(idx)
+            .is_some_and(|line| line.fold_end.is_some())
+    }
+
+    fn move_cursor(&mut self, forward: bool) -> bool {
+        let visible = self.visible_lines();
+        let Some(pos) = visible.iter().position(|idx| *idx == self.cursor) else {
+            return false;
+        };
+
+        let new_pos = if forward {
+            pos + 1
+        } else if let Some(pos) = pos.checked_sub(1) {
+            pos
+        } else {
+            return false;
+        };
+
+        match visible.get(new_pos) {
+            Some(idx) => {
+                self.cursor = *idx;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn nav_up(&mut self) -> bool {
+        self.move_cursor(false)
+    }
+
+    pub fn nav_down(&mut self) -> bool {
+        self.move_cursor(true)
+    }
+
+    /// Folds the region under the cursor, or moves to the start of the
+    /// enclosing region if there is nothing to fold.
+    pub fn nav_left(&mut self) -> bool {
+        if self.is_foldable(self.cursor) && !self.folded.contains(&self.cursor) {
+            self.folded.insert(self.cursor);
+            return true;
+        }
+
+        match self.enclosing_fold(self.cursor, false) {
+            Some(start) => {
+                self.cursor = start;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Unfolds the region under the cursor.
+    pub fn nav_right(&mut self) -> bool {
+        self.folded.remove(&self.cursor)
+    }
+
+    /// Folds or unfolds the region under the cursor, or the enclosing region
+    /// if the cursor isn't at the start of one.
+    pub fn toggle_fold(&mut self) -> bool {
+        let start = if self.is_foldable(self.cursor) {
+            self.cursor
+        } else if let Some(start) = self.enclosing_fold(self.cursor, false) {
+            start
+        } else {
+            return false;
+        };
+
+        if !self.folded.remove(&start) {
+            self.folded.insert(start);
+        }
+        self.cursor = start;
+        true
+    }
+
+    fn line_to_widget(&self, idx: usize, config: &Config) -> Line<'static> {
+        let line = &self.lines[idx];
+        let style_for_label = |label: &JsonLabel| {
+            label.color_key().map_or_else(Style::default, |color_key| {
+                Style::default().fg(config.color_map.get(&color_key))
+            })
+        };
+
+        let mut spans: Vec<_> = if line.labels.is_empty() {
+            vec![Span::from(line.text.clone())]
+        } else {
+            line.labels
+                .iter()
+                .map(|(text, label)| Span::styled(text.clone(), style_for_label(label)))
+                .collect()
+        };
+
+        if let Some(end) = line.fold_end.filter(|_| self.folded.contains(&idx)) {
+            spans.push(Span::from(" … ").fg(config.color_map.get(&ColorKey::FgSecondary)));
+            spans.push(
+                Span::from(self.lines[end].text.trim().to_string())
+                    .fg(config.color_map.get(&ColorKey::Punctuation)),
+            );
+        }
+
+        let widget = Line::from(spans);
+        if idx == self.cursor {
+            widget
+                .bg(config.color_map.get(&ColorKey::SelectionBg))
+                .bold()
+        } else {
+            widget
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, block: Block, config: &Config) {
+        let height = usize::from(block.inner(area).height);
+        let visible = self.visible_lines();
+
+        // scroll so that the cursor is visible
+        let cursor_pos = visible
+            .iter()
+            .position(|idx| *idx == self.cursor)
+            .unwrap_or_default();
+        if cursor_pos < self.scroll {
+            self.scroll = cursor_pos;
+        } else if height > 0 && cursor_pos >= self.scroll + height {
+            self.scroll = cursor_pos + 1 - height;
+        }
+
+        let lines: Vec<_> = visible
+            .iter()
+            .skip(self.scroll)
+            .take(height)
+            .map(|idx| self.line_to_widget(*idx, config))
+            .collect();
+
+        let content = Paragraph::new(lines)
+            .block(block)
+            .style(Style::default().fg(config.color_map.get(&ColorKey::FgPrimary)));
+        frame.render_widget(content, area);
+
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(None)
+            .track_symbol(None)
+            .end_symbol(None);
+        let mut scrollbar_state =
+            ScrollbarState::new(visible.len().saturating_sub(height)).position(self.scroll);
+        frame.render_stateful_widget(
+            scrollbar,
+            area.inner(Margin::new(0, 1)),
+            &mut scrollbar_state,
+        );
+    }
+}
+
+/// Adds the lines for a pretty-printed value, which is on its own line or
+/// starts a multi-line document or array.
+fn push_lines(
+    lines: &mut Vec<JsonLine>,
+    doc_idx: usize,
+    path: Vec<MongoKey>,
+    key: Option<&str>,
+    value: &Bson,
+    depth: usize,
+    trailing_comma: bool,
+) {
+    let indent = INDENT.repeat(depth);
+    let key = key
+        .map(|key| format!("{}: ", serde_json::Value::from(key)))
+        .unwrap_or_default();
+    let comma = if trailing_comma { "," } else { "" };
+    let new_line = |text: String, path: Vec<MongoKey>| JsonLine {
+        doc_idx,
+        path,
+        text,
+        labels: vec![],
+        fold_end: None,
+    };
+
+    let (children, open, close): (Vec<(MongoKey, Option<&str>, &Bson)>, _, _) = match value {
+        Bson::Document(doc) if !doc.is_empty() => (
+            doc.iter()
+                .map(|(key, value)| (MongoKey::from(key.clone()), Some(key.as_str()), value))
+                .collect(),
+            '{',
+            '}',
+        ),
+        Bson::Array(array) if !array.is_empty() => (
+            array
+                .iter()
+                .enumerate()
+                .map(|(idx, value)| (MongoKey::Usize(idx), None, value))
+                .collect(),
+            '[',
+            ']',
+        ),
+        value => {
+            let json = value.clone().into_relaxed_extjson();
+            lines.push(new_line(format!("{indent}{key}{json}{comma}"), path));
+            return;
+        }
+    };
+
+    let start = lines.len();
+    lines.push(new_line(format!("{indent}{key}{open}"), path.clone()));
+
+    let num_children = children.len();
+    for (idx, (child_key, child_name, child)) in children.into_iter().enumerate() {
+        let mut child_path = path.clone();
+        child_path.push(child_key);
+        push_lines(
+            lines,
+            doc_idx,
+            child_path,
+            child_name,
+            child,
+            depth + 1,
+            idx + 1 < num_children,
+        );
+    }
+
+    lines.push(new_line(format!("{indent}{close}{comma}"), path));
+    lines[start].fold_end = Some(lines.len() - 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::bson::bson;
+
+    fn json() -> DocJson {
+        let mut json = DocJson::default();
+        json.set_docs(
+            &[
+                bson!({ "_id": 1, "a": { "b": "c" }, "d": [1, 2] }),
+                bson!({ "_id": 2 }),
+            ],
+            true,
+        );
+        json
+    }
+
+    #[test]
+    fn pretty_prints_docs() {
+        let texts: Vec<_> = json().lines.into_iter().map(|line| line.text).collect();
+
+        assert_eq!(
+            texts,
+            vec![
+                "{",
+                "  \"_id\": 1,",
+                "  \"a\": {",
+                "    \"b\": \"c\"",
+                "  },",
+                "  \"d\": [",
+                "    1,",
+                "    2",
+                "  ]",
+                "}",
+                "{",
+                "  \"_id\": 2",
+                "}",
+            ]
+        );
+    }
+
+    #[test]
+    fn folded_lines_are_skipped() {
+        let mut json = json();
+        json.cursor = 2;
+        json.toggle_fold();
+
+        json.nav_down();
+        assert_eq!(json.cursor, 5);
+        assert_eq!(
+            json.selected_path(),
+            [MongoKey::Usize(1), MongoKey::String("d".into())]
+        );
+
+        // folding from inside a region folds the whole region
+        json.nav_down();
+        json.toggle_fold();
+        assert_eq!(json.cursor, 5);
+        json.nav_down();
+        assert_eq!(json.cursor, 9);
+    }
+
+    #[test]
+    fn selecting_a_path_unfolds_it() {
+        let mut json = json();
+        json.toggle_fold();
+        assert_eq!(json.visible_lines(), vec![0, 10, 11, 12]);
+
+        json.select_path(&[
+            MongoKey::Usize(1),
+            MongoKey::String("a".into()),
+            MongoKey::String("b".into()),
+        ]);
+
+        assert_eq!(json.cursor, 3);
+        assert!(json.folded.is_empty());
+    }
+}
@@ -0,0 +1,475 @@
+use crate::{
+    config::{color_map::ColorKey, Config},
+    utils::{doc_diff::bson_preview, mongo_tree::value_color_key},
+};
+use mongodb::bson::{Bson, Document};
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Cell, Row, Table, TableState},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet, HashSet},
+};
+
+const DEFAULT_COLUMN_WIDTH: u16 = 20;
+const MIN_COLUMN_WIDTH: u16 = 4;
+const MAX_COLUMN_WIDTH: u16 = 80;
+const COLUMN_WIDTH_STEP: u16 = 4;
+const COLUMN_SPACING: u16 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+/// The user's choices about how columns are shown, which are kept around
+/// even when the columns themselves change.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnSettings {
+    widths: BTreeMap<String, u16>,
+    hidden: BTreeSet<String>,
+}
+
+/// Shows a page of documents as a table, with a column for each field path.
+#[derive(Debug, Clone, Default)]
+pub struct DocTable {
+    /// Every field path on the page, including hidden ones.
+    columns: Vec<String>,
+    settings: ColumnSettings,
+
+    /// Indices into the page of documents, in the order they are displayed.
+    rows: Vec<usize>,
+    sort: Option<(String, SortDirection)>,
+
+    /// Index of the selected column among the visible ones.
+    selected_column: usize,
+    /// Index of the first visible column that is rendered.
+    column_offset: usize,
+    state: TableState,
+}
+
+impl DocTable {
+    pub fn settings(&self) -> ColumnSettings {
+        self.settings.clone()
+    }
+
+    pub fn set_settings(&mut self, settings: ColumnSettings) {
+        self.settings = settings;
+    }
+
+    /// Updates the table's columns and rows for a new page of documents. If a
+    /// projection includes specific fields, only those fields get columns.
+    pub fn set_docs(&mut self, docs: &[Bson], projection: &Document, reset_state: bool) {
+        self.columns = projected_paths(projection).unwrap_or_else(|| field_paths(docs));
+        self.rows = (0..docs.len()).collect();
+        self.sort_rows(docs);
+
+        if reset_state || self.state.selected().is_none() {
+            self.state = TableState::default().with_selected((!docs.is_empty()).then_some(0));
+            self.selected_column = 0;
+            self.column_offset = 0;
+        } else {
+            let last_row = self.rows.len().saturating_sub(1);
+            self.state
+                .select(self.state.selected().map(|row| row.min(last_row)));
+            self.clamp_selected_column();
+        }
+    }
+
+    fn visible_columns(&self) -> Vec<&String> {
+        self.columns
+            .iter()
+            .filter(|path| !self.settings.hidden.contains(*path))
+            .collect()
+    }
+
+    fn width(&self, path: &str) -> u16 {
+        self.settings
+            .widths
+            .get(path)
+            .copied()
+            .unwrap_or(DEFAULT_COLUMN_WIDTH)
+    }
+
+    fn clamp_selected_column(&mut self) {
+        let num_visible = self.visible_columns().len();
+        self.selected_column = self.selected_column.min(num_visible.saturating_sub(1));
+        self.column_offset = self.column_offset.min(self.selected_column);
+    }
+
+    /// The index (in the page of documents) of the document in the selected row.
+    pub fn selected_doc_idx(&self) -> Option<usize> {
+        self.rows.get(self.state.selected()?).copied()
+    }
+
+    /// The field path of the selected column.
+    pub fn selected_path(&self) -> Option<&String> {
+        self.visible_columns().get(self.selected_column).copied()
+    }
+
+    pub fn nav_up(&mut self) -> bool {
+        match self.state.selected() {
+            Some(row) if row > 0 => {
+                self.state.select(Some(row - 1));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn nav_down(&mut self) -> bool {
+        match self.state.selected() {
+            Some(row) if row + 1 < self.rows.len() => {
+                self.state.select(Some(row + 1));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn nav_left(&mut self) -> bool {
+        if self.selected_column == 0 {
+            return false;
+        }
+        self.selected_column -= 1;
+        self.column_offset = self.column_offset.min(self.selected_column);
+        true
+    }
+
+    pub fn nav_right(&mut self) -> bool {
+        if self.selected_column + 1 >= self.visible_columns().len() {
+            return false;
+        }
+        self.selected_column += 1;
+        true
+    }
+
+    pub fn resize_selected_column(&mut self, grow: bool) {
+        let Some(path) = self.selected_path().cloned() else {
+            return;
+        };
+        let width = self.width(&path);
+        let width = if grow {
+            (width + COLUMN_WIDTH_STEP).min(MAX_COLUMN_WIDTH)
+        } else {
+            width
+                .saturating_sub(COLUMN_WIDTH_STEP)
+                .max(MIN_COLUMN_WIDTH)
+        };
+        self.settings.widths.insert(path, width);
+    }
+
+    pub fn hide_selected_column(&mut self) {
+        if let Some(path) = self.selected_path().cloned() {
+            self.settings.hidden.insert(path);
+            self.clamp_selected_column();
+        }
+    }
+
+    pub fn show_all_columns(&mut self) {
+        self.settings.hidden.clear();
+    }
+
+    /// Sorts the page by the selected column, cycling between ascending,
+    /// descending, and the order the documents came back from the server.
+    pub fn cycle_sort(&mut self, docs: &[Bson]) {
+        let Some(path) = self.selected_path().cloned() else {
+            return;
+        };
+
+        self.sort = match &self.sort {
+            Some((sorted_path, SortDirection::Ascending)) if *sorted_path == path => {
+                Some((path, SortDirection::Descending))
+            }
+            Some((sorted_path, SortDirection::Descending)) if *sorted_path == path => None,
+            _ => Some((path, SortDirection::Ascending)),
+        };
+
+        self.rows = (0..docs.len()).collect();
+        self.sort_rows(docs);
+    }
+
+    fn sort_rows(&mut self, docs: &[Bson]) {
+        let Some((path, direction)) = &self.sort else {
+            return;
+        };
+
+        let value = |idx: usize| {
+            docs.get(idx)
+                .and_then(Bson::as_document)
+                .and_then(|doc| value_at_path(doc, path))
+        };
+
+        self.rows.sort_by(|a, b| {
+            let ordering = compare_values(value(*a), value(*b));
+            match direction {
+                SortDirection::Ascending => ordering,
+                SortDirection::Descending => ordering.reverse(),
+            }
+        });
+    }
+
+    pub fn render(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        block: Block,
+        docs: &[Bson],
+        config: &Config,
+    ) {
+        let inner_width = block.inner(area).width;
+
+        // scroll horizontally so that the selected column is visible
+        let columns: Vec<_> = self.visible_columns().into_iter().cloned().collect();
+        let widths: Vec<_> = columns.iter().map(|path| self.width(path)).collect();
+        while self.column_offset < self.selected_column
+            && widths[self.column_offset..=self.selected_column]
+                .iter()
+                .map(|width| width + COLUMN_SPACING)
+                .sum::<u16>()
+                > inner_width
+        {
+            self.column_offset += 1;
+        }
+        let columns = &columns[self.column_offset.min(columns.len())..];
+        let widths = &widths[self.column_offset.min(widths.len())..];
+
+        let header = Row::new(columns.iter().zip(widths).map(|(path, width)| {
+            let indicator = match &self.sort {
+                Some((sorted_path, SortDirection::Ascending)) if sorted_path == path => " ▲",
+                Some((sorted_path, SortDirection::Descending)) if sorted_path == path => " ▼",
+                _ => "",
+            };
+            Cell::from(truncate(&format!("{path}{indicator}"), *width))
+                .fg(config.color_map.get(&ColorKey::Key))
+        }))
+        .bold();
+
+        let rows = self.rows.iter().map(|idx| {
+            let doc = docs.get(*idx).and_then(Bson::as_document);
+            Row::new(columns.iter().zip(widths).map(|(path, width)| {
+                doc.and_then(|doc| value_at_path(doc, path))
+                    .map_or_else(Cell::default, |value| {
+                        Cell::from(truncate(&cell_text(value), *width))
+                            .fg(config.color_map.get(&value_color_key(value)))
+                    })
+            }))
+        });
+
+        let table = Table::new(rows, widths.iter().map(|width| Constraint::Length(*width)))
+            .header(header)
+            .block(block)
+            .column_spacing(COLUMN_SPACING)
+            .style(Style::default().fg(config.color_map.get(&ColorKey::FgPrimary)))
+            .row_highlight_style(Style::default().bold())
+            .cell_highlight_style(
+                Style::default()
+                    .fg(config.color_map.get(&ColorKey::SelectionFg))
+                    .bg(config.color_map.get(&ColorKey::SelectionBg)),
+            );
+
+        self.state
+            .select_column(Some(self.selected_column - self.column_offset));
+        frame.render_stateful_widget(table, area, &mut self.state);
+    }
+}
+
+/// Gets the value at a dotted path in a document, eg. `address.city`.
+pub fn value_at_path<'a>(doc: &'a Document, path: &str) -> Option<&'a Bson> {
+    let mut keys = path.split('.');
+    let mut value = doc.get(keys.next()?)?;
+    for key in keys {
+        value = value.as_document()?.get(key)?;
+    }
+    Some(value)
+}
+
+/// The paths of every field in a page of documents, in the order they first
+/// appear. Nested documents are flattened; arrays get a single column.
+fn field_paths(docs: &[Bson]) -> Vec<String> {
+    fn collect(
+        prefix: Option<&str>,
+        doc: &Document,
+        seen: &mut HashSet<String>,
+        out: &mut Vec<String>,
+    ) {
+        for (key, value) in doc {
+            let path = prefix.map_or_else(|| key.clone(), |prefix| format!("{prefix}.{key}"));
+            match value {
+                Bson::Document(doc) if !doc.is_empty() => collect(Some(&path), doc, seen, out),
+                _ => {
+                    if seen.insert(path.clone()) {
+                        out.push(path);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut paths = vec![];
+    for doc in docs.iter().filter_map(Bson::as_document) {
+        collect(None, doc, &mut seen, &mut paths);
+    }
+    paths
+}
+
+/// The fields included by a projection, if it includes specific fields.
+fn projected_paths(projection: &Document) -> Option<Vec<String>> {
+    let is_included = |value: &Bson| match value {
+        Bson::Boolean(b) => *b,
+        Bson::Int32(n) => *n != 0,
+        Bson::Int64(n) => *n != 0,
+        Bson::Double(n) => *n != 0.0,
+        // expressions like `$slice` compute a value for the field
+        _ => true,
+    };
+
+    let included: Vec<_> = projection
+        .iter()
+        .filter(|(key, value)| *key != "_id" && is_included(value))
+        .map(|(key, _)| key.clone())
+        .collect();
+    if included.is_empty() {
+        return None;
+    }
+
+    let id_excluded = projection
+        .get("_id")
+        .is_some_and(|value| !is_included(value));
+    let id = (!id_excluded).then(|| "_id".to_string());
+    Some(id.into_iter().chain(included).collect())
+}
+
+fn cell_text(value: &Bson) -> String {
+    match value {
+        Bson::String(s) => s.clone(),
+        value => bson_preview(value),
+    }
+}
+
+fn truncate(text: &str, width: u16) -> String {
+    let width = usize::from(width);
+    let text = text.replace('\n', " ");
+    if text.chars().count() <= width {
+        text
+    } else {
+        let mut truncated: String = text.chars().take(width.saturating_sub(1)).collect();
+        truncated.push('…');
+        truncated
+    }
+}
+
+/// Orders values for sorting a column. Missing values come first, then
+/// values are ordered by type, and then by value within a type.
+fn compare_values(a: Option<&Bson>, b: Option<&Bson>) -> Ordering {
+    const fn type_rank(value: &Bson) -> u8 {
+        match value {
+            Bson::Null | Bson::Undefined => 0,
+            Bson::Int32(_) | Bson::Int64(_) | Bson::Double(_) | Bson::Decimal128(_) => 1,
+            Bson::String(_) | Bson::Symbol(_) => 2,
+            Bson::Boolean(_) => 3,
+            Bson::DateTime(_) | Bson::Timestamp(_) => 4,
+            Bson::ObjectId(_) => 5,
+            _ => 6,
+        }
+    }
+
+    #[expect(clippy::cast_precision_loss)]
+    fn as_f64(value: &Bson) -> Option<f64> {
+        match value {
+            Bson::Int32(n) => Some(f64::from(*n)),
+            Bson::Int64(n) => Some(*n as f64),
+            Bson::Double(n) => Some(*n),
+            Bson::Decimal128(n) => n.to_string().parse().ok(),
+            _ => None,
+        }
+    }
+
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+        (Some(a), Some(b)) => type_rank(a).cmp(&type_rank(b)).then_with(|| match (a, b) {
+            (Bson::String(a), Bson::String(b)) => a.cmp(b),
+            (Bson::Boolean(a), Bson::Boolean(b)) => a.cmp(b),
+            (Bson::DateTime(a), Bson::DateTime(b)) => a.cmp(b),
+            (Bson::ObjectId(a), Bson::ObjectId(b)) => a.cmp(b),
+            (a, b) => match (as_f64(a), as_f64(b)) {
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                _ => bson_preview(a).cmp(&bson_preview(b)),
+            },
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::bson::{bson, doc};
+
+    fn docs() -> Vec<Bson> {
+        vec![
+            bson!({ "_id": 1, "name": "b", "address": { "city": "x" } }),
+            bson!({ "_id": 2, "name": "a", "tags": ["t"] }),
+            bson!({ "_id": 3, "address": { "city": "y" } }),
+        ]
+    }
+
+    #[test]
+    fn columns_are_union_of_field_paths() {
+        let mut table = DocTable::default();
+        table.set_docs(&docs(), &Document::default(), true);
+
+        assert_eq!(table.columns, vec!["_id", "name", "address.city", "tags"]);
+    }
+
+    #[test]
+    fn columns_follow_projection() {
+        let mut table = DocTable::default();
+        table.set_docs(&docs(), &doc! { "name": 1 }, true);
+        assert_eq!(table.columns, vec!["_id", "name"]);
+
+        table.set_docs(&docs(), &doc! { "name": 1, "_id": 0 }, true);
+        assert_eq!(table.columns, vec!["name"]);
+
+        // exclusion projections still show whatever fields come back
+        table.set_docs(&docs(), &doc! { "tags": 0 }, true);
+        assert_eq!(table.columns, vec!["_id", "name", "address.city", "tags"]);
+    }
+
+    #[test]
+    fn sort_by_selected_column() {
+        let docs = docs();
+        let mut table = DocTable::default();
+        table.set_docs(&docs, &Document::default(), true);
+        table.nav_right();
+
+        table.cycle_sort(&docs);
+        assert_eq!(table.rows, vec![2, 1, 0]);
+
+        table.cycle_sort(&docs);
+        assert_eq!(table.rows, vec![0, 1, 2]);
+
+        table.cycle_sort(&docs);
+        assert_eq!(table.rows, vec![0, 1, 2]);
+        assert_eq!(table.sort, None);
+    }
+
+    #[test]
+    fn hidden_columns_are_skipped() {
+        let mut table = DocTable::default();
+        table.set_docs(&docs(), &Document::default(), true);
+
+        table.nav_right();
+        table.hide_selected_column();
+
+        assert_eq!(table.selected_path(), Some(&"address.city".to_string()));
+
+        table.show_all_columns();
+        assert_eq!(table.visible_columns().len(), 4);
+    }
+}
@@ -10,8 +10,11 @@ use ratatui::{
 use std::{cell::Cell, rc::Rc};
 use tui_input::{backend::crossterm::EventHandler, Input as TuiInput};
 
+pub mod conn_details_input;
 pub mod conn_name_input;
+pub mod conn_ssh_input;
 pub mod conn_str_input;
+pub mod conn_tls_input;
 pub mod doc_input;
 pub mod input_modal;
 
@@ -70,7 +73,18 @@ where
         self.formatter.on_change(value);
     }
 
-    fn handle_raw_event(&mut self, event: &CrosstermEvent, queue: &mut SignalQueue) {
+    pub fn cursor(&self) -> usize {
+        self.state.cursor()
+    }
+
+    /// Replaces the input's text and moves the cursor to the given character
+    /// index.
+    pub fn set_value_with_cursor(&mut self, value: &str, cursor: usize) {
+        self.state = TuiInput::new(value.to_string()).with_cursor(cursor);
+        self.formatter.on_change(value);
+    }
+
+    pub fn handle_raw_event(&mut self, event: &CrosstermEvent, queue: &mut SignalQueue) {
         if self.is_editing() {
             self.state.handle_event(event);
             self.formatter.on_change(self.state.value());
@@ -111,7 +125,7 @@ where
         }
     }
 
-    fn render(&self, frame: &mut Frame, area: Rect, focused: bool) {
+    pub fn render(&self, frame: &mut Frame, area: Rect, focused: bool) {
         let (border_color, bg_color) = if focused {
             let border_color = if self.is_editing() {
                 self.config.color_map.get(&ColorKey::PanelActiveInputBorder)
@@ -167,6 +181,15 @@ pub trait InputFormatter {
 #[derive(Default, Debug, Clone)]
 pub struct DefaultFormatter {
     text: Text<'static>,
+
+    /// Whether to hide the text, like for a password.
+    masked: bool,
+}
+
+impl DefaultFormatter {
+    pub const fn set_masked(&mut self, masked: bool) {
+        self.masked = masked;
+    }
 }
 
 impl InputFormatter for DefaultFormatter {
@@ -175,6 +198,10 @@ impl InputFormatter for DefaultFormatter {
     }
 
     fn on_change(&mut self, text: &str) {
-        self.text = text.to_string().into();
+        self.text = if self.masked {
+            "•".repeat(text.chars().count()).into()
+        } else {
+            text.to_string().into()
+        };
     }
 }
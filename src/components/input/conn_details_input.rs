@@ -0,0 +1,255 @@
+use super::{conn_tls_input::TlsField, DefaultFormatter, InnerInput};
+use crate::{
+    components::{
+        connection_screen::ConnScrFocus,
+        tab::{CloneWithFocus, TabFocus},
+        Component,
+    },
+    config::Config,
+    model::connection::{Connection, Environment},
+    system::{
+        command::{Command, CommandCategory, CommandGroup},
+        event::Event,
+        message::{ConnScreenAction, Message},
+        signal::SignalQueue,
+    },
+};
+use ratatui::prelude::{Frame, Rect};
+use serde::{Deserialize, Serialize};
+use std::{cell::Cell, rc::Rc};
+
+/// The optional fields of a connection that help organize the connection list,
+/// in the order they're edited.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConnDetail {
+    Folder,
+    Tags,
+    Environment,
+}
+
+impl ConnDetail {
+    const fn title(self) -> &'static str {
+        match self {
+            Self::Folder => "Folder",
+            Self::Tags => "Tags",
+            Self::Environment => "Environment",
+        }
+    }
+
+    const fn next(self) -> Option<Self> {
+        match self {
+            Self::Folder => Some(Self::Tags),
+            Self::Tags => Some(Self::Environment),
+            Self::Environment => None,
+        }
+    }
+
+    const fn previous(self) -> Option<Self> {
+        match self {
+            Self::Folder => None,
+            Self::Tags => Some(Self::Folder),
+            Self::Environment => Some(Self::Tags),
+        }
+    }
+
+    /// The value of this field for a connection, as it's written in the input.
+    fn value_of(self, conn: &Connection) -> String {
+        match self {
+            Self::Folder => conn.folder.clone().unwrap_or_default(),
+            Self::Tags => conn.tags.join(", "),
+            Self::Environment => conn
+                .environment
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// An optional input for a connection's folder, comma-separated tags, or
+/// environment (dev, staging, or prod).
+#[derive(Debug, Clone)]
+pub struct ConnDetailsInput {
+    detail: ConnDetail,
+    focus: Rc<Cell<TabFocus>>,
+    input: InnerInput<DefaultFormatter>,
+}
+
+impl Default for ConnDetailsInput {
+    fn default() -> Self {
+        Self::new(
+            ConnDetail::Folder,
+            Rc::default(),
+            Rc::default(),
+            Config::default(),
+        )
+    }
+}
+
+impl CloneWithFocus for ConnDetailsInput {
+    fn clone_with_focus(&self, focus: Rc<Cell<TabFocus>>) -> Self {
+        Self {
+            focus,
+            ..self.clone()
+        }
+    }
+}
+
+impl ConnDetailsInput {
+    pub fn new(
+        detail: ConnDetail,
+        focus: Rc<Cell<TabFocus>>,
+        cursor_pos: Rc<Cell<(u16, u16)>>,
+        config: Config,
+    ) -> Self {
+        let input = InnerInput::new(
+            detail.title(),
+            cursor_pos,
+            config,
+            DefaultFormatter::default(),
+        );
+        Self {
+            detail,
+            focus,
+            input,
+        }
+    }
+
+    /// The folder in the input, or `None` if it's empty.
+    pub fn folder(&self) -> Option<String> {
+        let folder = self.input.value().trim().trim_matches('/');
+        (!folder.is_empty()).then(|| folder.to_string())
+    }
+
+    /// The tags in the input, without duplicates or empty ones.
+    pub fn tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = vec![];
+        for tag in self.input.value().split(',').map(str::trim) {
+            if !tag.is_empty() && !tags.iter().any(|t| t == tag) {
+                tags.push(tag.to_string());
+            }
+        }
+        tags
+    }
+
+    /// The environment in the input, or `None` if it's empty.
+    ///
+    /// # Errors
+    /// If the input isn't empty but isn't an environment either.
+    pub fn environment(&self) -> anyhow::Result<Option<Environment>> {
+        let value = self.input.value().trim();
+        if value.is_empty() {
+            Ok(None)
+        } else {
+            value.parse().map(Some)
+        }
+    }
+
+    pub const fn start_editing(&mut self) {
+        self.input.start_editing();
+    }
+
+    pub const fn stop_editing(&mut self) {
+        self.input.stop_editing();
+    }
+}
+
+impl Component for ConnDetailsInput {
+    fn is_focused(&self) -> bool {
+        self.focus.get() == TabFocus::ConnScr(ConnScrFocus::DetailIn(self.detail))
+    }
+
+    fn focus(&self) {
+        self.focus
+            .set(TabFocus::ConnScr(ConnScrFocus::DetailIn(self.detail)));
+    }
+
+    fn commands(&self) -> Vec<CommandGroup> {
+        vec![
+            CommandGroup::new(vec![Command::Confirm], "next field")
+                .in_cat(CommandCategory::StatusBarOnly),
+            CommandGroup::new(vec![Command::Back], "previous field")
+                .in_cat(CommandCategory::StatusBarOnly),
+        ]
+    }
+
+    fn handle_raw_event(&mut self, event: &crossterm::event::Event, queue: &mut SignalQueue) {
+        self.input.handle_raw_event(event, queue);
+    }
+
+    fn handle_command(&mut self, command: &Command, queue: &mut SignalQueue) {
+        if !self.input.is_editing() {
+            return;
+        }
+
+        let action = match command {
+            Command::Confirm => {
+                if self.detail == ConnDetail::Environment {
+                    if let Err(err) = self.environment() {
+                        queue.push(Event::ErrorOccurred(err.to_string().into()));
+                        return;
+                    }
+                }
+                self.detail
+                    .next()
+                    .map_or(ConnScreenAction::FinishEditingConn, |detail| {
+                        ConnScreenAction::FocusConnDetailInput(detail)
+                    })
+            }
+            Command::Back => self.detail.previous().map_or(
+                ConnScreenAction::FocusConnTlsInput(TlsField::Options),
+                ConnScreenAction::FocusConnDetailInput,
+            ),
+            _ => return,
+        };
+        queue.push(Message::to_conn_scr(action));
+    }
+
+    fn handle_event(&mut self, event: &Event, _queue: &mut SignalQueue) {
+        match event {
+            Event::ConnectionCreated(..) => self.input.set_value(""),
+            Event::EditConnectionStarted(conn) => {
+                self.input.set_value(&self.detail.value_of(conn));
+            }
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.render(frame, area, self.is_focused());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::ComponentTestHarness;
+
+    fn details_input(detail: ConnDetail) -> ConnDetailsInput {
+        ConnDetailsInput {
+            detail,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parse_tags() {
+        let mut test = ComponentTestHarness::new(details_input(ConnDetail::Tags));
+
+        test.component_mut().start_editing();
+        test.given_string(" analytics, ,replica,analytics ");
+
+        assert_eq!(test.component().tags(), vec!["analytics", "replica"]);
+    }
+
+    #[test]
+    fn reject_unknown_environment() {
+        let mut test = ComponentTestHarness::new(details_input(ConnDetail::Environment));
+
+        test.component_mut().start_editing();
+        test.given_string("qa");
+        test.given_command(Command::Confirm);
+
+        test.expect_event(|e| matches!(e, Event::ErrorOccurred(..)));
+    }
+}
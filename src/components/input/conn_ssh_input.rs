@@ -0,0 +1,167 @@
+use super::{conn_tls_input::TlsField, DefaultFormatter, InnerInput};
+use crate::{
+    components::{
+        connection_screen::ConnScrFocus,
+        tab::{CloneWithFocus, TabFocus},
+        Component,
+    },
+    config::Config,
+    model::connection::SshTunnel,
+    system::{
+        command::{Command, CommandCategory, CommandGroup},
+        event::Event,
+        message::{ConnScreenAction, Message},
+        signal::SignalQueue,
+    },
+};
+use ratatui::prelude::{Frame, Rect};
+use std::{cell::Cell, rc::Rc};
+
+/// An optional input for the SSH tunnel that a connection goes through,
+/// written like `ssh://user@bastion:22?key_file=~/.ssh/id_ed25519`.
+#[derive(Debug, Default, Clone)]
+pub struct ConnSshInput {
+    focus: Rc<Cell<TabFocus>>,
+    input: InnerInput<DefaultFormatter>,
+}
+
+impl CloneWithFocus for ConnSshInput {
+    fn clone_with_focus(&self, focus: Rc<Cell<TabFocus>>) -> Self {
+        Self {
+            focus,
+            ..self.clone()
+        }
+    }
+}
+
+impl ConnSshInput {
+    pub fn new(
+        focus: Rc<Cell<TabFocus>>,
+        cursor_pos: Rc<Cell<(u16, u16)>>,
+        config: Config,
+    ) -> Self {
+        let input = InnerInput::new(
+            "SSH Tunnel (optional)",
+            cursor_pos,
+            config,
+            DefaultFormatter::default(),
+        );
+        Self { focus, input }
+    }
+
+    /// The tunnel settings in the input, or `None` if it's empty.
+    ///
+    /// # Errors
+    /// If the input isn't empty but isn't a valid tunnel either.
+    pub fn tunnel(&self) -> anyhow::Result<Option<SshTunnel>> {
+        let value = self.input.value().trim();
+        if value.is_empty() {
+            Ok(None)
+        } else {
+            value.parse().map(Some)
+        }
+    }
+
+    pub const fn start_editing(&mut self) {
+        self.input.start_editing();
+    }
+
+    pub const fn stop_editing(&mut self) {
+        self.input.stop_editing();
+    }
+}
+
+impl Component for ConnSshInput {
+    fn is_focused(&self) -> bool {
+        self.focus.get() == TabFocus::ConnScr(ConnScrFocus::SshIn)
+    }
+
+    fn focus(&self) {
+        self.focus.set(TabFocus::ConnScr(ConnScrFocus::SshIn));
+    }
+
+    fn commands(&self) -> Vec<CommandGroup> {
+        vec![
+            CommandGroup::new(vec![Command::Confirm], "next field")
+                .in_cat(CommandCategory::StatusBarOnly),
+            CommandGroup::new(vec![Command::Back], "previous field")
+                .in_cat(CommandCategory::StatusBarOnly),
+        ]
+    }
+
+    fn handle_raw_event(&mut self, event: &crossterm::event::Event, queue: &mut SignalQueue) {
+        self.input.handle_raw_event(event, queue);
+    }
+
+    fn handle_command(&mut self, command: &Command, queue: &mut SignalQueue) {
+        if !self.input.is_editing() {
+            return;
+        }
+
+        match command {
+            Command::Confirm => {
+                if let Err(err) = self.tunnel() {
+                    queue.push(Event::ErrorOccurred(err.to_string().into()));
+                } else {
+                    queue.push(Message::to_conn_scr(ConnScreenAction::FocusConnTlsInput(
+                        TlsField::CaFile,
+                    )));
+                }
+            }
+            Command::Back => {
+                queue.push(Message::to_conn_scr(ConnScreenAction::FocusConnStrInput));
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event, _queue: &mut SignalQueue) {
+        match event {
+            Event::ConnectionCreated(..) => self.input.set_value(""),
+            Event::EditConnectionStarted(conn) => self.input.set_value(
+                &conn
+                    .ssh_tunnel
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default(),
+            ),
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.render(frame, area, self.is_focused());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{model::connection::Connection, testing::ComponentTestHarness};
+
+    #[test]
+    fn reject_invalid_tunnel() {
+        let mut test = ComponentTestHarness::new(ConnSshInput::default());
+
+        test.component_mut().start_editing();
+        test.given_string("ssh://user@");
+        test.given_command(Command::Confirm);
+
+        test.expect_event(|e| matches!(e, Event::ErrorOccurred(..)));
+    }
+
+    #[test]
+    fn populate_with_connection_on_edit() {
+        let mut test = ComponentTestHarness::new(ConnSshInput::default());
+
+        let mut connection = Connection::new("name".to_string(), "url".to_string());
+        connection.ssh_tunnel = "ssh://me@bastion".parse().ok();
+
+        test.given_event(Event::EditConnectionStarted(connection));
+
+        assert_eq!(
+            test.component().tunnel().ok().flatten(),
+            "ssh://me@bastion".parse().ok()
+        );
+    }
+}
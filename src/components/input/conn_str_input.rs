@@ -5,21 +5,41 @@ use crate::{
         tab::{CloneWithFocus, TabFocus},
         Component,
     },
-    config::Config,
+    config::{color_map::ColorKey, Config},
+    model::conn_str::ConnStrInfo,
     system::{
         command::{Command, CommandCategory, CommandGroup},
         event::Event,
-        message::{ConnScreenAction, Message},
+        message::{ClientAction, ConnScreenAction, Message},
         signal::SignalQueue,
     },
 };
-use ratatui::prelude::{Frame, Rect};
-use std::{cell::Cell, rc::Rc};
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Paragraph, Wrap},
+};
+use std::{
+    cell::Cell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+/// How long to wait after the last keystroke before parsing the connection
+/// string for its preview.
+const INSPECT_DELAY: Duration = Duration::from_millis(300);
 
 #[derive(Debug, Default, Clone)]
 pub struct ConnStrInput {
     focus: Rc<Cell<TabFocus>>,
     input: InnerInput<DefaultFormatter>,
+    config: Config,
+
+    /// When the connection string was last changed, if it hasn't been
+    /// inspected since.
+    changed_at: Option<Instant>,
+
+    /// The options in the connection string, or why it couldn't be parsed.
+    preview: Option<Result<ConnStrInfo, String>>,
 }
 
 impl CloneWithFocus for ConnStrInput {
@@ -40,10 +60,21 @@ impl ConnStrInput {
         let input = InnerInput::new(
             "Connection String",
             cursor_pos,
-            config,
+            config.clone(),
             DefaultFormatter::default(),
         );
-        Self { focus, input }
+        Self {
+            focus,
+            input,
+            config,
+            ..Default::default()
+        }
+    }
+
+    fn set_value(&mut self, value: &str) {
+        self.input.set_value(value);
+        self.preview = None;
+        self.changed_at = (!value.is_empty()).then(Instant::now);
     }
 
     pub fn value(&self) -> &str {
@@ -57,6 +88,43 @@ impl ConnStrInput {
     pub const fn stop_editing(&mut self) {
         self.input.stop_editing();
     }
+
+    /// Renders the options in the connection string, or why it couldn't be
+    /// parsed.
+    pub fn render_preview(&self, frame: &mut Frame, area: Rect) {
+        let label = self.config.color_map.get(&ColorKey::FgSecondary);
+        let error = self.config.color_map.get(&ColorKey::IndicatorError);
+
+        let lines = match &self.preview {
+            Some(Ok(info)) => info
+                .lines()
+                .into_iter()
+                .map(|(name, value)| {
+                    let value = if name == "Unknown options" && !info.unknown_options.is_empty() {
+                        value.fg(error)
+                    } else {
+                        value.into()
+                    };
+                    Line::from(vec![format!("{name}: ").fg(label), value])
+                })
+                .collect(),
+            Some(Err(err)) => vec![Line::from(err.clone()).fg(error)],
+            None => vec![Line::from("Type in a connection string to preview it.").fg(label)],
+        };
+
+        let mut block = Block::bordered()
+            .border_style(self.config.color_map.get(&ColorKey::PanelInactiveBorder))
+            .title(" Preview ");
+        if let Some(key) = self.config.key_map.key_for_command(Command::TestConnection) {
+            block =
+                block.title_bottom(Line::from(format!(" {key}: test connection ")).right_aligned());
+        }
+        let paragraph = Paragraph::new(lines)
+            .wrap(Wrap { trim: true })
+            .fg(self.config.color_map.get(&ColorKey::FgPrimary))
+            .block(block);
+        frame.render_widget(paragraph, area);
+    }
 }
 
 impl Component for ConnStrInput {
@@ -70,7 +138,7 @@ impl Component for ConnStrInput {
 
     fn commands(&self) -> Vec<crate::system::command::CommandGroup> {
         vec![
-            CommandGroup::new(vec![Command::Confirm], "confirm")
+            CommandGroup::new(vec![Command::Confirm], "next field")
                 .in_cat(CommandCategory::StatusBarOnly),
             CommandGroup::new(vec![Command::Back], "previous field")
                 .in_cat(CommandCategory::StatusBarOnly),
@@ -78,7 +146,11 @@ impl Component for ConnStrInput {
     }
 
     fn handle_raw_event(&mut self, event: &crossterm::event::Event, queue: &mut SignalQueue) {
+        let old_value = self.input.value().to_string();
         self.input.handle_raw_event(event, queue);
+        if self.input.value() != old_value {
+            self.changed_at = Some(Instant::now());
+        }
     }
 
     fn handle_command(&mut self, command: &Command, queue: &mut SignalQueue) {
@@ -88,7 +160,7 @@ impl Component for ConnStrInput {
 
         match command {
             Command::Confirm => {
-                queue.push(Message::to_conn_scr(ConnScreenAction::FinishEditingConn));
+                queue.push(Message::to_conn_scr(ConnScreenAction::FocusConnSshInput));
             }
             Command::Back => {
                 queue.push(Message::to_conn_scr(ConnScreenAction::FocusConnNameInput));
@@ -97,10 +169,28 @@ impl Component for ConnStrInput {
         }
     }
 
-    fn handle_event(&mut self, event: &Event, _queue: &mut SignalQueue) {
+    fn handle_event(&mut self, event: &Event, queue: &mut SignalQueue) {
         match event {
-            Event::ConnectionCreated(..) => self.input.set_value(""),
-            Event::EditConnectionStarted(conn) => self.input.set_value(&conn.connection_str),
+            Event::ConnectionCreated(..) => self.set_value(""),
+            Event::EditConnectionStarted(conn) => self.set_value(&conn.connection_str),
+            Event::Tick
+                if self
+                    .changed_at
+                    .is_some_and(|changed_at| changed_at.elapsed() >= INSPECT_DELAY) =>
+            {
+                self.changed_at = None;
+                if self.input.value().is_empty() {
+                    self.preview = None;
+                } else {
+                    queue.push(Message::to_client(ClientAction::InspectConnStr(
+                        self.input.value().to_string(),
+                    )));
+                }
+            }
+            // results for an older version of the string are left out
+            Event::ConnStrInspected { conn_str, result } if conn_str == self.input.value() => {
+                self.preview = Some(result.clone());
+            }
             _ => {}
         }
     }
@@ -138,4 +228,37 @@ mod tests {
 
         assert_eq!(test.component().value(), "url");
     }
+
+    #[test]
+    fn show_preview_of_latest_value() {
+        let mut test = ComponentTestHarness::new(ConnStrInput::default());
+
+        test.component_mut().start_editing();
+        test.given_string("mongodb://localhost");
+        test.component_mut().changed_at = Some(
+            Instant::now()
+                .checked_sub(INSPECT_DELAY)
+                .expect("should be later than the delay"),
+        );
+        test.given_event(Event::Tick);
+        test.expect_message(|m| {
+            matches!(
+                m.read_as_client(),
+                Some(ClientAction::InspectConnStr(s)) if s == "mongodb://localhost"
+            )
+        });
+
+        // a result for an outdated value is ignored
+        test.given_event(Event::ConnStrInspected {
+            conn_str: "mongodb://local".to_string(),
+            result: Err("outdated".to_string()),
+        });
+        assert!(test.component().preview.is_none());
+
+        test.given_event(Event::ConnStrInspected {
+            conn_str: "mongodb://localhost".to_string(),
+            result: Err("invalid".to_string()),
+        });
+        assert_eq!(test.component().preview, Some(Err("invalid".to_string())));
+    }
 }
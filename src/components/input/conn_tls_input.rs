@@ -0,0 +1,244 @@
+use super::{conn_details_input::ConnDetail, DefaultFormatter, InnerInput};
+use crate::{
+    components::{
+        connection_screen::ConnScrFocus,
+        tab::{CloneWithFocus, TabFocus},
+        Component,
+    },
+    config::Config,
+    model::connection::{Connection, TlsSettings},
+    system::{
+        command::{Command, CommandCategory, CommandGroup},
+        event::Event,
+        message::{ConnScreenAction, Message},
+        signal::SignalQueue,
+    },
+};
+use ratatui::prelude::{Frame, Rect};
+use serde::{Deserialize, Serialize};
+use std::{cell::Cell, rc::Rc};
+
+/// The fields of a connection's TLS settings, in the order they're edited.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TlsField {
+    CaFile,
+    CertKeyFile,
+    Options,
+}
+
+impl TlsField {
+    const fn title(self) -> &'static str {
+        match self {
+            Self::CaFile => "TLS CA File",
+            Self::CertKeyFile => "Client Cert + Key",
+            Self::Options => "TLS Options",
+        }
+    }
+
+    const fn next(self) -> Option<Self> {
+        match self {
+            Self::CaFile => Some(Self::CertKeyFile),
+            Self::CertKeyFile => Some(Self::Options),
+            Self::Options => None,
+        }
+    }
+
+    const fn previous(self) -> Option<Self> {
+        match self {
+            Self::CaFile => None,
+            Self::CertKeyFile => Some(Self::CaFile),
+            Self::Options => Some(Self::CertKeyFile),
+        }
+    }
+
+    /// The value of this field for a connection, as it's written in the input.
+    fn value_of(self, conn: &Connection) -> String {
+        let Some(tls) = &conn.tls else {
+            return String::new();
+        };
+        match self {
+            Self::CaFile => tls.ca_file.clone().unwrap_or_default(),
+            Self::CertKeyFile => tls.cert_key_file.clone().unwrap_or_default(),
+            Self::Options => tls.flags(),
+        }
+    }
+}
+
+/// An optional input for a connection's CA file, client certificate file, or
+/// TLS options (`x509` and `allow-invalid-certificates`, comma-separated).
+#[derive(Debug, Clone)]
+pub struct ConnTlsInput {
+    field: TlsField,
+    focus: Rc<Cell<TabFocus>>,
+    input: InnerInput<DefaultFormatter>,
+}
+
+impl Default for ConnTlsInput {
+    fn default() -> Self {
+        Self::new(
+            TlsField::CaFile,
+            Rc::default(),
+            Rc::default(),
+            Config::default(),
+        )
+    }
+}
+
+impl CloneWithFocus for ConnTlsInput {
+    fn clone_with_focus(&self, focus: Rc<Cell<TabFocus>>) -> Self {
+        Self {
+            focus,
+            ..self.clone()
+        }
+    }
+}
+
+impl ConnTlsInput {
+    pub fn new(
+        field: TlsField,
+        focus: Rc<Cell<TabFocus>>,
+        cursor_pos: Rc<Cell<(u16, u16)>>,
+        config: Config,
+    ) -> Self {
+        let input = InnerInput::new(
+            field.title(),
+            cursor_pos,
+            config,
+            DefaultFormatter::default(),
+        );
+        Self {
+            field,
+            focus,
+            input,
+        }
+    }
+
+    /// The file path in the input, or `None` if it's empty.
+    pub fn path(&self) -> Option<String> {
+        let path = self.input.value().trim();
+        (!path.is_empty()).then(|| path.to_string())
+    }
+
+    /// The settings with the options in the input turned on.
+    ///
+    /// # Errors
+    /// If one of the options isn't known.
+    pub fn options(&self) -> anyhow::Result<TlsSettings> {
+        let mut settings = TlsSettings::default();
+        settings.set_flags(self.input.value())?;
+        Ok(settings)
+    }
+
+    pub const fn start_editing(&mut self) {
+        self.input.start_editing();
+    }
+
+    pub const fn stop_editing(&mut self) {
+        self.input.stop_editing();
+    }
+}
+
+impl Component for ConnTlsInput {
+    fn is_focused(&self) -> bool {
+        self.focus.get() == TabFocus::ConnScr(ConnScrFocus::TlsIn(self.field))
+    }
+
+    fn focus(&self) {
+        self.focus
+            .set(TabFocus::ConnScr(ConnScrFocus::TlsIn(self.field)));
+    }
+
+    fn commands(&self) -> Vec<CommandGroup> {
+        vec![
+            CommandGroup::new(vec![Command::Confirm], "next field")
+                .in_cat(CommandCategory::StatusBarOnly),
+            CommandGroup::new(vec![Command::Back], "previous field")
+                .in_cat(CommandCategory::StatusBarOnly),
+        ]
+    }
+
+    fn handle_raw_event(&mut self, event: &crossterm::event::Event, queue: &mut SignalQueue) {
+        self.input.handle_raw_event(event, queue);
+    }
+
+    fn handle_command(&mut self, command: &Command, queue: &mut SignalQueue) {
+        if !self.input.is_editing() {
+            return;
+        }
+
+        let action = match command {
+            Command::Confirm => {
+                if self.field == TlsField::Options {
+                    if let Err(err) = self.options() {
+                        queue.push(Event::ErrorOccurred(err.to_string().into()));
+                        return;
+                    }
+                }
+                self.field.next().map_or(
+                    ConnScreenAction::FocusConnDetailInput(ConnDetail::Folder),
+                    ConnScreenAction::FocusConnTlsInput,
+                )
+            }
+            Command::Back => self.field.previous().map_or(
+                ConnScreenAction::FocusConnSshInput,
+                ConnScreenAction::FocusConnTlsInput,
+            ),
+            _ => return,
+        };
+        queue.push(Message::to_conn_scr(action));
+    }
+
+    fn handle_event(&mut self, event: &Event, _queue: &mut SignalQueue) {
+        match event {
+            Event::ConnectionCreated(..) => self.input.set_value(""),
+            Event::EditConnectionStarted(conn) => {
+                self.input.set_value(&self.field.value_of(conn));
+            }
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.render(frame, area, self.is_focused());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::ComponentTestHarness;
+
+    fn tls_input(field: TlsField) -> ConnTlsInput {
+        ConnTlsInput {
+            field,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reject_unknown_option() {
+        let mut test = ComponentTestHarness::new(tls_input(TlsField::Options));
+
+        test.component_mut().start_editing();
+        test.given_string("x509, insecure");
+        test.given_command(Command::Confirm);
+
+        test.expect_event(|e| matches!(e, Event::ErrorOccurred(..)));
+    }
+
+    #[test]
+    fn move_to_details_after_options() {
+        let mut test = ComponentTestHarness::new(tls_input(TlsField::Options));
+
+        test.component_mut().start_editing();
+        test.given_string("x509");
+        test.given_command(Command::Confirm);
+
+        test.expect_message(|m| {
+            matches!(
+                m.read_as_conn_scr(),
+                Some(ConnScreenAction::FocusConnDetailInput(ConnDetail::Folder))
+            )
+        });
+    }
+}
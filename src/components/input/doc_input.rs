@@ -1,11 +1,13 @@
 use super::{InnerInput, InputFormatter};
 use crate::{
+    client::search::field_paths,
     components::{
         primary_screen::PrimScrFocus,
         tab::{CloneWithFocus, TabFocus},
         Component,
     },
     config::{color_map::ColorKey, Config},
+    model::query_options::QueryOptions,
     persistence::PersistedComponent,
     system::{
         command::{Command, CommandCategory, CommandGroup},
@@ -13,13 +15,22 @@ use crate::{
         message::{AppAction, Message},
         signal::SignalQueue,
     },
-    utils::json_labeler::{JsonLabel, JsonLabeler},
+    utils::{
+        completion::{
+            Completer, Completions, FILTER_OPERATORS, PROJECTION_OPERATORS, PROJECTION_VALUES,
+            QUERY_OPTIONS, QUERY_OPTION_VALUES, SORT_VALUES,
+        },
+        edit_doc::{edit_query_doc, parse_query_doc},
+        json_labeler::{JsonLabel, JsonLabeler},
+    },
 };
-use mongodb::bson::Document;
+use crossterm::event::{Event as CrosstermEvent, KeyCode, KeyEventKind};
+use mongodb::bson::{Bson, Document};
 use ratatui::{
     prelude::{Frame, Rect},
-    style::Style,
+    style::{Style, Stylize},
     text::{Line, Span, Text},
+    widgets::{Block, Clear, List, ListState},
 };
 use std::{cell::Cell, rc::Rc};
 
@@ -29,6 +40,7 @@ pub enum DocInputKind {
     Filter,
     Projection,
     Sort,
+    Options,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -37,10 +49,17 @@ pub struct DocumentInput {
     input: InnerInput<DocInputFormatter>,
     focus: Rc<Cell<TabFocus>>,
     config: Config,
+
+    completer: Completer,
+    completions: Option<Completions>,
+    completion_state: ListState,
 }
 
 const DEFAULT_DOC: &str = "{}";
 
+/// The most suggestions shown at once in the completion popup.
+const MAX_VISIBLE_COMPLETIONS: u16 = 8;
+
 impl CloneWithFocus for DocumentInput {
     fn clone_with_focus(&self, focus: Rc<Cell<TabFocus>>) -> Self {
         Self {
@@ -64,11 +83,20 @@ impl DocumentInput {
             DocInputFormatter::new(config.clone()),
         );
         input.set_value(DEFAULT_DOC);
+        let completer = match kind {
+            DocInputKind::Filter => Completer::new(FILTER_OPERATORS, &[]),
+            DocInputKind::Projection => Completer::new(PROJECTION_OPERATORS, PROJECTION_VALUES),
+            DocInputKind::Sort => Completer::new(&[], SORT_VALUES),
+            DocInputKind::Options => Completer::new(QUERY_OPTIONS, QUERY_OPTION_VALUES),
+        };
         Self {
             kind,
             input,
             focus,
             config,
+            completer,
+            completions: None,
+            completion_state: ListState::default(),
         }
     }
 
@@ -76,12 +104,14 @@ impl DocumentInput {
         self.input.is_editing()
     }
 
-    pub const fn start_editing(&mut self) {
+    pub fn start_editing(&mut self) {
         self.input.start_editing();
+        self.completions = None;
     }
 
-    pub const fn stop_editing(&mut self) {
+    pub fn stop_editing(&mut self) {
         self.input.stop_editing();
+        self.completions = None;
     }
 
     pub const fn name(&self) -> &'static str {
@@ -89,21 +119,113 @@ impl DocumentInput {
             DocInputKind::Filter => "filter",
             DocInputKind::Projection => "projection",
             DocInputKind::Sort => "sort",
+            DocInputKind::Options => "options",
         }
     }
 
-    fn get_doc(&self) -> Option<Document> {
-        let doc_str = self.input.value();
-        json5::from_str::<serde_json::Value>(doc_str)
-            .ok()
-            .and_then(|value| mongodb::bson::to_document(&value).ok())
+    pub fn get_doc(&self) -> Option<Document> {
+        self.parse_doc().ok()
+    }
+
+    /// Parses the input's text, making sure that query options are valid.
+    fn parse_doc(&self) -> anyhow::Result<Document> {
+        let doc = parse_query_doc(self.input.value())?;
+        if matches!(self.kind, DocInputKind::Options) {
+            QueryOptions::try_from(&doc)?;
+        }
+        Ok(doc)
+    }
+
+    /// Replaces the input's text with the given document.
+    pub fn set_doc(&mut self, doc: &Document) {
+        let json = Bson::Document(doc.clone()).into_relaxed_extjson();
+        self.input.set_value(&json.to_string());
+    }
+
+    /// Updates the suggestions for the word before the cursor.
+    fn update_completions(&mut self) {
+        self.completions = self
+            .completer
+            .complete(self.input.value(), self.input.cursor());
+        self.completion_state = ListState::default().with_selected(Some(0));
+    }
+
+    /// Replaces the word before the cursor with the selected suggestion.
+    fn accept_completion(&mut self) {
+        let Some(completions) = self.completions.take() else {
+            return;
+        };
+        let Some(suggestion) = completions
+            .suggestions
+            .get(self.completion_state.selected().unwrap_or_default())
+        else {
+            return;
+        };
+
+        let (value, cursor) =
+            completions.apply(self.input.value(), self.input.cursor(), suggestion);
+        self.input.set_value_with_cursor(&value, cursor);
+    }
+
+    /// Renders the suggestions below the cursor, on top of anything else.
+    pub fn render_completions(&mut self, frame: &mut Frame) {
+        let Some(completions) = self.completions.as_ref().filter(|_| self.is_editing()) else {
+            return;
+        };
+
+        #[expect(clippy::cast_possible_truncation)]
+        let (width, height) = (
+            completions
+                .suggestions
+                .iter()
+                .map(|suggestion| suggestion.chars().count())
+                .max()
+                .unwrap_or_default() as u16
+                + 4,
+            (completions.suggestions.len() as u16).min(MAX_VISIBLE_COMPLETIONS) + 2,
+        );
+
+        // line the suggestions up with the start of the word being completed
+        #[expect(clippy::cast_possible_truncation)]
+        let word_len = (self.input.cursor() - completions.start) as u16;
+        let (cursor_x, cursor_y) = self.input.cursor_pos.get();
+        let frame_area = frame.area();
+        let area = Rect {
+            x: cursor_x.saturating_sub(word_len + 1),
+            y: cursor_y + 1,
+            width,
+            height,
+        }
+        .intersection(frame_area);
+
+        let items: Vec<_> = completions
+            .suggestions
+            .iter()
+            .map(|suggestion| Line::from(suggestion.clone()))
+            .collect();
+        let list = List::new(items)
+            .fg(self.config.color_map.get(&ColorKey::FgPrimary))
+            .highlight_style(
+                Style::default()
+                    .fg(self.config.color_map.get(&ColorKey::SelectionFg))
+                    .bg(self.config.color_map.get(&ColorKey::SelectionBg)),
+            )
+            .block(
+                Block::bordered()
+                    .border_style(self.config.color_map.get(&ColorKey::PopupBorder))
+                    .bg(self.config.color_map.get(&ColorKey::PopupBg)),
+            );
+
+        frame.render_widget(Clear, area);
+        frame.render_stateful_widget(list, area, &mut self.completion_state);
     }
 
-    const fn doc_updated_event(&self, doc: Document) -> Event {
+    pub const fn doc_updated_event(&self, doc: Document) -> Event {
         match self.kind {
             DocInputKind::Filter => Event::DocFilterUpdated(doc),
             DocInputKind::Projection => Event::DocProjectionUpdated(doc),
             DocInputKind::Sort => Event::DocSortUpdated(doc),
+            DocInputKind::Options => Event::DocOptionsUpdated(doc),
         }
     }
 }
@@ -130,6 +252,8 @@ impl Component for DocumentInput {
             vec![
                 CommandGroup::new(vec![Command::Confirm], "edit input")
                     .in_cat(CommandCategory::DocInputActions),
+                CommandGroup::new(vec![Command::Edit], "edit in editor")
+                    .in_cat(CommandCategory::DocInputActions),
                 CommandGroup::new(vec![Command::Reset], "reset input")
                     .in_cat(CommandCategory::DocInputActions),
             ]
@@ -139,16 +263,18 @@ impl Component for DocumentInput {
     fn handle_command(&mut self, command: &Command, queue: &mut SignalQueue) {
         if self.input.is_editing() {
             match command {
-                Command::Confirm => {
-                    if let Some(doc) = self.get_doc() {
-                        self.input.stop_editing();
+                Command::Confirm => match self.parse_doc() {
+                    Ok(doc) => {
+                        self.stop_editing();
                         queue.push(Event::DocumentPageChanged(0));
                         queue.push(self.doc_updated_event(doc));
                         queue.push(Message::to_app(AppAction::ExitRawMode));
-                    } else {
-                        queue.push(Event::ErrorOccurred("Invalid filter.".into()));
                     }
-                }
+                    Err(err) if matches!(self.kind, DocInputKind::Options) => {
+                        queue.push(Event::ErrorOccurred(err.to_string().into()));
+                    }
+                    Err(_) => queue.push(Event::ErrorOccurred("Invalid filter.".into())),
+                },
                 Command::Back => {
                     self.stop_editing();
                     queue.push(Message::to_app(AppAction::ExitRawMode));
@@ -161,6 +287,18 @@ impl Component for DocumentInput {
                     self.start_editing();
                     queue.push(Message::to_app(AppAction::EnterRawMode));
                 }
+                Command::Edit => {
+                    queue.push(Event::ReturnedFromAltScreen);
+                    match edit_query_doc(self.input.value()) {
+                        Ok(Some(doc)) => {
+                            self.set_doc(&doc);
+                            queue.push(Event::DocumentPageChanged(0));
+                            queue.push(self.doc_updated_event(doc));
+                        }
+                        Ok(None) => {}
+                        Err(err) => queue.push(Event::ErrorOccurred(err.into())),
+                    }
+                }
                 Command::Reset => {
                     self.input.set_value(DEFAULT_DOC);
                     queue.push(self.doc_updated_event(Document::default()));
@@ -170,8 +308,58 @@ impl Component for DocumentInput {
         }
     }
 
-    fn handle_raw_event(&mut self, event: &crossterm::event::Event, queue: &mut SignalQueue) {
+    fn handle_raw_event(&mut self, event: &CrosstermEvent, queue: &mut SignalQueue) {
+        if let (CrosstermEvent::Key(key), Some(completions)) = (event, &self.completions) {
+            if key.kind != KeyEventKind::Release {
+                let num_suggestions = completions.suggestions.len();
+                match key.code {
+                    KeyCode::Tab => {
+                        self.accept_completion();
+                        queue.push(Event::InputKeyPressed);
+                        return;
+                    }
+                    KeyCode::Up => {
+                        self.completion_state.select_previous();
+                        return;
+                    }
+                    KeyCode::Down => {
+                        let selected = self.completion_state.selected().unwrap_or_default();
+                        self.completion_state
+                            .select(Some((selected + 1).min(num_suggestions - 1)));
+                        return;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
         self.input.handle_raw_event(event, queue);
+        if self.is_editing() {
+            self.update_completions();
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event, _queue: &mut SignalQueue) {
+        // the options input suggests index names rather than fields
+        if matches!(self.kind, DocInputKind::Options) {
+            if let Event::IndexesListed(names) = event {
+                self.completer.set_strings(names.iter().cloned());
+            }
+            return;
+        }
+
+        match event {
+            Event::CollectionSelected(..) => self.completer.clear_fields(),
+            Event::FieldPathsSampled(paths) => self.completer.add_fields(paths.iter().cloned()),
+            Event::DocumentsUpdated { docs, .. } => {
+                let docs: Vec<_> = docs
+                    .iter()
+                    .filter_map(|doc| doc.as_document().cloned())
+                    .collect();
+                self.completer.add_fields(field_paths(&docs));
+            }
+            _ => {}
+        }
     }
 
     fn render(&mut self, frame: &mut Frame, area: Rect) {
@@ -228,18 +416,9 @@ impl DocInputFormatter {
     }
 
     fn style_for_label(&self, label: &JsonLabel) -> Style {
-        let color_key = match label {
-            JsonLabel::Punctuation => ColorKey::Punctuation,
-            JsonLabel::Number => ColorKey::Number,
-            JsonLabel::Key => ColorKey::Key,
-            JsonLabel::Value => ColorKey::String,
-            JsonLabel::Constant => ColorKey::Boolean,
-            JsonLabel::DollarSignKey => ColorKey::MongoOperator,
-            JsonLabel::Error => ColorKey::FgPrimary,
-            JsonLabel::Whitespace => return Style::default(),
-        };
-
-        Style::default().fg(self.config.color_map.get(&color_key))
+        label.color_key().map_or_else(Style::default, |color_key| {
+            Style::default().fg(self.config.color_map.get(&color_key))
+        })
     }
 }
 
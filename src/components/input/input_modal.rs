@@ -23,6 +23,13 @@ const INPUT_MODAL_HEIGHT: u16 = 1;
 pub enum InputKind {
     NewCollectionName,
     NewDatabaseName,
+    EditValue,
+    NewField,
+    NewArrayElement,
+    Password,
+    Passphrase,
+    NewPassphrase,
+    Concerns,
 }
 
 impl InputKind {
@@ -30,8 +37,37 @@ impl InputKind {
         match self {
             Self::NewCollectionName => "New Connection's Name",
             Self::NewDatabaseName => "New Database's Name",
+            Self::EditValue => "Edit Value",
+            Self::NewField => "New Field (name: value)",
+            Self::NewArrayElement => "New Array Element",
+            Self::Password => "Password",
+            Self::Passphrase => "Master Passphrase",
+            Self::NewPassphrase => "Passphrase to Encrypt With",
+            Self::Concerns => "Read/Write Concerns",
         }
     }
+
+    const fn confirm_label(self) -> &'static str {
+        match self {
+            Self::NewCollectionName => "create collection",
+            Self::NewDatabaseName => "create database",
+            Self::EditValue => "save value",
+            Self::NewField => "add field",
+            Self::NewArrayElement => "add element",
+            Self::Password => "connect",
+            Self::Passphrase => "unlock",
+            Self::NewPassphrase => "encrypt",
+            Self::Concerns => "apply",
+        }
+    }
+
+    /// Whether the input should hide what's typed.
+    const fn is_secret(self) -> bool {
+        matches!(
+            self,
+            Self::Password | Self::Passphrase | Self::NewPassphrase
+        )
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -70,10 +106,15 @@ impl InputModal {
         // HACK: it would be better if we could just compute this at render time,
         // like if the input didn't insist on rendering its own title
         self.input.set_title(kind.modal_title());
+        self.input.formatter.set_masked(kind.is_secret());
 
         self.kind = Some(kind);
         self.focus();
     }
+
+    pub fn set_value(&mut self, value: &str) {
+        self.input.set_value(value);
+    }
 }
 
 impl Component for InputModal {
@@ -103,21 +144,15 @@ impl Component for InputModal {
     }
 
     fn commands(&self) -> Vec<CommandGroup> {
-        match self.kind {
-            Some(InputKind::NewCollectionName) => vec![
-                CommandGroup::new(vec![Command::Confirm], "create collection")
-                    .in_cat(CommandCategory::StatusBarOnly),
-                CommandGroup::new(vec![Command::Back], "cancel")
-                    .in_cat(CommandCategory::StatusBarOnly),
-            ],
-            Some(InputKind::NewDatabaseName) => vec![
-                CommandGroup::new(vec![Command::Confirm], "create database")
-                    .in_cat(CommandCategory::StatusBarOnly),
-                CommandGroup::new(vec![Command::Back], "cancel")
-                    .in_cat(CommandCategory::StatusBarOnly),
-            ],
-            _ => vec![],
-        }
+        let Some(kind) = self.kind else {
+            return vec![];
+        };
+
+        vec![
+            CommandGroup::new(vec![Command::Confirm], kind.confirm_label())
+                .in_cat(CommandCategory::StatusBarOnly),
+            CommandGroup::new(vec![Command::Back], "cancel").in_cat(CommandCategory::StatusBarOnly),
+        ]
     }
 
     fn handle_raw_event(&mut self, event: &crossterm::event::Event, queue: &mut SignalQueue) {
@@ -148,7 +183,7 @@ impl Component for InputModal {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::testing::ComponentTestHarness;
+    use crate::{components::input::InputFormatter, testing::ComponentTestHarness};
 
     #[test]
     fn enter_text_then_confirm() {
@@ -181,4 +216,19 @@ mod tests {
         test.expect_message(|m| matches!(m.read_as_app(), Some(AppAction::ExitRawMode)));
         assert_eq!(test.component_mut().input.value(), "");
     }
+
+    #[test]
+    fn hide_secret_text() {
+        let mut test = ComponentTestHarness::new(InputModal::default());
+
+        test.component_mut().input.start_editing();
+        test.component_mut().show_with(InputKind::Password);
+
+        test.given_string("hunter2");
+
+        assert_eq!(
+            test.component().input.formatter.get_formatted().to_string(),
+            "•••••••"
+        );
+    }
 }
@@ -69,6 +69,18 @@ impl InnerList {
     }
 
     fn render(&mut self, frame: &mut Frame, area: Rect, items: Vec<ListItem>, focused: bool) {
+        self.render_with_footer(frame, area, items, focused, vec![]);
+    }
+
+    /// Renders the list with extra titles along the bottom border.
+    fn render_with_footer(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        items: Vec<ListItem>,
+        focused: bool,
+        footer: Vec<Line>,
+    ) {
         let (border_color, bg_color, highlight_text, highlight_bg, unselected_fg) = if focused {
             (
                 self.config.color_map.get(&ColorKey::PanelActiveBorder),
@@ -87,13 +99,16 @@ impl InnerList {
             )
         };
 
+        let block = footer.into_iter().fold(
+            Block::bordered()
+                .bg(bg_color)
+                .title(format!(" {} ", self.title))
+                .border_style(Style::default().fg(border_color)),
+            Block::title_bottom,
+        );
+
         let list = List::new(items)
-            .block(
-                Block::bordered()
-                    .bg(bg_color)
-                    .title(format!(" {} ", self.title))
-                    .border_style(Style::default().fg(border_color)),
-            )
+            .block(block)
             .style(Style::default().fg(unselected_fg))
             .highlight_style(Style::default().bold().fg(highlight_text).bg(highlight_bg));
 
@@ -26,6 +26,7 @@ pub struct Collections {
     focus: Rc<Cell<TabFocus>>,
     pub items: Vec<Collection>,
     list: InnerList,
+    read_only: bool,
 }
 
 impl CloneWithFocus for Collections {
@@ -53,8 +54,7 @@ impl Collections {
             .and_then(|index| self.items.get(index))
     }
 
-    // TODO: remove? only used for hydration
-    fn select(&mut self, collection: Option<Collection>) {
+    pub fn select(&mut self, collection: Option<Collection>) {
         let index = collection.and_then(|collection| {
             self.items
                 .iter()
@@ -75,14 +75,18 @@ impl Component for Collections {
 
     fn commands(&self) -> Vec<CommandGroup> {
         let mut out = InnerList::base_commands();
-        out.append(&mut vec![
+        out.push(
             CommandGroup::new(vec![Command::Confirm], "select collection")
                 .in_cat(CommandCategory::ConnActions),
-            CommandGroup::new(vec![Command::CreateNew], "new collection")
-                .in_cat(CommandCategory::ConnActions),
-            CommandGroup::new(vec![Command::Delete], "drop collection")
-                .in_cat(CommandCategory::ConnActions),
-        ]);
+        );
+        if !self.read_only {
+            out.append(&mut vec![
+                CommandGroup::new(vec![Command::CreateNew], "new collection")
+                    .in_cat(CommandCategory::ConnActions),
+                CommandGroup::new(vec![Command::Delete], "drop collection")
+                    .in_cat(CommandCategory::ConnActions),
+            ]);
+        }
         out
     }
 
@@ -117,7 +121,14 @@ impl Component for Collections {
                 }
             }
             Event::CollectionsUpdated(colls) => {
+                // keep the same item selected, even if it moved in the list
+                let selected = self.get_selected().cloned();
                 self.items.clone_from(colls);
+                if let Some(index) = selected
+                    .and_then(|selected| self.items.iter().position(|item| *item == selected))
+                {
+                    self.list.state.select(Some(index));
+                }
 
                 if self.list.state.selected().is_none() {
                     if let Some(first_coll) = colls.first() {
@@ -127,6 +138,7 @@ impl Component for Collections {
                     }
                 }
             }
+            Event::ReadOnlyModeChanged(read_only) => self.read_only = *read_only,
             Event::ConfirmYes(Command::Delete) => {
                 if self.is_focused() {
                     if let Some(coll) = self.get_selected() {
@@ -193,6 +205,23 @@ mod tests {
         assert_eq!(test.component().list.state.selected(), Some(0));
     }
 
+    #[test]
+    fn keep_selection_on_new_data() {
+        let mut component = Collections {
+            items: vec![get_dummy_collection()],
+            ..Default::default()
+        };
+        component.select(Some(get_dummy_collection()));
+        let mut test = ComponentTestHarness::new(component);
+
+        test.given_event(Event::CollectionsUpdated(vec![
+            Collection::new("another_collection".to_string()),
+            get_dummy_collection(),
+        ]));
+
+        assert_eq!(test.component().list.state.selected(), Some(1));
+    }
+
     #[test]
     fn create_collection() {
         let coll_spec = get_dummy_collection();
@@ -8,6 +8,7 @@ use crate::{
     components::{
         confirm_modal::ConfirmKind,
         connection_screen::ConnScrFocus,
+        input::input_modal::InputKind,
         tab::{CloneWithFocus, TabFocus},
         Component,
     },
@@ -17,17 +18,23 @@ use crate::{
     system::{
         command::{Command, CommandCategory, CommandGroup},
         event::Event,
-        message::{ClientAction, ConnScreenAction, Message, TabAction},
+        message::{AppAction, ConnScreenAction, Message, TabAction},
         signal::SignalQueue,
     },
+    utils::secrets::{encrypt, is_encrypted},
+};
+use crossterm::event::{Event as CrosstermEvent, KeyCode};
+use nucleo::{
+    pattern::{CaseMatching, Normalization, Pattern},
+    Matcher, Utf32Str,
 };
-use crossterm::event::KeyCode;
 use ratatui::{
     layout::Flex,
     prelude::*,
     widgets::{Block, ListItem, Paragraph},
 };
 use serde::{Deserialize, Serialize};
+use tui_input::{backend::crossterm::EventHandler, Input};
 
 #[derive(Debug, Clone)]
 pub struct Connections {
@@ -35,6 +42,11 @@ pub struct Connections {
     pub connection_manager: ConnectionManager,
     pub list: InnerList,
     pub config: Config,
+
+    /// Narrows the list to connections whose name, folder, tags, or
+    /// environment fuzzily match.
+    pub filter: Input,
+    pub filtering: bool,
 }
 
 impl Default for Connections {
@@ -44,6 +56,8 @@ impl Default for Connections {
             connection_manager: ConnectionManager::default(),
             list: InnerList::default(),
             config: Config::default(),
+            filter: Input::default(),
+            filtering: false,
         }
     }
 }
@@ -68,24 +82,161 @@ impl Connections {
             connection_manager,
             list: InnerList::new("Connections", config.clone()),
             config,
+            filter: Input::default(),
+            filtering: false,
         }
     }
 
+    /// The indices of the connections that are shown, grouped by folder and
+    /// narrowed by the filter. Connections without a folder come first.
+    fn visible_indices(&self) -> Vec<usize> {
+        let connections = self.connection_manager.connections();
+        let pattern = Pattern::parse(
+            self.filter.value(),
+            CaseMatching::Smart,
+            Normalization::Smart,
+        );
+        let mut matcher = Matcher::new(nucleo::Config::DEFAULT);
+        let mut buf = vec![];
+
+        let mut indices: Vec<usize> = (0..connections.len())
+            .filter(|idx| {
+                let conn = &connections[*idx];
+                let haystack = [
+                    conn.name.as_str(),
+                    conn.folder.as_deref().unwrap_or_default(),
+                    &conn.tags.join(" "),
+                    &conn
+                        .environment
+                        .map(|env| env.to_string())
+                        .unwrap_or_default(),
+                ]
+                .join(" ");
+                pattern
+                    .score(Utf32Str::new(&haystack, &mut buf), &mut matcher)
+                    .is_some()
+            })
+            .collect();
+
+        // the sort is stable, so connections in the same folder stay in order
+        indices.sort_by(|a, b| connections[*a].folder.cmp(&connections[*b].folder));
+        indices
+    }
+
     fn get_selected_conn(&self) -> Option<Ref<'_, Connection>> {
-        let index = self.list.state.selected()?;
+        let index = *self.visible_indices().get(self.list.state.selected()?)?;
         Ref::filter_map(self.connection_manager.connections(), |v| v.get(index)).ok()
     }
 
     pub fn select_conn(&mut self, conn: &Connection) {
+        let connections = self.connection_manager.connections().clone();
         let index = self
-            .connection_manager
-            .connections()
+            .visible_indices()
             .iter()
-            .position(|c| c == conn);
+            .position(|idx| connections[*idx].id() == conn.id());
         self.list.state.select(index);
     }
 
+    /// Selects the best match after the filter changes.
+    fn select_first_visible(&mut self) {
+        let has_matches = !self.visible_indices().is_empty();
+        self.list.state.select(has_matches.then_some(0));
+    }
+
+    fn stop_filtering(&mut self, queue: &mut SignalQueue) {
+        self.filtering = false;
+        queue.push(Message::to_app(AppAction::ExitRawMode));
+    }
+
+    /// Encrypts the selected connection's connection string, and saves it.
+    fn encrypt_selected(&mut self, passphrase: &str, queue: &mut SignalQueue) {
+        if passphrase.is_empty() {
+            queue.push(Event::ErrorOccurred("Passphrase can't be empty.".into()));
+            return;
+        }
+        let Some(mut conn) = self.get_selected_conn().map(|conn| conn.clone()) else {
+            return;
+        };
+
+        match encrypt(&conn.connection_str, passphrase) {
+            Ok(encrypted) => conn.connection_str = encrypted,
+            Err(err) => {
+                queue.push(Event::ErrorOccurred(err.to_string().into()));
+                return;
+            }
+        }
+
+        if self.connection_manager.update_connection(&conn).is_ok() {
+            queue.push(Event::ConnectionUpdated(conn));
+        } else {
+            queue.push(Event::ErrorOccurred(
+                "Could not save updated connections.".into(),
+            ));
+        }
+    }
+
+    /// Makes the selected connection read-only, and saves it. It can't be made
+    /// writable again from here, so that a read-only connection can be handed
+    /// to someone without them being a keypress away from writing.
+    fn make_read_only_selected(&mut self, queue: &mut SignalQueue) {
+        let Some(mut conn) = self.get_selected_conn().map(|conn| conn.clone()) else {
+            return;
+        };
+        if conn.read_only {
+            queue.push(Event::ErrorOccurred(
+                "The connection is already read-only. To make it writable, edit `read_only` in \
+                 the saved connections file."
+                    .into(),
+            ));
+            return;
+        }
+        conn.read_only = true;
+
+        if self.connection_manager.update_connection(&conn).is_ok() {
+            queue.push(Event::ConnectionUpdated(conn));
+        } else {
+            queue.push(Event::ErrorOccurred(
+                "Could not save updated connections.".into(),
+            ));
+        }
+    }
+
+    fn list_item(&self, conn: &Connection) -> ListItem<'static> {
+        let secondary = self.config.color_map.get(&ColorKey::FgSecondary);
+
+        let mut first_line = vec![];
+        if let Some(folder) = &conn.folder {
+            first_line.push(format!("{folder} ▸ ").fg(secondary));
+        }
+        first_line.push(
+            conn.name
+                .clone()
+                .fg(self.config.color_map.get(&ColorKey::FgPrimary)),
+        );
+        if let Some(environment) = conn.environment {
+            first_line.push(
+                format!(" [{environment}]").fg(self.config.color_map.get(&environment.into())),
+            );
+        }
+        if conn.read_only {
+            first_line.push(" (read-only)".fg(secondary));
+        }
+        for tag in &conn.tags {
+            first_line.push(format!(" #{tag}").fg(secondary));
+        }
+
+        let masked_conn_str = Self::mask_password(&conn.connection_str);
+        ListItem::new(Text::from(vec![
+            Line::from(first_line),
+            Line::from(format!(" {masked_conn_str}")).fg(secondary),
+        ]))
+    }
+
     fn mask_password(conn_str: &str) -> String {
+        if is_encrypted(conn_str) {
+            return "(encrypted)".to_string();
+        }
+
         let Some((before_slashes, after_slashes)) = conn_str.split_once("//") else {
             return String::from(conn_str);
         };
@@ -109,8 +260,24 @@ impl Component for Connections {
     }
 
     fn commands(&self) -> Vec<CommandGroup> {
+        if self.filtering {
+            return vec![
+                CommandGroup::new(vec![Command::Confirm], "connect")
+                    .in_cat(CommandCategory::StatusBarOnly),
+                CommandGroup::new(vec![Command::Back], "clear filter")
+                    .in_cat(CommandCategory::StatusBarOnly),
+            ];
+        }
+
         let mut out = InnerList::base_commands();
+        if !self.filter.value().is_empty() {
+            out.push(
+                CommandGroup::new(vec![Command::Back], "clear filter")
+                    .in_cat(CommandCategory::AppNav),
+            );
+        }
         out.append(&mut vec![
+            CommandGroup::new(vec![Command::Search], "filter").in_cat(CommandCategory::AppNav),
             CommandGroup::new(vec![Command::Confirm], "connect")
                 .in_cat(CommandCategory::CollActions),
             CommandGroup::new(vec![Command::CreateNew], "new connection")
@@ -119,18 +286,42 @@ impl Component for Connections {
                 .in_cat(CommandCategory::CollActions),
             CommandGroup::new(vec![Command::Delete], "delete connection")
                 .in_cat(CommandCategory::CollActions),
+            CommandGroup::new(vec![Command::Encrypt], "encrypt connection")
+                .in_cat(CommandCategory::CollActions),
+            CommandGroup::new(vec![Command::MakeReadOnly], "make read-only")
+                .in_cat(CommandCategory::CollActions),
         ]);
         out
     }
 
     fn handle_command(&mut self, command: &Command, queue: &mut SignalQueue) {
+        if self.filtering {
+            match command {
+                Command::Confirm => self.stop_filtering(queue),
+                Command::Back => {
+                    self.filter.reset();
+                    self.select_first_visible();
+                    self.stop_filtering(queue);
+                    return;
+                }
+                _ => return,
+            }
+        }
+
         self.list
-            .handle_base_command(command, self.connection_manager.connections().len(), queue);
+            .handle_base_command(command, self.visible_indices().len(), queue);
         match command {
+            Command::Search => {
+                self.filtering = true;
+                queue.push(Message::to_app(AppAction::EnterRawMode));
+            }
+            Command::Back if !self.filter.value().is_empty() => {
+                self.filter.reset();
+                self.select_first_visible();
+            }
             Command::Confirm => {
                 if let Some(conn) = self.get_selected_conn() {
                     queue.push(Event::ConnectionSelected(conn.clone()));
-                    queue.push(Message::to_client(ClientAction::Connect(conn.clone())));
                 }
             }
             Command::CreateNew => {
@@ -138,7 +329,7 @@ impl Component for Connections {
             }
             Command::Edit => {
                 if let Some(conn) = self.get_selected_conn() {
-                    queue.push(Message::to_conn_scr(ConnScreenAction::StartEditingConn(conn.clone())));
+                    queue.push(Message::to_conn_scr(ConnScreenAction::StartEditingConn(Box::new(conn.clone()))));
                 }
             }
             Command::Delete => {
@@ -146,14 +337,61 @@ impl Component for Connections {
                         ConfirmKind::DeleteConnection,
                     )));
             }
+            Command::MakeReadOnly => self.make_read_only_selected(queue),
+            Command::Encrypt
+                if self
+                    .get_selected_conn()
+                    .is_some_and(|conn| !is_encrypted(&conn.connection_str)) =>
+            {
+                queue.push(Message::to_tab(TabAction::RequestInput(InputKind::NewPassphrase)));
+            }
             _ => {}
         }
     }
 
+    fn handle_raw_event(&mut self, event: &CrosstermEvent, queue: &mut SignalQueue) {
+        if !self.filtering {
+            return;
+        }
+
+        // the selection can still be moved while typing
+        if let CrosstermEvent::Key(key) = event {
+            let command = match key.code {
+                KeyCode::Up => Some(Command::NavUp),
+                KeyCode::Down => Some(Command::NavDown),
+                _ => None,
+            };
+            if let Some(command) = command {
+                let num_items = self.visible_indices().len();
+                self.list.handle_base_command(&command, num_items, queue);
+                return;
+            }
+        }
+
+        if self.filter.handle_event(event).is_some_and(|c| c.value) {
+            self.select_first_visible();
+            queue.push(Event::ListSelectionChanged);
+        }
+    }
+
     fn handle_event(&mut self, event: &Event, queue: &mut SignalQueue) {
+        match event {
+            Event::InputConfirmed(InputKind::NewPassphrase, passphrase) => {
+                self.encrypt_selected(passphrase, queue);
+            }
+            // editing a connection can move it to another folder
+            Event::ConnectionUpdated(conn) => self.select_conn(conn),
+            _ => {}
+        }
+
         // only process the confirmation if this component is focused
         if self.is_focused() && matches!(event, Event::ConfirmYes(Command::Delete)) {
-            let Some(index_to_delete) = self.list.state.selected() else {
+            let Some(index_to_delete) = self
+                .list
+                .state
+                .selected()
+                .and_then(|idx| self.visible_indices().get(idx).copied())
+            else {
                 return;
             };
 
@@ -212,24 +450,34 @@ impl Component for Connections {
 
             frame.render_widget(Paragraph::new(vec![line1, line2]), layout[0]);
         } else {
-            let items: Vec<ListItem> = self
-                .connection_manager
-                .connections()
+            let connections = self.connection_manager.connections();
+            let visible_indices = self.visible_indices();
+            let items: Vec<ListItem> = visible_indices
                 .iter()
-                .map(|item| {
-                    let masked_conn_str = Self::mask_password(&item.connection_str);
-
-                    let text = Text::from(vec![
-                        Line::from(item.name.clone())
-                            .fg(self.config.color_map.get(&ColorKey::FgPrimary)),
-                        Line::from(format!(" {masked_conn_str}"))
-                            .fg(self.config.color_map.get(&ColorKey::FgSecondary)),
-                    ]);
-                    ListItem::new(text)
-                })
+                .map(|idx| self.list_item(&connections[*idx]))
                 .collect();
 
-            self.list.render(frame, area, items, self.is_focused());
+            let footer = if self.filtering || !self.filter.value().is_empty() {
+                let color = self.config.color_map.get(&ColorKey::DocumentsSearch);
+                vec![
+                    Line::from(format!(" filter: \"{}\" ", self.filter.value()))
+                        .left_aligned()
+                        .fg(color),
+                    Line::from(format!(
+                        " {} of {} ",
+                        visible_indices.len(),
+                        connections.len()
+                    ))
+                    .right_aligned()
+                    .fg(color),
+                ]
+            } else {
+                vec![]
+            };
+            drop(connections);
+
+            self.list
+                .render_with_footer(frame, area, items, self.is_focused(), footer);
         }
     }
 }
@@ -262,6 +510,98 @@ impl PersistedComponent for Connections {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing::{mock_storage::MockStorage, ComponentTestHarness};
+
+    #[test]
+    fn encrypt_selected_connection() {
+        let conn = Connection::new("conn".to_string(), "mongodb://a:b@host".to_string());
+        let connection_manager = ConnectionManager::new(vec![conn], Rc::new(MockStorage::default()));
+        let mut test = ComponentTestHarness::new(Connections {
+            connection_manager,
+            ..Default::default()
+        });
+
+        test.given_command(Command::NavDown);
+        test.given_command(Command::Encrypt);
+        test.expect_message(|m| {
+            matches!(
+                m.read_as_tab(),
+                Some(TabAction::RequestInput(InputKind::NewPassphrase))
+            )
+        });
+
+        test.given_event(Event::InputConfirmed(
+            InputKind::NewPassphrase,
+            "hunter2".to_string(),
+        ));
+        test.expect_event(
+            |e| matches!(e, Event::ConnectionUpdated(c) if is_encrypted(&c.connection_str)),
+        );
+    }
+
+    #[test]
+    fn only_make_connections_read_only() {
+        let conn = Connection::new("prod".to_string(), "mongodb://host".to_string());
+        let connection_manager =
+            ConnectionManager::new(vec![conn], Rc::new(MockStorage::default()));
+        let mut test = ComponentTestHarness::new(Connections {
+            connection_manager,
+            ..Default::default()
+        });
+
+        test.given_command(Command::NavDown);
+        test.given_command(Command::MakeReadOnly);
+        test.expect_event(|e| matches!(e, Event::ConnectionUpdated(c) if c.read_only));
+
+        test.given_command(Command::MakeReadOnly);
+        test.expect_event(|e| matches!(e, Event::ErrorOccurred(..)));
+        assert!(test
+            .component()
+            .connection_manager
+            .connections()
+            .iter()
+            .all(|c| c.read_only));
+    }
+
+    #[test]
+    fn filter_by_tags() {
+        let mut tagged = Connection::new("orders".to_string(), "mongodb://host1".to_string());
+        tagged.tags = vec!["analytics".to_string()];
+        let untagged = Connection::new("users".to_string(), "mongodb://host2".to_string());
+        let connection_manager =
+            ConnectionManager::new(vec![untagged, tagged], Rc::new(MockStorage::default()));
+        let mut test = ComponentTestHarness::new(Connections {
+            connection_manager,
+            ..Default::default()
+        });
+
+        test.given_command(Command::Search);
+        test.expect_message(|m| matches!(m.read_as_app(), Some(AppAction::EnterRawMode)));
+        test.given_string("anlytcs");
+        assert_eq!(test.component().visible_indices(), vec![1]);
+
+        test.given_command(Command::Confirm);
+        test.expect_event(|e| matches!(e, Event::ConnectionSelected(c) if c.name == "orders"));
+
+        // clearing the filter shows every connection again
+        test.given_command(Command::Back);
+        assert_eq!(test.component().visible_indices(), vec![0, 1]);
+    }
+
+    #[test]
+    fn group_by_folder() {
+        let mut work = Connection::new("b".to_string(), "url".to_string());
+        work.folder = Some("work".to_string());
+        let unfiled = Connection::new("a".to_string(), "url".to_string());
+        let connection_manager =
+            ConnectionManager::new(vec![work, unfiled], Rc::new(MockStorage::default()));
+        let test = ComponentTestHarness::new(Connections {
+            connection_manager,
+            ..Default::default()
+        });
+
+        assert_eq!(test.component().visible_indices(), vec![1, 0]);
+    }
 
     #[test]
     fn test_mask_password() {
@@ -28,8 +28,9 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Default, Clone)]
 pub struct Databases {
     focus: Rc<Cell<TabFocus>>,
-    items: Vec<Database>,
+    pub items: Vec<Database>,
     list: InnerList,
+    read_only: bool,
 }
 
 impl CloneWithFocus for Databases {
@@ -57,7 +58,7 @@ impl Databases {
             .and_then(|index| self.items.get(index))
     }
 
-    fn select(&mut self, database: Option<Database>) {
+    pub fn select(&mut self, database: Option<Database>) {
         let index = database
             .and_then(|database| self.items.iter().position(|db| *db.name == database.name));
         self.list.state.select(index);
@@ -75,14 +76,18 @@ impl Component for Databases {
 
     fn commands(&self) -> Vec<CommandGroup> {
         let mut out = InnerList::base_commands();
-        out.append(&mut vec![
+        out.push(
             CommandGroup::new(vec![Command::Confirm], "select database")
                 .in_cat(CommandCategory::DbActions),
-            CommandGroup::new(vec![Command::CreateNew], "new database")
-                .in_cat(CommandCategory::DbActions),
-            CommandGroup::new(vec![Command::Delete], "drop database")
-                .in_cat(CommandCategory::DbActions),
-        ]);
+        );
+        if !self.read_only {
+            out.append(&mut vec![
+                CommandGroup::new(vec![Command::CreateNew], "new database")
+                    .in_cat(CommandCategory::DbActions),
+                CommandGroup::new(vec![Command::Delete], "drop database")
+                    .in_cat(CommandCategory::DbActions),
+            ]);
+        }
         out
     }
 
@@ -119,7 +124,14 @@ impl Component for Databases {
                 }
             }
             Event::DatabasesUpdated(dbs) => {
+                // keep the same item selected, even if it moved in the list
+                let selected = self.get_selected().cloned();
                 self.items.clone_from(dbs);
+                if let Some(index) = selected
+                    .and_then(|selected| self.items.iter().position(|item| *item == selected))
+                {
+                    self.list.state.select(Some(index));
+                }
 
                 if self.list.state.selected().is_none() {
                     if let Some(first_db) = dbs.first() {
@@ -129,6 +141,7 @@ impl Component for Databases {
                     }
                 }
             }
+            Event::ReadOnlyModeChanged(read_only) => self.read_only = *read_only,
             Event::ConfirmYes(Command::Delete) => {
                 if self.is_focused() {
                     if let Some(db) = self.get_selected() {
@@ -233,6 +246,22 @@ mod tests {
         });
     }
 
+    #[test]
+    fn hide_writes_when_read_only() {
+        let mut test = ComponentTestHarness::new(Databases::default());
+
+        test.given_event(Event::ReadOnlyModeChanged(true));
+        let commands: Vec<_> = test
+            .component()
+            .commands()
+            .into_iter()
+            .flat_map(|group| group.commands)
+            .collect();
+
+        assert!(!commands.contains(&Command::CreateNew));
+        assert!(!commands.contains(&Command::Delete));
+    }
+
     #[test]
     fn persisting_and_hydrate() {
         let db_spec = get_dummy_database();
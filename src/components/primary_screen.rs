@@ -12,14 +12,19 @@ use crate::{
         Component,
     },
     config::Config,
+    model::{
+        collection::Collection, database::Database, launch::LaunchTarget,
+        reference::ReferenceTarget,
+    },
     persistence::PersistedComponent,
     system::{
         command::{Command, CommandCategory, CommandGroup},
         event::Event,
-        message::{Message, PrimScreenAction},
+        message::{Message, PrimScreenAction, TabAction},
         signal::SignalQueue,
     },
 };
+use mongodb::bson::Document;
 use ratatui::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::{cell::Cell, rc::Rc};
@@ -73,6 +78,53 @@ impl PrimaryScreen<'_> {
         }
     }
 
+    /// Switches to the database and collection of a referenced document and
+    /// queries for just that document.
+    fn show_referenced_doc(&mut self, target: &ReferenceTarget, queue: &mut SignalQueue) {
+        self.db_list.select(Some(target.db.clone()));
+        self.coll_list.items.clone_from(&target.colls);
+        self.coll_list.select(Some(target.coll.clone()));
+        self.query_input.set_filter(&target.filter);
+
+        queue.push(Event::DatabaseHighlighted(target.db.clone()));
+        queue.push(Event::DatabaseSelected(target.db.clone()));
+        queue.push(Event::CollectionSelected(target.coll.clone()));
+        queue.push(Event::DocumentPageChanged(0));
+        queue.push(Event::DocFilterUpdated(target.filter.clone()));
+        queue.push(Event::DocProjectionUpdated(Document::new()));
+        queue.push(Event::DocSortUpdated(Document::new()));
+        queue.push(Event::DocOptionsUpdated(Document::new()));
+    }
+
+    /// Opens a database, and maybe a collection with a query, as if the user
+    /// had selected them.
+    fn open_target(&mut self, target: &LaunchTarget, queue: &mut SignalQueue) {
+        let db = Database::new(target.db.clone());
+        self.db_list.items = vec![db.clone()];
+        self.db_list.select(Some(db.clone()));
+        queue.push(Event::DatabaseHighlighted(db.clone()));
+        queue.push(Event::DatabaseSelected(db));
+
+        let Some(coll) = &target.coll else {
+            self.focus.set(TabFocus::PrimScr(PrimScrFocus::CollList));
+            queue.push(Event::FocusedChanged);
+            return;
+        };
+        let coll = Collection::new(coll.clone());
+        self.coll_list.items = vec![coll.clone()];
+        self.coll_list.select(Some(coll.clone()));
+        self.query_input
+            .set_query(&target.filter, &target.projection, &target.sort);
+
+        queue.push(Event::CollectionSelected(coll));
+        queue.push(Event::DocumentPageChanged(0));
+        queue.push(Event::DocFilterUpdated(target.filter.clone()));
+        queue.push(Event::DocProjectionUpdated(target.projection.clone()));
+        queue.push(Event::DocSortUpdated(target.sort.clone()));
+        self.focus.set(TabFocus::PrimScr(PrimScrFocus::DocTree));
+        queue.push(Event::FocusedChanged);
+    }
+
     /// Narrows the shared `AppFocus` variable into the focus enum for this componenent
     fn internal_focus(&self) -> Option<PrimScrFocus> {
         match self.focus.get() {
@@ -102,6 +154,10 @@ impl Component for PrimaryScreen<'_> {
             out.push(
                 CommandGroup::new(vec![Command::Back], "back").in_cat(CommandCategory::AppNav),
             );
+            out.push(
+                CommandGroup::new(vec![Command::ShowAuditLog], "audit log")
+                    .in_cat(CommandCategory::AppNav),
+            );
         }
 
         match self.internal_focus() {
@@ -195,6 +251,9 @@ impl Component for PrimaryScreen<'_> {
                 }
                 _ => {}
             },
+            Command::ShowAuditLog if !self.query_input.is_editing() => {
+                queue.push(Message::to_tab(TabAction::RequestAuditLog));
+            }
             _ => {}
         }
     }
@@ -218,6 +277,7 @@ impl Component for PrimaryScreen<'_> {
         self.db_list.handle_event(event, queue);
         self.coll_list.handle_event(event, queue);
         self.doc_tree.handle_event(event, queue);
+        self.query_input.handle_event(event, queue);
     }
 
     fn handle_message(&mut self, message: &Message, queue: &mut SignalQueue) {
@@ -226,6 +286,13 @@ impl Component for PrimaryScreen<'_> {
                 self.focus.set(TabFocus::PrimScr(*focus));
                 queue.push(Event::FocusedChanged);
             }
+            Some(PrimScreenAction::UpdateQuery(update)) => {
+                self.query_input.apply_update(update.clone(), queue);
+            }
+            Some(PrimScreenAction::ShowReferencedDoc(target)) => {
+                self.show_referenced_doc(target, queue);
+            }
+            Some(PrimScreenAction::OpenTarget(target)) => self.open_target(target, queue),
             None => {}
         }
     }
@@ -245,7 +312,7 @@ impl Component for PrimaryScreen<'_> {
         let sidebar_top = sidebar_layout[0];
         let sidebar_btm = sidebar_layout[1];
 
-        let query_input_size = if self.query_input.is_expanded() { 7 } else { 3 };
+        let query_input_size = if self.query_input.is_expanded() { 9 } else { 3 };
 
         let main_view_layout = Layout::default()
             .direction(Direction::Vertical)
@@ -299,3 +366,38 @@ impl PersistedComponent for PrimaryScreen<'_> {
         self.query_input.hydrate(storage.query_input);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::ComponentTestHarness;
+    use mongodb::bson::doc;
+
+    #[test]
+    fn open_collection_with_query() {
+        let mut test = ComponentTestHarness::new(PrimaryScreen::default());
+        let target = LaunchTarget {
+            db: "shop".to_string(),
+            coll: Some("orders".to_string()),
+            filter: doc! { "status": "open" },
+            projection: Document::new(),
+            sort: doc! { "total": -1 },
+        };
+
+        test.given_message(Message::to_prim_scr(PrimScreenAction::OpenTarget(
+            Box::new(target),
+        )));
+
+        test.expect_event(
+            |e| matches!(e, Event::CollectionSelected(coll) if coll.name == "orders"),
+        );
+        test.expect_event(
+            |e| matches!(e, Event::DocFilterUpdated(filter) if *filter == doc! { "status": "open" }),
+        );
+        assert_eq!(
+            test.component().internal_focus(),
+            Some(PrimScrFocus::DocTree)
+        );
+        assert!(test.component().query_input.is_expanded());
+    }
+}
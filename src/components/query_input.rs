@@ -1,5 +1,6 @@
 use std::{cell::Cell, rc::Rc};
 
+use mongodb::bson::{doc, Bson, Document};
 use ratatui::{
     layout::Offset,
     prelude::*,
@@ -32,6 +33,7 @@ pub enum QueryInFocus {
     Filter,
     Projection,
     Sort,
+    Options,
 }
 
 impl PartialEq<DocInputKind> for QueryInFocus {
@@ -41,6 +43,7 @@ impl PartialEq<DocInputKind> for QueryInFocus {
             (Self::Filter, DocInputKind::Filter)
                 | (Self::Projection, DocInputKind::Projection)
                 | (Self::Sort, DocInputKind::Sort)
+                | (Self::Options, DocInputKind::Options)
         )
     }
 }
@@ -57,10 +60,31 @@ impl From<DocInputKind> for QueryInFocus {
             DocInputKind::Filter => Self::Filter,
             DocInputKind::Projection => Self::Projection,
             DocInputKind::Sort => Self::Sort,
+            DocInputKind::Options => Self::Options,
         }
     }
 }
 
+/// A change to the query made by pointing at a value in the documents view,
+/// rather than by typing.
+#[derive(Debug, Clone)]
+pub enum QueryUpdate {
+    /// Only match documents where the field at the path has the given value,
+    /// or (if `negate` is set) where it doesn't.
+    Filter {
+        path: String,
+        value: Bson,
+        negate: bool,
+    },
+
+    /// Sort by the field at the path, or switch its direction if the results
+    /// are already sorted by it.
+    ToggleSort(String),
+
+    /// Include or exclude the field at the path in the projection.
+    Project { path: String, include: bool },
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct QueryInput {
     focus: Rc<Cell<TabFocus>>,
@@ -69,6 +93,7 @@ pub struct QueryInput {
     filter_input: DocumentInput,
     projection_input: DocumentInput,
     sort_input: DocumentInput,
+    options_input: DocumentInput,
 
     expanded: bool,
 }
@@ -79,6 +104,7 @@ impl CloneWithFocus for QueryInput {
             filter_input: self.filter_input.clone_with_focus(focus.clone()),
             projection_input: self.projection_input.clone_with_focus(focus.clone()),
             sort_input: self.sort_input.clone_with_focus(focus.clone()),
+            options_input: self.options_input.clone_with_focus(focus.clone()),
             focus,
             config: self.config.clone(),
             expanded: self.expanded,
@@ -107,6 +133,12 @@ impl QueryInput {
         let sort_input = DocumentInput::new(
             DocInputKind::Sort,
             focus.clone(),
+            cursor_pos.clone(),
+            config.clone(),
+        );
+        let options_input = DocumentInput::new(
+            DocInputKind::Options,
+            focus.clone(),
             cursor_pos,
             config.clone(),
         );
@@ -116,6 +148,7 @@ impl QueryInput {
             filter_input,
             projection_input,
             sort_input,
+            options_input,
             expanded: false,
         }
     }
@@ -124,6 +157,7 @@ impl QueryInput {
         self.filter_input.is_editing()
             || self.projection_input.is_editing()
             || self.sort_input.is_editing()
+            || self.options_input.is_editing()
     }
 
     pub const fn is_expanded(&self) -> bool {
@@ -153,6 +187,10 @@ impl QueryInput {
                 self.projection_input.focus();
                 true
             }
+            Some(QueryInFocus::Options) => {
+                self.sort_input.focus();
+                true
+            }
             _ => false,
         }
     }
@@ -172,13 +210,83 @@ impl QueryInput {
                 self.sort_input.focus();
                 true
             }
+            Some(QueryInFocus::Sort) => {
+                self.options_input.focus();
+                true
+            }
             _ => false,
         }
     }
 
+    /// Applies the update to the filter, sort, or projection, updating the
+    /// text in the corresponding input.
+    pub fn apply_update(&mut self, update: QueryUpdate, queue: &mut SignalQueue) {
+        let is_filter = matches!(update, QueryUpdate::Filter { .. });
+        let (input, doc) = match update {
+            QueryUpdate::Filter {
+                path,
+                value,
+                negate,
+            } => {
+                let mut filter = self.filter_input.get_doc().unwrap_or_default();
+                if negate {
+                    filter.insert(path, doc! { "$ne": value });
+                } else {
+                    filter.insert(path, value);
+                }
+                (&mut self.filter_input, filter)
+            }
+            QueryUpdate::ToggleSort(path) => {
+                let mut sort = self.sort_input.get_doc().unwrap_or_default();
+                let direction = if sort.get(&path).is_some_and(is_included) {
+                    -1
+                } else {
+                    1
+                };
+
+                // sorting by a new field replaces the current sort
+                if !sort.contains_key(&path) {
+                    sort.clear();
+                }
+                sort.insert(path, direction);
+                (&mut self.sort_input, sort)
+            }
+            QueryUpdate::Project { path, include } => {
+                let projection = self.projection_input.get_doc().unwrap_or_default();
+                let projection = update_projection(projection, path, include);
+                (&mut self.projection_input, projection)
+            }
+        };
+
+        input.set_doc(&doc);
+        queue.push(Event::DocumentPageChanged(0));
+        queue.push(input.doc_updated_event(doc));
+
+        // the sort and projection inputs are only visible when expanded
+        if !is_filter && !self.expanded {
+            self.expanded = true;
+            queue.push(Event::QueryInputExpanded);
+        }
+    }
+
+    /// Replaces the query with one that only has the given filter.
+    pub fn set_filter(&mut self, filter: &Document) {
+        self.set_query(filter, &Document::new(), &Document::new());
+    }
+
+    /// Replaces the query, expanding the input if it has a projection or sort
+    /// so that they can be seen.
+    pub fn set_query(&mut self, filter: &Document, projection: &Document, sort: &Document) {
+        self.filter_input.set_doc(filter);
+        self.projection_input.set_doc(projection);
+        self.sort_input.set_doc(sort);
+        self.options_input.set_doc(&Document::new());
+        self.expanded |= !projection.is_empty() || !sort.is_empty();
+    }
+
     pub fn focus_last(&self) {
         if self.is_expanded() {
-            self.sort_input.focus();
+            self.options_input.focus();
         } else {
             self.filter_input.focus();
         }
@@ -257,6 +365,7 @@ impl QueryInput {
             DocInputKind::Filter => &mut self.filter_input,
             DocInputKind::Projection => &mut self.projection_input,
             DocInputKind::Sort => &mut self.sort_input,
+            DocInputKind::Options => &mut self.options_input,
         };
         let label = &input.name()[0..4];
         let label_and_border = format!("{}{label}:", symbols::line::NORMAL.vertical);
@@ -318,7 +427,7 @@ impl QueryInput {
 
     /// renders at the bottom of `area`
     fn render_bottom_border(&self, frame: &mut Frame, area: Rect) {
-        let focused = matches!(self.internal_focus(), Some(QueryInFocus::Sort));
+        let focused = matches!(self.internal_focus(), Some(QueryInFocus::Options));
         let (border_color, bg_color) = self.get_colors(focused);
         let style = Style::default().fg(border_color).bg(bg_color);
 
@@ -375,6 +484,65 @@ impl QueryInput {
             .buffer_mut()
             .set_string(area.x, area.y, &border_line, style);
     }
+
+    /// renders at the top of `area`
+    fn render_horizontal_divider3(&self, frame: &mut Frame, area: Rect) {
+        let focused = matches!(
+            self.internal_focus(),
+            Some(QueryInFocus::Sort | QueryInFocus::Options)
+        );
+        let (border_color, bg_color) = self.get_colors(focused);
+        let style = Style::default().fg(border_color).bg(bg_color);
+
+        let horizontal_line = symbols::line::HORIZONTAL.repeat((area.width - 2) as usize);
+        let border_line = format!(
+            "{}{}{}",
+            symbols::line::NORMAL.vertical_right,
+            horizontal_line,
+            symbols::line::NORMAL.vertical_left
+        );
+        frame
+            .buffer_mut()
+            .set_string(area.x, area.y, &border_line, style);
+    }
+}
+
+/// Whether a value in a projection includes a field (rather than excluding
+/// it), or a value in a sort is ascending.
+fn is_included(value: &Bson) -> bool {
+    match value {
+        Bson::Boolean(b) => *b,
+        Bson::Int32(n) => *n > 0,
+        Bson::Int64(n) => *n > 0,
+        Bson::Double(n) => *n > 0.0,
+        // expressions like `$slice` compute a value for the field
+        _ => true,
+    }
+}
+
+/// Includes or excludes a field in a projection. A projection can't mix
+/// included and excluded fields (other than `_id`), so if the projection
+/// already has fields of the other kind, the field is removed from them.
+fn update_projection(mut projection: Document, path: String, include: bool) -> Document {
+    let has_fields = |projection: &Document, included: bool| {
+        projection
+            .iter()
+            .any(|(key, value)| key != "_id" && is_included(value) == included)
+    };
+
+    if path == "_id" || !has_fields(&projection, !include) {
+        projection.insert(path, i32::from(include));
+    } else {
+        projection.remove(&path);
+
+        // excluding the last included field leaves an empty projection,
+        // which would include everything
+        if !include && !has_fields(&projection, true) {
+            projection.insert(path, 0);
+        }
+    }
+
+    projection
 }
 
 impl Component for QueryInput {
@@ -400,6 +568,9 @@ impl Component for QueryInput {
             Some(QueryInFocus::Sort) => {
                 out.append(&mut self.sort_input.commands());
             }
+            Some(QueryInFocus::Options) => {
+                out.append(&mut self.options_input.commands());
+            }
             None => {}
         }
         out
@@ -419,6 +590,7 @@ impl Component for QueryInput {
                     self.projection_input.handle_command(command, queue);
                 }
                 Some(QueryInFocus::Sort) => self.sort_input.handle_command(command, queue),
+                Some(QueryInFocus::Options) => self.options_input.handle_command(command, queue),
                 None => {}
             }
         }
@@ -429,10 +601,18 @@ impl Component for QueryInput {
             Some(QueryInFocus::Filter) => self.filter_input.handle_raw_event(event, queue),
             Some(QueryInFocus::Projection) => self.projection_input.handle_raw_event(event, queue),
             Some(QueryInFocus::Sort) => self.sort_input.handle_raw_event(event, queue),
+            Some(QueryInFocus::Options) => self.options_input.handle_raw_event(event, queue),
             None => {}
         }
     }
 
+    fn handle_event(&mut self, event: &Event, queue: &mut SignalQueue) {
+        self.filter_input.handle_event(event, queue);
+        self.projection_input.handle_event(event, queue);
+        self.sort_input.handle_event(event, queue);
+        self.options_input.handle_event(event, queue);
+    }
+
     fn focus(&self) {
         self.filter_input.focus();
     }
@@ -466,6 +646,15 @@ impl Component for QueryInput {
                 rect.offset(Offset { x: 0, y: 1 }),
             );
             self.render_horizontal_divider2(frame, rect);
+
+            rect = rect.offset(Offset { x: 0, y: 2 });
+
+            self.render_subcomponent(
+                DocInputKind::Options,
+                frame,
+                rect.offset(Offset { x: 0, y: 1 }),
+            );
+            self.render_horizontal_divider3(frame, rect);
             self.render_bottom_border(frame, rect);
         } else {
             let (border_color, bg_color) = self.get_color_for(QueryInFocus::Filter);
@@ -483,6 +672,12 @@ impl Component for QueryInput {
                 area.offset(Offset { x: 0, y: 1 }).inner(Margin::new(1, 0)),
             );
         }
+
+        // render the suggestions last so they cover the inputs below
+        self.filter_input.render_completions(frame);
+        self.projection_input.render_completions(frame);
+        self.sort_input.render_completions(frame);
+        self.options_input.render_completions(frame);
     }
 }
 
@@ -491,6 +686,8 @@ pub struct PersistedQueryInput {
     filter_input: String,
     projection_input: String,
     sort_input: String,
+    #[serde(default)]
+    options_input: String,
     expanded: bool,
 }
 
@@ -502,6 +699,7 @@ impl PersistedComponent for QueryInput {
             filter_input: self.filter_input.persist(),
             projection_input: self.projection_input.persist(),
             sort_input: self.sort_input.persist(),
+            options_input: self.options_input.persist(),
             expanded: self.expanded,
         }
     }
@@ -510,6 +708,145 @@ impl PersistedComponent for QueryInput {
         self.filter_input.hydrate(storage.filter_input);
         self.projection_input.hydrate(storage.projection_input);
         self.sort_input.hydrate(storage.sort_input);
+        self.options_input.hydrate(storage.options_input);
         self.expanded = storage.expanded;
     }
 }
+
+#[expect(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{system::signal::Signal, testing::ComponentTestHarness};
+
+    fn query_input() -> QueryInput {
+        QueryInput::new(Rc::default(), Rc::default(), Config::default())
+    }
+
+    fn events(queue: &mut SignalQueue) -> Vec<Event> {
+        std::iter::from_fn(|| queue.pop())
+            .filter_map(|signal| match signal {
+                Signal::Event(event) => Some(event),
+                Signal::Message(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn add_value_to_filter() {
+        let mut query_input = query_input();
+        let mut queue = SignalQueue::default();
+
+        query_input.apply_update(
+            QueryUpdate::Filter {
+                path: "a.b".into(),
+                value: Bson::Int32(5),
+                negate: false,
+            },
+            &mut queue,
+        );
+        query_input.apply_update(
+            QueryUpdate::Filter {
+                path: "c".into(),
+                value: Bson::String("x".into()),
+                negate: true,
+            },
+            &mut queue,
+        );
+
+        // numbers are read back from the input's text as 64-bit integers
+        let expected = doc! { "a.b": 5_i64, "c": { "$ne": "x" } };
+        assert_eq!(query_input.filter_input.get_doc().unwrap(), expected);
+        assert!(events(&mut queue)
+            .iter()
+            .any(|event| matches!(event, Event::DocFilterUpdated(doc) if *doc == expected)));
+        assert!(!query_input.is_expanded());
+    }
+
+    #[test]
+    fn toggle_sort_direction() {
+        let mut query_input = query_input();
+        let mut queue = SignalQueue::default();
+
+        query_input.apply_update(QueryUpdate::ToggleSort("a".into()), &mut queue);
+        assert_eq!(
+            query_input.sort_input.get_doc().unwrap(),
+            doc! { "a": 1_i64 }
+        );
+        assert!(query_input.is_expanded());
+
+        query_input.apply_update(QueryUpdate::ToggleSort("a".into()), &mut queue);
+        assert_eq!(
+            query_input.sort_input.get_doc().unwrap(),
+            doc! { "a": -1_i64 }
+        );
+
+        query_input.apply_update(QueryUpdate::ToggleSort("b".into()), &mut queue);
+        assert_eq!(
+            query_input.sort_input.get_doc().unwrap(),
+            doc! { "b": 1_i64 }
+        );
+        assert!(events(&mut queue)
+            .iter()
+            .any(|event| matches!(event, Event::DocSortUpdated(doc) if *doc == doc! { "b": 1 })));
+    }
+
+    #[test]
+    fn include_and_exclude_fields() {
+        assert_eq!(
+            update_projection(doc! {}, "a".into(), true),
+            doc! { "a": 1 }
+        );
+        assert_eq!(
+            update_projection(doc! { "a": 1, "b": 1 }, "a".into(), false),
+            doc! { "b": 1 }
+        );
+        assert_eq!(
+            update_projection(doc! { "_id": 0, "a": 1 }, "a".into(), false),
+            doc! { "_id": 0, "a": 0 }
+        );
+        assert_eq!(
+            update_projection(doc! { "a": 0, "b": 0 }, "a".into(), true),
+            doc! { "b": 0 }
+        );
+        assert_eq!(
+            update_projection(doc! { "a": 1 }, "_id".into(), false),
+            doc! { "a": 1, "_id": 0 }
+        );
+    }
+
+    #[test]
+    fn check_and_persist_options() {
+        let mut test = ComponentTestHarness::new(query_input());
+        test.given_command(Command::ExpandCollapse);
+        test.component().focus_last();
+        assert_eq!(
+            test.component().internal_focus(),
+            Some(QueryInFocus::Options)
+        );
+
+        test.component_mut().options_input.start_editing();
+        test.component_mut()
+            .options_input
+            .hydrate("{ batchSize: 5 }".into());
+        test.given_command(Command::Confirm);
+        test.expect_event(
+            |e| matches!(e, Event::ErrorOccurred(err) if err.to_string().contains("Unknown query option")),
+        );
+
+        test.component_mut()
+            .options_input
+            .hydrate("{ hint: \"email_1\", limit: 100 }".into());
+        test.given_command(Command::Confirm);
+        test.expect_event(|e| {
+            matches!(e, Event::DocOptionsUpdated(doc) if *doc == doc! { "hint": "email_1", "limit": 100_i64 })
+        });
+
+        let mut hydrated = query_input();
+        hydrated.hydrate(test.component().persist());
+        assert_eq!(
+            hydrated.options_input.get_doc().unwrap(),
+            doc! { "hint": "email_1", "limit": 100_i64 }
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Clear, List, ListItem, ListState, Paragraph},
+};
+use std::{cell::Cell, rc::Rc};
+
+use crate::{
+    client::search::SearchResult,
+    components::{tab::TabFocus, Component},
+    config::{color_map::ColorKey, Config},
+    system::{
+        command::{Command, CommandCategory, CommandGroup},
+        event::Event,
+        signal::SignalQueue,
+    },
+    utils::{doc_diff::bson_preview, field_edit::dotted_path},
+};
+
+use super::tab::CloneWithFocus;
+
+const SEARCH_RESULTS_MODAL_WIDTH: u16 = 100;
+
+#[derive(Debug, Default, Clone)]
+pub struct SearchResultsModal {
+    focus: Rc<Cell<TabFocus>>,
+    config: Config,
+
+    text: String,
+    results: Vec<SearchResult>,
+    state: ListState,
+}
+
+impl CloneWithFocus for SearchResultsModal {
+    fn clone_with_focus(&self, focus: Rc<Cell<TabFocus>>) -> Self {
+        Self {
+            focus,
+            ..self.clone()
+        }
+    }
+}
+
+impl SearchResultsModal {
+    pub fn new(focus: Rc<Cell<TabFocus>>, config: Config) -> Self {
+        Self {
+            focus,
+            config,
+            ..Default::default()
+        }
+    }
+
+    /// Shows the results of searching every page for the given text.
+    pub fn show_with(&mut self, text: String, results: Vec<SearchResult>) {
+        self.state = ListState::default().with_selected((!results.is_empty()).then_some(0));
+        self.text = text;
+        self.results = results;
+        self.focus();
+    }
+
+    fn selected_result(&self) -> Option<&SearchResult> {
+        self.results.get(self.state.selected()?)
+    }
+
+    fn result_to_item(&self, result: &SearchResult) -> ListItem<'static> {
+        let secondary = self.config.color_map.get(&ColorKey::FgSecondary);
+
+        ListItem::new(Line::from(vec![
+            Span::from(format!("page {:<4} ", result.page + 1)).fg(secondary),
+            Span::from(format!("{} ", bson_preview(&result.id))).fg(secondary),
+            Span::from(format!("{}: ", dotted_path(&result.path)))
+                .fg(self.config.color_map.get(&ColorKey::Key)),
+            Span::from(result.value.clone()),
+        ]))
+    }
+}
+
+impl Component for SearchResultsModal {
+    fn is_focused(&self) -> bool {
+        self.focus.get() == TabFocus::SearchResultsModal
+    }
+
+    fn focus(&self) {
+        self.focus.set(TabFocus::SearchResultsModal);
+    }
+
+    fn commands(&self) -> Vec<CommandGroup> {
+        vec![
+            CommandGroup::new(vec![Command::Confirm], "go to match")
+                .in_cat(CommandCategory::StatusBarOnly),
+            CommandGroup::new(vec![Command::Back], "close").in_cat(CommandCategory::StatusBarOnly),
+            CommandGroup::new(vec![Command::NavUp, Command::NavDown], "navigate"),
+        ]
+    }
+
+    fn handle_command(&mut self, command: &Command, queue: &mut SignalQueue) {
+        match command {
+            Command::Confirm => {
+                if let Some(result) = self.selected_result() {
+                    queue.push(Event::SearchResultOpened(result.clone()));
+                }
+            }
+            Command::Back => queue.push(Event::SearchResultsClosed),
+            Command::NavUp => self.state.select_previous(),
+            Command::NavDown => self.state.select_next(),
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let layout = Layout::vertical(vec![
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(2),
+        ])
+        .split(area);
+        let layout = Layout::horizontal(vec![
+            Constraint::Fill(1),
+            Constraint::Length(SEARCH_RESULTS_MODAL_WIDTH + 2),
+            Constraint::Fill(1),
+        ])
+        .split(layout[1]);
+        let modal_area = layout[1];
+
+        let num_results = self.results.len();
+        let match_word = if num_results == 1 { "match" } else { "matches" };
+        let block = Block::bordered()
+            .border_style(self.config.color_map.get(&ColorKey::PopupBorder))
+            .title(format!(" Search: \"{}\" ", self.text))
+            .title(Line::from(format!(" {num_results} {match_word} ")).right_aligned())
+            .bg(self.config.color_map.get(&ColorKey::PopupBg));
+        let inner_area = block.inner(modal_area);
+
+        frame.render_widget(Clear, modal_area);
+        frame.render_widget(block, modal_area);
+
+        if self.results.is_empty() {
+            let content = Paragraph::new("(no documents matched)")
+                .fg(self.config.color_map.get(&ColorKey::FgSecondary));
+            frame.render_widget(content, inner_area);
+            return;
+        }
+
+        let items: Vec<_> = self
+            .results
+            .iter()
+            .map(|result| self.result_to_item(result))
+            .collect();
+        let list = List::new(items)
+            .fg(self.config.color_map.get(&ColorKey::FgPrimary))
+            .highlight_style(
+                Style::default()
+                    .fg(self.config.color_map.get(&ColorKey::SelectionFg))
+                    .bg(self.config.color_map.get(&ColorKey::SelectionBg)),
+            );
+        frame.render_stateful_widget(list, inner_area, &mut self.state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::ComponentTestHarness, utils::mongo_tree::MongoKey};
+    use mongodb::bson::Bson;
+
+    fn result(page: usize) -> SearchResult {
+        SearchResult {
+            page,
+            id: Bson::Int32(1),
+            path: vec![MongoKey::String("name".to_string())],
+            value: "tongo".to_string(),
+        }
+    }
+
+    #[test]
+    fn open_selected_result() {
+        let mut test = ComponentTestHarness::new(SearchResultsModal::default());
+        test.component_mut()
+            .show_with("tongo".to_string(), vec![result(0), result(3)]);
+
+        test.given_command(Command::NavDown);
+        test.given_command(Command::Confirm);
+
+        test.expect_event(|e| matches!(e, Event::SearchResultOpened(result) if result.page == 3));
+    }
+}
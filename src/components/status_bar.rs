@@ -7,6 +7,10 @@ use std::time::{Duration, Instant};
 use crate::{
     components::Component,
     config::{color_map::ColorKey, Config},
+    model::{
+        concerns::Concerns,
+        server::{ServerStatus, Topology},
+    },
     system::{
         command::{CommandCategory, CommandManager},
         event::Event,
@@ -67,6 +71,12 @@ pub struct StatusBar {
     command_manager: CommandManager,
     message: Option<Message>,
 
+    /// The health of the current tab's connection.
+    server_status: ServerStatus,
+
+    /// The read and write concerns of the current tab.
+    concerns: Concerns,
+
     config: Config,
 
     // NOTE: used for debugging
@@ -104,6 +114,48 @@ impl StatusBar {
         Some(paragraph)
     }
 
+    pub fn set_server_status(&mut self, server_status: ServerStatus) {
+        self.server_status = server_status;
+    }
+
+    pub fn set_concerns(&mut self, concerns: Concerns) {
+        self.concerns = concerns;
+    }
+
+    /// The server's version, topology, primary, and latency (and the tab's
+    /// concerns, if any are set), or just the app's name and version if the
+    /// tab isn't connected.
+    fn server_widget(&self) -> Line<'_> {
+        let secondary = self.config.color_map.get(&ColorKey::FgSecondary);
+        if self.server_status.lost {
+            return Line::from(vec![
+                "● ".fg(self.config.color_map.get(&ColorKey::IndicatorError)),
+                "disconnected".fg(secondary),
+            ]);
+        }
+
+        let Some(info) = &self.server_status.info else {
+            return Line::from(format!("tongo v{}", env!("CARGO_PKG_VERSION")))
+                .fg(self.config.color_map.get(&ColorKey::AppName));
+        };
+
+        let mut details = vec![format!("mongo {}", info.version), info.topology.to_string()];
+        if let (Topology::ReplicaSet(_), Some(primary)) = (&info.topology, &info.primary) {
+            details.push(format!("primary {primary}"));
+        }
+        if let Some(latency) = self.server_status.latency {
+            details.push(format!("{} ms", latency.as_millis()));
+        }
+        if !self.concerns.is_empty() {
+            details.push(self.concerns.summary());
+        }
+
+        Line::from(vec![
+            "● ".fg(self.config.color_map.get(&ColorKey::IndicatorSuccess)),
+            details.join(" · ").fg(secondary),
+        ])
+    }
+
     #[expect(clippy::cast_possible_truncation)]
     pub fn height(&self, width: u16) -> u16 {
         self.message_widget()
@@ -124,8 +176,19 @@ impl Component for StatusBar {
         if let Some(message_content) = self.message_widget() {
             frame.render_widget(message_content, area.inner(Margin::new(1, 0)));
         } else {
-            let layout = Layout::horizontal([Constraint::Fill(1), Constraint::Length(16)])
+            let right_content = if DEBUG_RENDER_COUNT {
+                self.renders += 1;
+                Line::from(format!("{}", &self.renders))
+            } else {
+                self.server_widget()
+            }
+            .right_aligned();
+
+            #[expect(clippy::cast_possible_truncation)]
+            let right_width = right_content.width() as u16;
+            let layout = Layout::horizontal([Constraint::Fill(1), Constraint::Length(right_width)])
                 .horizontal_margin(1)
+                .spacing(2)
                 .split(area);
 
             let primary = self.config.color_map.get(&ColorKey::FgPrimary);
@@ -155,15 +218,6 @@ impl Component for StatusBar {
                     .collect::<Vec<Span>>(),
             );
 
-            let right_content = if DEBUG_RENDER_COUNT {
-                self.renders += 1;
-                Line::from(format!("{}", &self.renders)).right_aligned()
-            } else {
-                Line::from(format!("tongo v{}", env!("CARGO_PKG_VERSION")))
-                    .fg(self.config.color_map.get(&ColorKey::AppName))
-                    .right_aligned()
-            };
-
             frame.render_widget(commands, layout[0]);
             frame.render_widget(right_content, layout[1]);
         }
@@ -181,9 +235,23 @@ impl Component for StatusBar {
             Event::DocInsertComplete => {
                 self.message = Some(Message::success("Document created."));
             }
+            Event::EditReviewDiscarded => {
+                self.message = Some(Message::info("Changes discarded."));
+            }
             Event::DocDeleteComplete => {
                 self.message = Some(Message::success("Document deleted."));
             }
+            Event::DocMarkedForComparison => {
+                self.message = Some(Message::info(
+                    "Document marked. Mark another document to compare them.",
+                ));
+            }
+            Event::WriteReverted(kind) => {
+                self.message = Some(Message::success(&format!("Reverted the {kind}.")));
+            }
+            Event::WriteUndone(kind) => {
+                self.message = Some(Message::success(&format!("Undid the last {kind}.")));
+            }
             Event::CollectionCreationConfirmed => {
                 self.message = Some(Message::success("Collection created."));
             }
@@ -193,6 +261,20 @@ impl Component for StatusBar {
             Event::DataSentToClipboard => {
                 self.message = Some(Message::info("Copied to clipboard."));
             }
+            Event::ConnectionLost => {
+                self.message = Some(Message::error(
+                    "Lost the connection to the server. It will keep being pinged.",
+                ));
+            }
+            Event::ConnectionTested(latency) => {
+                self.message = Some(Message::success(&format!(
+                    "Reached the server in {} ms.",
+                    latency.as_millis()
+                )));
+            }
+            Event::ConnectionRestored => {
+                self.message = Some(Message::success("Connection to the server restored."));
+            }
             _ => (),
         }
 
@@ -1,17 +1,24 @@
 use crate::{
     client::{Client, PersistedClient},
     components::{
+        audit_log_modal::AuditLogModal,
         confirm_modal::ConfirmModal,
         connection_screen::{ConnScrFocus, ConnectionScreen, PersistedConnectionScreen},
+        diff_modal::DiffModal,
         list::connections::Connections,
         primary_screen::{PersistedPrimaryScreen, PrimScrFocus, PrimaryScreen},
+        search_results_modal::SearchResultsModal,
         Component,
     },
     config::Config,
-    model::connection::{Connection, ConnectionManager},
+    model::{
+        concerns::Concerns,
+        connection::{Connection, ConnectionManager},
+        server::ServerStatus,
+    },
     persistence::PersistedComponent,
     system::{
-        command::{Command, CommandGroup},
+        command::{Command, CommandCategory, CommandGroup},
         event::Event,
         message::{AppAction, Message, TabAction},
         signal::SignalQueue,
@@ -21,14 +28,17 @@ use ratatui::{layout::Rect, Frame};
 use serde::{Deserialize, Serialize};
 use std::{cell::Cell, rc::Rc};
 
-use super::input::input_modal::InputModal;
+use super::input::input_modal::{InputKind, InputModal};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TabFocus {
     ConnScr(ConnScrFocus),
     PrimScr(PrimScrFocus),
+    AuditLogModal,
     ConfModal,
+    DiffModal,
     InputModal,
+    SearchResultsModal,
     NotFocused,
 }
 
@@ -48,8 +58,11 @@ pub struct Tab<'a> {
     client: Client,
     conn_screen: ConnectionScreen,
     primary_screen: PrimaryScreen<'a>,
+    audit_log_modal: AuditLogModal,
     confirm_modal: ConfirmModal,
+    diff_modal: DiffModal,
     input_modal: InputModal,
+    search_results_modal: SearchResultsModal,
 
     // used when displaying the confirm modal or while the app is unfocused
     focus: Rc<Cell<TabFocus>>,
@@ -62,8 +75,11 @@ impl Default for Tab<'_> {
             client: Client::default(),
             conn_screen: ConnectionScreen::default(),
             primary_screen: PrimaryScreen::default(),
+            audit_log_modal: AuditLogModal::default(),
             confirm_modal: ConfirmModal::default(),
+            diff_modal: DiffModal::default(),
             input_modal: InputModal::default(),
+            search_results_modal: SearchResultsModal::default(),
             focus: Rc::new(Cell::new(TabFocus::default())),
             background_focus: None,
         }
@@ -78,8 +94,11 @@ impl Clone for Tab<'_> {
             client: self.client.clone(),
             conn_screen: self.conn_screen.clone_with_focus(focus.clone()),
             primary_screen: self.primary_screen.clone_with_focus(focus.clone()),
+            audit_log_modal: self.audit_log_modal.clone_with_focus(focus.clone()),
             confirm_modal: self.confirm_modal.clone_with_focus(focus.clone()),
+            diff_modal: self.diff_modal.clone_with_focus(focus.clone()),
             input_modal: self.input_modal.clone_with_focus(focus.clone()),
+            search_results_modal: self.search_results_modal.clone_with_focus(focus.clone()),
             focus,
             background_focus: self.background_focus,
         }
@@ -105,17 +124,20 @@ impl Tab<'_> {
         // initialize shared data
         let focus = Rc::new(Cell::new(initial_focus));
 
-        let confirm_modal = ConfirmModal::new(focus.clone(), config.clone());
+        let audit_log_modal = AuditLogModal::new(focus.clone(), config.clone());
+        let confirm_modal = ConfirmModal::new(focus.clone(), cursor_pos.clone(), config.clone());
+        let diff_modal = DiffModal::new(focus.clone(), config.clone());
         let input_modal = InputModal::new(focus.clone(), cursor_pos.clone(), config.clone());
+        let search_results_modal = SearchResultsModal::new(focus.clone(), config.clone());
 
         let primary_screen = PrimaryScreen::new(focus.clone(), cursor_pos.clone(), config.clone());
 
-        let client = Client::new(config.clone());
+        let mut client = Client::new(config.clone());
         let mut connection_list =
             Connections::new(focus.clone(), config.clone(), connection_manager.clone());
 
         if let Some(conn) = selected_connection {
-            client.connect(conn.connection_str.clone());
+            client.connect(&conn);
             connection_list.select_conn(&conn);
         }
 
@@ -132,8 +154,11 @@ impl Tab<'_> {
 
             conn_screen,
             primary_screen,
+            audit_log_modal,
             confirm_modal,
+            diff_modal,
             input_modal,
+            search_results_modal,
 
             focus,
 
@@ -141,6 +166,14 @@ impl Tab<'_> {
         }
     }
 
+    pub const fn server_status(&self) -> &ServerStatus {
+        self.client.server_status()
+    }
+
+    pub const fn concerns(&self) -> &Concerns {
+        self.client.concerns()
+    }
+
     pub fn exec_queued_ops(&mut self) {
         self.client.exec_queued_ops();
     }
@@ -154,20 +187,44 @@ impl Component for Tab<'_> {
 
         match self.focus.get() {
             TabFocus::ConnScr(_) => out.append(&mut self.conn_screen.commands()),
-            TabFocus::PrimScr(_) => out.append(&mut self.primary_screen.commands()),
+            TabFocus::PrimScr(_) => {
+                out.append(&mut self.primary_screen.commands());
+                out.push(
+                    CommandGroup::new(vec![Command::EditConcerns], "read/write concerns")
+                        .in_cat(CommandCategory::TabActions),
+                );
+            }
+            TabFocus::AuditLogModal => out.append(&mut self.audit_log_modal.commands()),
             TabFocus::ConfModal => out.append(&mut self.confirm_modal.commands()),
+            TabFocus::DiffModal => out.append(&mut self.diff_modal.commands()),
             TabFocus::InputModal => out.append(&mut self.input_modal.commands()),
+            TabFocus::SearchResultsModal => out.append(&mut self.search_results_modal.commands()),
             TabFocus::NotFocused => {}
         }
         out
     }
 
     fn handle_command(&mut self, command: &Command, queue: &mut SignalQueue) {
+        self.client.handle_command(command, queue);
+
+        if matches!(self.focus.get(), TabFocus::PrimScr(_)) && *command == Command::EditConcerns {
+            queue.push(Message::to_tab(TabAction::RequestInputWithValue(
+                InputKind::Concerns,
+                self.client.concerns().to_string(),
+            )));
+            return;
+        }
+
         match self.focus.get() {
             TabFocus::ConnScr(_) => self.conn_screen.handle_command(command, queue),
             TabFocus::PrimScr(_) => self.primary_screen.handle_command(command, queue),
+            TabFocus::AuditLogModal => self.audit_log_modal.handle_command(command, queue),
             TabFocus::ConfModal => self.confirm_modal.handle_command(command, queue),
+            TabFocus::DiffModal => self.diff_modal.handle_command(command, queue),
             TabFocus::InputModal => self.input_modal.handle_command(command, queue),
+            TabFocus::SearchResultsModal => {
+                self.search_results_modal.handle_command(command, queue);
+            }
             TabFocus::NotFocused => {}
         }
     }
@@ -176,8 +233,13 @@ impl Component for Tab<'_> {
         match self.focus.get() {
             TabFocus::ConnScr(_) => self.conn_screen.handle_raw_event(event, queue),
             TabFocus::PrimScr(_) => self.primary_screen.handle_raw_event(event, queue),
+            TabFocus::AuditLogModal => self.audit_log_modal.handle_raw_event(event, queue),
             TabFocus::ConfModal => self.confirm_modal.handle_raw_event(event, queue),
+            TabFocus::DiffModal => self.diff_modal.handle_raw_event(event, queue),
             TabFocus::InputModal => self.input_modal.handle_raw_event(event, queue),
+            TabFocus::SearchResultsModal => {
+                self.search_results_modal.handle_raw_event(event, queue);
+            }
             TabFocus::NotFocused => {}
         }
     }
@@ -187,18 +249,34 @@ impl Component for Tab<'_> {
             Event::ConnectionCreated(..) | Event::ConnectionSelected(..) => {
                 self.primary_screen.focus();
             }
-            Event::ConfirmYes(..)
+            Event::AuditLogClosed
+            | Event::ConfirmYes(..)
             | Event::ConfirmNo
+            | Event::EditReviewApplied
+            | Event::EditReviewDiscarded
             | Event::InputConfirmed(..)
-            | Event::InputCanceled => {
+            | Event::InputCanceled
+            | Event::SearchResultOpened(..)
+            | Event::SearchResultsClosed => {
                 self.focus
                     .set(self.background_focus.take().unwrap_or_default());
             }
+            Event::SecretRequested(kind) => {
+                self.background_focus = Some(self.focus.get());
+                self.input_modal.show_with(*kind);
+                queue.push(Message::to_app(AppAction::EnterRawMode));
+            }
+            Event::DocSearchAllComplete { text, results } => {
+                self.background_focus = Some(self.focus.get());
+                self.search_results_modal
+                    .show_with(text.clone(), results.clone());
+            }
             _ => {}
         }
         self.client.handle_event(event, queue);
         self.conn_screen.handle_event(event, queue);
         self.primary_screen.handle_event(event, queue);
+        self.audit_log_modal.handle_event(event, queue);
     }
 
     fn handle_message(&mut self, message: &Message, queue: &mut SignalQueue) {
@@ -210,15 +288,42 @@ impl Component for Tab<'_> {
             self.primary_screen.handle_message(message, queue);
         } else {
             match message.read_as_tab() {
+                Some(TabAction::RequestAuditLog) => match self.client.audit_log().read_entries() {
+                    Ok((entries, skipped)) => {
+                        self.background_focus = Some(self.focus.get());
+                        self.audit_log_modal.show_with(entries);
+                        if skipped > 0 {
+                            queue.push(Event::ErrorOccurred(
+                                format!("Skipped {skipped} unreadable lines in the audit log.")
+                                    .into(),
+                            ));
+                        }
+                    }
+                    Err(err) => queue.push(Event::ErrorOccurred(err.to_string().into())),
+                },
                 Some(TabAction::RequestConfirmation(kind)) => {
                     self.background_focus = Some(self.focus.get());
-                    self.confirm_modal.show_with(*kind);
+                    let protected_name = self.client.protected_name(*kind);
+                    if protected_name.is_some() {
+                        queue.push(Message::to_app(AppAction::EnterRawMode));
+                    }
+                    self.confirm_modal.show_with(*kind, protected_name);
+                }
+                Some(TabAction::RequestEditReview { original, edited }) => {
+                    self.background_focus = Some(self.focus.get());
+                    self.diff_modal.show_with(original.clone(), edited.clone());
                 }
                 Some(TabAction::RequestInput(kind)) => {
                     self.background_focus = Some(self.focus.get());
                     self.input_modal.show_with(*kind);
                     queue.push(Message::to_app(AppAction::EnterRawMode));
                 }
+                Some(TabAction::RequestInputWithValue(kind, value)) => {
+                    self.background_focus = Some(self.focus.get());
+                    self.input_modal.show_with(*kind);
+                    self.input_modal.set_value(value);
+                    queue.push(Message::to_app(AppAction::EnterRawMode));
+                }
                 _ => {}
             }
         }
@@ -229,6 +334,14 @@ impl Component for Tab<'_> {
         match self.focus.get() {
             TabFocus::PrimScr(..) => self.primary_screen.render(frame, area),
             TabFocus::ConnScr(..) => self.conn_screen.render(frame, area),
+            TabFocus::AuditLogModal => {
+                match self.background_focus {
+                    Some(TabFocus::PrimScr(..)) => self.primary_screen.render(frame, area),
+                    Some(TabFocus::ConnScr(..)) => self.conn_screen.render(frame, area),
+                    _ => {}
+                }
+                self.audit_log_modal.render(frame, area);
+            }
             TabFocus::ConfModal => {
                 match self.background_focus {
                     Some(TabFocus::PrimScr(..)) => self.primary_screen.render(frame, area),
@@ -237,6 +350,14 @@ impl Component for Tab<'_> {
                 }
                 self.confirm_modal.render(frame, area);
             }
+            TabFocus::DiffModal => {
+                match self.background_focus {
+                    Some(TabFocus::PrimScr(..)) => self.primary_screen.render(frame, area),
+                    Some(TabFocus::ConnScr(..)) => self.conn_screen.render(frame, area),
+                    _ => {}
+                }
+                self.diff_modal.render(frame, area);
+            }
             TabFocus::InputModal => {
                 match self.background_focus {
                     Some(TabFocus::PrimScr(..)) => self.primary_screen.render(frame, area),
@@ -245,6 +366,14 @@ impl Component for Tab<'_> {
                 }
                 self.input_modal.render(frame, area);
             }
+            TabFocus::SearchResultsModal => {
+                match self.background_focus {
+                    Some(TabFocus::PrimScr(..)) => self.primary_screen.render(frame, area),
+                    Some(TabFocus::ConnScr(..)) => self.conn_screen.render(frame, area),
+                    _ => {}
+                }
+                self.search_results_modal.render(frame, area);
+            }
             TabFocus::NotFocused => {}
         }
     }
@@ -272,9 +401,12 @@ impl PersistedComponent for Tab<'_> {
                 };
                 TabFocus::PrimScr(ps_focus)
             }
-            TabFocus::ConfModal | TabFocus::InputModal | TabFocus::NotFocused => {
-                self.background_focus.unwrap_or_default()
-            }
+            TabFocus::AuditLogModal
+            | TabFocus::ConfModal
+            | TabFocus::DiffModal
+            | TabFocus::InputModal
+            | TabFocus::SearchResultsModal
+            | TabFocus::NotFocused => self.background_focus.unwrap_or_default(),
         };
 
         PersistedTab {
@@ -292,7 +424,7 @@ impl PersistedComponent for Tab<'_> {
 
         self.client.hydrate(storage.client);
         if let Some(conn) = storage.conn_screen.conn_list.selected_conn {
-            self.client.connect(conn.connection_str);
+            self.client.connect(&conn);
         }
     }
 }
@@ -1,7 +1,7 @@
 use super::Component;
 use crate::{
     config::{color_map::ColorKey, Config},
-    model::connection::Connection,
+    model::connection::{Connection, Environment},
     persistence::PersistedComponent,
     system::{
         command::{Command, CommandCategory, CommandGroup},
@@ -25,6 +25,8 @@ struct TabSpec {
     connection: Option<String>,
     db: Option<String>,
     coll: Option<String>,
+    #[serde(default)]
+    environment: Option<Environment>,
 }
 
 impl TabSpec {
@@ -71,6 +73,7 @@ impl Default for TabBar {
 impl TabBar {
     pub fn new(selected_connection: Option<Connection>, config: Config) -> Self {
         let base_tab = TabSpec {
+            environment: selected_connection.as_ref().and_then(|c| c.environment),
             connection: selected_connection.map(|c| c.name),
             ..Default::default()
         };
@@ -92,6 +95,13 @@ impl TabBar {
         self.tabs.len()
     }
 
+    /// The environment of the current tab's connection, if it has one.
+    pub fn current_environment(&self) -> Option<Environment> {
+        self.tabs
+            .get(self.current_tab_idx)
+            .and_then(|tab_spec| tab_spec.environment)
+    }
+
     fn tab_names(&self) -> impl Iterator<Item = String> + Clone + use<'_> {
         self.tabs
             .iter()
@@ -232,6 +242,16 @@ impl Component for TabBar {
     }
 
     fn handle_event(&mut self, event: &Event, _queue: &mut SignalQueue) {
+        // an edited connection may be open in any tab, not just the current one
+        if let Event::ConnectionUpdated(conn) = event {
+            for tab in &mut self.tabs {
+                if tab.connection.as_ref() == Some(&conn.name) {
+                    tab.environment = conn.environment;
+                }
+            }
+            return;
+        }
+
         let Some(current_tab) = self.tabs.get_mut(self.current_tab_idx) else {
             return;
         };
@@ -239,6 +259,7 @@ impl Component for TabBar {
         match event {
             Event::ConnectionSelected(conn) | Event::ConnectionCreated(conn) => {
                 current_tab.connection = Some(conn.name.clone());
+                current_tab.environment = conn.environment;
                 current_tab.db = None;
                 current_tab.coll = None;
                 self.scroll_to_current_tab();
@@ -278,10 +299,30 @@ impl Component for TabBar {
         let mut scroll_view = ScrollView::new(Size::new(content_width as u16, 1))
             .scrollbars_visibility(ScrollbarVisibility::Never);
 
-        let tabs = Tabs::new(tab_names)
+        // tabs connected to an environment are shown in its color, so the
+        // selected one is made bold to stand out
+        let titles = tab_names
+            .zip(&self.tabs)
+            .enumerate()
+            .map(|(idx, (name, tab_spec))| {
+                let selected = idx == self.current_tab_idx;
+                match tab_spec.environment {
+                    Some(environment) => {
+                        let style =
+                            Style::default().fg(self.config.color_map.get(&environment.into()));
+                        Line::from(name).style(if selected { style.bold() } else { style })
+                    }
+                    None if selected => {
+                        Line::from(name).fg(self.config.color_map.get(&ColorKey::TabActive))
+                    }
+                    None => Line::from(name),
+                }
+            });
+
+        let tabs = Tabs::new(titles)
             .bg(self.config.color_map.get(&ColorKey::PanelInactiveBg))
             .fg(self.config.color_map.get(&ColorKey::TabInactive))
-            .highlight_style(self.config.color_map.get(&ColorKey::TabActive))
+            .highlight_style(Style::default())
             // remove padding (but add spaces to divider) to get better control over margins and scrolling
             .padding("", "")
             .divider(format!(" {} ", symbols::border::PLAIN.vertical_left))
@@ -498,6 +539,50 @@ mod tests {
         assert_eq!(test.component().tabs[0].name(), String::from("New Tab"));
     }
 
+    #[test]
+    fn track_environment_of_current_tab() {
+        let mut test = ComponentTestHarness::new(TabBar::default());
+
+        let mut conn = Connection::new(String::from("Prod"), String::from("url"));
+        conn.environment = Some(Environment::Prod);
+        test.given_event(Event::ConnectionSelected(conn));
+        assert_eq!(
+            test.component().current_environment(),
+            Some(Environment::Prod)
+        );
+
+        // a new tab isn't connected to anything yet
+        test.given_command(Command::NewTab);
+        assert_eq!(test.component().current_environment(), None);
+
+        test.given_command(Command::GotoTab(1));
+        assert_eq!(
+            test.component().current_environment(),
+            Some(Environment::Prod)
+        );
+    }
+
+    #[test]
+    fn refresh_environment_when_connection_updated() {
+        let mut test = ComponentTestHarness::new(TabBar::default());
+
+        let mut conn = Connection::new(String::from("Shared"), String::from("url"));
+        conn.environment = Some(Environment::Staging);
+        test.given_event(Event::ConnectionSelected(conn.clone()));
+
+        // update the connection while another tab is current
+        test.given_command(Command::NewTab);
+        conn.environment = Some(Environment::Prod);
+        test.given_event(Event::ConnectionUpdated(conn));
+        assert_eq!(test.component().current_environment(), None);
+
+        test.given_command(Command::GotoTab(1));
+        assert_eq!(
+            test.component().current_environment(),
+            Some(Environment::Prod)
+        );
+    }
+
     #[test]
     fn duplicate_tab() {
         let mut test = ComponentTestHarness::new(TabBar::default());
@@ -1,6 +1,8 @@
 use anyhow::{Context, Result};
 use color_map::{ColorMap, RawColorMap};
 use key_map::KeyMap;
+use protection::{Protection, RawProtection};
+use reference_map::ReferenceMap;
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, rc::Rc};
 
@@ -8,6 +10,8 @@ use crate::utils::storage::{CONFIG_FILE_NAME, THEME_FILE_NAME};
 
 pub mod color_map;
 pub mod key_map;
+pub mod protection;
+pub mod reference_map;
 
 type RawKeyMap = HashMap<String, String>;
 
@@ -18,11 +22,21 @@ pub struct RawConfig {
     #[serde(deserialize_with = "validate_page_size")]
     pub page_size: usize,
 
+    #[serde(rename = "read-only")]
+    #[serde(default)]
+    pub read_only: bool,
+
     #[serde(default)]
     pub keys: RawKeyMap,
 
     #[serde(default)]
     pub theme: Option<RawColorMap>,
+
+    #[serde(default)]
+    pub references: HashMap<String, String>,
+
+    #[serde(default)]
+    pub protection: RawProtection,
 }
 
 const fn default_page_size() -> usize {
@@ -68,8 +82,14 @@ impl TryFrom<(Option<String>, Option<String>)> for RawConfig {
 #[derive(Debug, Default, Clone)]
 pub struct Config {
     pub page_size: usize,
+
+    /// Blocks writes through every connection, not just read-only ones.
+    pub read_only: bool,
+
     pub key_map: Rc<KeyMap>,
     pub color_map: Rc<ColorMap>,
+    pub reference_map: Rc<ReferenceMap>,
+    pub protection: Rc<Protection>,
 }
 
 impl TryFrom<RawConfig> for Config {
@@ -83,11 +103,26 @@ impl TryFrom<RawConfig> for Config {
         } else {
             Rc::new(ColorMap::default())
         };
+        let reference_map = Rc::new(
+            config
+                .references
+                .try_into()
+                .context("Could not load references")?,
+        );
+        let protection = Rc::new(
+            config
+                .protection
+                .try_into()
+                .context("Could not load protection settings")?,
+        );
 
         Ok(Self {
             page_size,
+            read_only: config.read_only,
             key_map,
             color_map,
+            reference_map,
+            protection,
         })
     }
 }
@@ -1,3 +1,4 @@
+use crate::model::connection::Environment;
 use anyhow::{anyhow, bail, Result};
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
@@ -24,6 +25,12 @@ pub struct RawColorMap {
     #[serde(default)]
     popup: HashMap<String, String>,
 
+    #[serde(default)]
+    diff: HashMap<String, String>,
+
+    #[serde(default)]
+    env: HashMap<String, String>,
+
     #[serde(default)]
     palette: HashMap<String, String>,
 }
@@ -68,6 +75,26 @@ pub enum ColorKey {
     // documents
     DocumentsNote,
     DocumentsSearch,
+
+    // diff
+    DiffAdded,
+    DiffRemoved,
+    DiffChanged,
+
+    // environments
+    EnvDev,
+    EnvStaging,
+    EnvProd,
+}
+
+impl From<Environment> for ColorKey {
+    fn from(environment: Environment) -> Self {
+        match environment {
+            Environment::Dev => Self::EnvDev,
+            Environment::Staging => Self::EnvStaging,
+            Environment::Prod => Self::EnvProd,
+        }
+    }
 }
 
 /// A helper struct for parsing the color map.
@@ -136,6 +163,24 @@ impl ColorKeyMapping {
             ("search", ColorKey::DocumentsSearch),
         ],
     };
+
+    const DIFF: Self = Self {
+        section: "diff",
+        mappings: &[
+            ("added", ColorKey::DiffAdded),
+            ("removed", ColorKey::DiffRemoved),
+            ("changed", ColorKey::DiffChanged),
+        ],
+    };
+
+    const ENV: Self = Self {
+        section: "env",
+        mappings: &[
+            ("dev", ColorKey::EnvDev),
+            ("staging", ColorKey::EnvStaging),
+            ("prod", ColorKey::EnvProd),
+        ],
+    };
 }
 
 #[derive(Debug)]
@@ -188,6 +233,16 @@ impl Default for ColorMap {
                 // documents
                 ColorKey::DocumentsNote => Color::Gray,
                 ColorKey::DocumentsSearch => Color::Cyan,
+
+                // diff
+                ColorKey::DiffAdded => Color::Green,
+                ColorKey::DiffRemoved => Color::Red,
+                ColorKey::DiffChanged => Color::Yellow,
+
+                // environments
+                ColorKey::EnvDev => Color::Green,
+                ColorKey::EnvStaging => Color::Yellow,
+                ColorKey::EnvProd => Color::Red,
             };
 
             map.insert(key, color);
@@ -224,6 +279,8 @@ impl TryFrom<RawColorMap> for ColorMap {
         color_map.process_section(&ColorKeyMapping::POPUP, &map.popup, &palette)?;
         color_map.process_section(&ColorKeyMapping::DATA, &map.data, &palette)?;
         color_map.process_section(&ColorKeyMapping::DOCUMENTS, &map.documents, &palette)?;
+        color_map.process_section(&ColorKeyMapping::DIFF, &map.diff, &palette)?;
+        color_map.process_section(&ColorKeyMapping::ENV, &map.env, &palette)?;
 
         Ok(color_map)
     }
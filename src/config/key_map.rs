@@ -119,6 +119,7 @@ fn string_to_command(value: &str) -> Result<Command> {
     // TODO: better names?
     match value {
         "show-commands" => Ok(Command::ShowHelpModal),
+        "show-audit-log" => Ok(Command::ShowAuditLog),
 
         "nav-up" => Ok(Command::NavUp),
         "nav-down" => Ok(Command::NavDown),
@@ -143,12 +144,35 @@ fn string_to_command(value: &str) -> Result<Command> {
         "last-page" => Ok(Command::LastPage),
 
         "delete" => Ok(Command::Delete),
+        "encrypt" => Ok(Command::Encrypt),
+        "make-read-only" => Ok(Command::MakeReadOnly),
+        "test-connection" => Ok(Command::TestConnection),
+        "reconnect" => Ok(Command::Reconnect),
+        "edit-concerns" => Ok(Command::EditConcerns),
         "search" => Ok(Command::Search),
+        "search-mode" => Ok(Command::CycleSearchMode),
+        "search-scope" => Ok(Command::CycleSearchScope),
         "back" => Ok(Command::Back),
         "quit" => Ok(Command::Quit),
 
         "duplicate-doc" => Ok(Command::DuplicateDoc),
         "yank" => Ok(Command::Yank),
+        "undo" => Ok(Command::Undo),
+        "compare" => Ok(Command::Compare),
+        "add-field" => Ok(Command::AddField),
+
+        "filter-by-value" => Ok(Command::FilterByValue),
+        "filter-out-value" => Ok(Command::FilterOutValue),
+        "sort-by-field" => Ok(Command::SortByField),
+        "include-field" => Ok(Command::IncludeField),
+        "exclude-field" => Ok(Command::ExcludeField),
+        "follow-reference" => Ok(Command::FollowReference),
+
+        "toggle-view" => Ok(Command::ToggleView),
+        "sort-column" => Ok(Command::SortColumn),
+        "hide-column" => Ok(Command::HideColumn),
+        "shrink-column" => Ok(Command::ShrinkColumn),
+        "grow-column" => Ok(Command::GrowColumn),
 
         "new-tab" => Ok(Command::NewTab),
         "next-tab" => Ok(Command::NextTab),
@@ -199,6 +223,7 @@ impl Default for KeyMap {
         for command in Command::iter() {
             let key_code = match command {
                 Command::ShowHelpModal => KeyCode::Char('?'),
+                Command::ShowAuditLog => KeyCode::Char('l'),
                 Command::NavUp => KeyCode::Up,
                 Command::NavDown => KeyCode::Down,
                 Command::NavLeft => KeyCode::Left,
@@ -218,11 +243,31 @@ impl Default for KeyMap {
                 Command::FirstPage => KeyCode::Char('P'),
                 Command::LastPage => KeyCode::Char('N'),
                 Command::Delete => KeyCode::Char('D'),
+                Command::Encrypt => KeyCode::Char('Z'),
+                Command::MakeReadOnly => KeyCode::Char('O'),
+                Command::Reconnect => KeyCode::Char('B'),
+                Command::EditConcerns => KeyCode::Char('V'),
                 Command::Search => KeyCode::Char('/'),
+                Command::CycleSearchMode => KeyCode::Char('M'),
+                Command::CycleSearchScope => KeyCode::Char('W'),
                 Command::Back => KeyCode::Esc,
                 Command::Quit => KeyCode::Char('q'),
                 Command::DuplicateDoc => KeyCode::Char('C'),
                 Command::Yank => KeyCode::Char('y'),
+                Command::Undo => KeyCode::Char('u'),
+                Command::Compare => KeyCode::Char('m'),
+                Command::AddField => KeyCode::Char('a'),
+                Command::FilterByValue => KeyCode::Char('f'),
+                Command::FilterOutValue => KeyCode::Char('F'),
+                Command::SortByField => KeyCode::Char('o'),
+                Command::IncludeField => KeyCode::Char('i'),
+                Command::ExcludeField => KeyCode::Char('e'),
+                Command::FollowReference => KeyCode::Char('g'),
+                Command::ToggleView => KeyCode::Char('v'),
+                Command::SortColumn => KeyCode::Char('s'),
+                Command::HideColumn => KeyCode::Char('x'),
+                Command::ShrinkColumn => KeyCode::Char('<'),
+                Command::GrowColumn => KeyCode::Char('>'),
                 Command::NewTab => KeyCode::Char('T'),
                 Command::NextTab => KeyCode::Char(']'),
                 Command::PreviousTab => KeyCode::Char('['),
@@ -237,11 +282,20 @@ impl Default for KeyMap {
                 Command::GotoTab(7) => KeyCode::Char('7'),
                 Command::GotoTab(8) => KeyCode::Char('8'),
                 Command::GotoTab(9) => KeyCode::Char('9'),
-                Command::GotoTab(_) => continue,
+                // these are set up below
+                Command::TestConnection | Command::GotoTab(_) => continue,
             };
 
             map.insert(command, key_code.into());
         }
+        // it needs a modifier, since it's used while typing
+        map.insert(
+            Command::TestConnection,
+            Key {
+                code: KeyCode::Char('t'),
+                modifiers: KeyModifiers::CONTROL,
+            },
+        );
         // need to manually set up the `GotoTab` commands
         for num in 1..=9 {
             map.insert(
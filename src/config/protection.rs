@@ -0,0 +1,146 @@
+use crate::model::connection::{Connection, Environment};
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+const SYSTEM_DATABASES: [&str; 3] = ["admin", "local", "config"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct RawProtection {
+    pub system_databases: bool,
+    pub environments: Vec<String>,
+    pub patterns: Vec<String>,
+}
+
+impl Default for RawProtection {
+    fn default() -> Self {
+        Self {
+            system_databases: true,
+            environments: vec![Environment::Prod.to_string()],
+            patterns: vec![],
+        }
+    }
+}
+
+/// Databases and collections that can only be dropped after typing in their
+/// name. Patterns are globs (eg. `prod_*` or `*.payments`) matched against
+/// database names and `database.collection` names.
+#[derive(Debug)]
+pub struct Protection {
+    system_databases: bool,
+    environments: Vec<Environment>,
+    patterns: Vec<Regex>,
+}
+
+impl Default for Protection {
+    fn default() -> Self {
+        RawProtection::default()
+            .try_into()
+            .expect("default protection should be valid")
+    }
+}
+
+impl TryFrom<RawProtection> for Protection {
+    type Error = anyhow::Error;
+
+    fn try_from(raw: RawProtection) -> Result<Self> {
+        let environments = raw
+            .environments
+            .iter()
+            .map(|env| env.parse())
+            .collect::<Result<_>>()?;
+        let patterns = raw
+            .patterns
+            .iter()
+            .map(|pattern| {
+                let regex = regex::escape(pattern)
+                    .replace(r"\*", ".*")
+                    .replace(r"\?", ".");
+                Regex::new(&format!("^{regex}$"))
+                    .context(format!("Invalid protection pattern \"{pattern}\""))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            system_databases: raw.system_databases,
+            environments,
+            patterns,
+        })
+    }
+}
+
+impl Protection {
+    /// Whether everything on a connection is protected, because of its
+    /// environment or one of its tags.
+    #[must_use]
+    pub fn protects_conn(&self, conn: &Connection) -> bool {
+        conn.environment
+            .iter()
+            .copied()
+            .chain(conn.tags.iter().filter_map(|tag| tag.parse().ok()))
+            .any(|env| self.environments.contains(&env))
+    }
+
+    #[must_use]
+    pub fn protects_db(&self, db: &str) -> bool {
+        (self.system_databases && SYSTEM_DATABASES.contains(&db)) || self.matches(db)
+    }
+
+    /// Whether a collection is protected, either by itself or because its
+    /// database is.
+    #[must_use]
+    pub fn protects_coll(&self, db: &str, coll: &str) -> bool {
+        self.protects_db(db) || self.matches(&format!("{db}.{coll}"))
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        self.patterns.iter().any(|pattern| pattern.is_match(name))
+    }
+}
+
+#[expect(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn protect_matching_names() {
+        let protection = Protection::try_from(RawProtection {
+            patterns: vec!["prod_*".to_string(), "*.payments".to_string()],
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert!(protection.protects_db("admin"));
+        assert!(protection.protects_db("prod_orders"));
+        assert!(!protection.protects_db("orders"));
+        assert!(protection.protects_coll("local", "startup_log"));
+        assert!(protection.protects_coll("shop", "payments"));
+        assert!(!protection.protects_coll("shop", "payments_old"));
+    }
+
+    #[test]
+    fn protect_prod_connections() {
+        let protection = Protection::default();
+
+        let mut conn = Connection::new("conn".into(), "mongodb://localhost".into());
+        assert!(!protection.protects_conn(&conn));
+
+        conn.tags = vec!["production".to_string()];
+        assert!(protection.protects_conn(&conn));
+
+        conn.tags = vec![];
+        conn.environment = Some(Environment::Prod);
+        assert!(protection.protects_conn(&conn));
+    }
+
+    #[test]
+    fn reject_unknown_environments() {
+        assert!(Protection::try_from(RawProtection {
+            environments: vec!["qa".to_string()],
+            ..Default::default()
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,98 @@
+use anyhow::{bail, Result};
+use std::collections::HashMap;
+
+/// A field in a collection, eg. `customers._id`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FieldRef {
+    pub coll: String,
+    pub field: String,
+}
+
+impl TryFrom<&str> for FieldRef {
+    type Error = anyhow::Error;
+
+    fn try_from(s: &str) -> Result<Self> {
+        let (coll, field) = s.split_once('.').unwrap_or((s, "_id"));
+        if coll.is_empty() || field.is_empty() {
+            bail!("Reference \"{s}\" should look like \"collection.field\"");
+        }
+
+        Ok(Self {
+            coll: coll.to_string(),
+            field: field.to_string(),
+        })
+    }
+}
+
+/// Fields that refer to documents in other collections (in the same
+/// database), like a foreign key. Configured as eg.
+/// `"orders.customerId" = "customers._id"`.
+#[derive(Debug, Default)]
+pub struct ReferenceMap {
+    map: HashMap<FieldRef, FieldRef>,
+}
+
+impl TryFrom<HashMap<String, String>> for ReferenceMap {
+    type Error = anyhow::Error;
+
+    fn try_from(raw: HashMap<String, String>) -> Result<Self> {
+        let map = raw
+            .iter()
+            .map(|(from, to)| Ok((from.as_str().try_into()?, to.as_str().try_into()?)))
+            .collect::<Result<_>>()?;
+
+        Ok(Self { map })
+    }
+}
+
+impl ReferenceMap {
+    /// Gets the field that a field in a collection refers to, if any. The path
+    /// should not include array indices, eg. `items.productId`.
+    #[must_use]
+    pub fn target(&self, coll: &str, field: &str) -> Option<&FieldRef> {
+        self.map.get(&FieldRef {
+            coll: coll.to_string(),
+            field: field.to_string(),
+        })
+    }
+}
+
+#[expect(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_reference_map() {
+        let map = ReferenceMap::try_from(HashMap::from([
+            ("orders.customerId".to_string(), "customers._id".to_string()),
+            ("orders.items.productId".to_string(), "products".to_string()),
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            map.target("orders", "customerId"),
+            Some(&FieldRef {
+                coll: "customers".into(),
+                field: "_id".into()
+            })
+        );
+        assert_eq!(
+            map.target("orders", "items.productId"),
+            Some(&FieldRef {
+                coll: "products".into(),
+                field: "_id".into()
+            })
+        );
+        assert_eq!(map.target("customers", "customerId"), None);
+    }
+
+    #[test]
+    fn reject_invalid_references() {
+        assert!(ReferenceMap::try_from(HashMap::from([(
+            "orders.".to_string(),
+            "customers".to_string()
+        )]))
+        .is_err());
+    }
+}
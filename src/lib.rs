@@ -1,4 +1,5 @@
 pub mod app;
+pub mod cli;
 mod client;
 mod components;
 pub mod config;
@@ -5,8 +5,9 @@ use std::{io::Stdout, path::PathBuf, rc::Rc};
 
 use tongo::{
     app::App,
+    cli::{run_query, QueryArgs},
     config::Config,
-    model::connection::Connection,
+    model::{connection::Connection, launch::LaunchSpec},
     persistence::PersistedComponent,
     utils::storage::{get_app_data_path, FileStorage, Storage},
 };
@@ -15,8 +16,24 @@ use tongo::{
 #[derive(Parser)]
 #[command(author)]
 pub struct Args {
+    #[command(subcommand)]
+    command: Option<CliCommand>,
+
     #[clap(flatten)]
     load_options: Option<LoadOptions>,
+
+    #[clap(flatten)]
+    target_options: TargetOptions,
+
+    /// Block every write to the database, whichever connection is used
+    #[arg(long)]
+    read_only: bool,
+}
+
+#[derive(clap::Subcommand)]
+pub enum CliCommand {
+    /// Run a query and print the results, without starting the TUI
+    Query(QueryArgs),
 }
 
 /// Detemines how (if at all) the app will automatically load a connection
@@ -37,6 +54,44 @@ pub struct LoadOptions {
     url: Option<String>,
 }
 
+/// Where to open the app once it's connected. These can also be set in a
+/// connection string, eg.
+/// `mongodb://localhost/shop?tongoColl=orders&tongoFilter={status:"open"}`
+#[derive(Debug, clap::Args, Clone)]
+pub struct TargetOptions {
+    /// Open a database
+    #[arg(long, conflicts_with = "last")]
+    db: Option<String>,
+
+    /// Open a collection in the database
+    #[arg(long, conflicts_with = "last")]
+    collection: Option<String>,
+
+    /// Query the collection with a filter, eg. `{ status: "open" }`
+    #[arg(long, conflicts_with = "last")]
+    filter: Option<String>,
+
+    /// Sort the collection's documents, eg. `{ createdAt: -1 }`
+    #[arg(long, conflicts_with = "last")]
+    sort: Option<String>,
+
+    /// Only show some fields of the collection's documents, eg. `{ name: 1 }`
+    #[arg(long, conflicts_with = "last")]
+    projection: Option<String>,
+}
+
+impl From<TargetOptions> for LaunchSpec {
+    fn from(options: TargetOptions) -> Self {
+        Self {
+            db: options.db,
+            coll: options.collection,
+            filter: options.filter,
+            projection: options.projection,
+            sort: options.sort,
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     init_tracing()?;
@@ -52,17 +107,28 @@ async fn main() -> Result<()> {
     let storage = FileStorage::init()?;
 
     // load config
-    let config: Config = storage.read_config()?.try_into()?;
+    let mut config: Config = storage.read_config()?.try_into()?;
+    config.read_only |= args.read_only;
 
     // load connections
     let stored_connections = storage.read_connections().unwrap_or_default();
 
-    // connect to a connection based on command line argument (if applicable)
+    if let Some(CliCommand::Query(query_args)) = args.command {
+        return run_query(query_args, &stored_connections).await;
+    }
+
+    // connect to a connection based on command line argument (if applicable),
+    // taking the place to open out of a connection string
+    let mut uri_spec = LaunchSpec::default();
     let connection =
         args.load_options
             .clone()
             .and_then(|group| match (group.url, group.connection) {
-                (Some(url), _) => Some(Connection::new("Unnamed Connection".to_string(), url)),
+                (Some(url), _) => {
+                    let (url, spec) = LaunchSpec::split_conn_str(&url);
+                    uri_spec = spec;
+                    Some(Connection::new("Unnamed Connection".to_string(), url))
+                }
                 (_, Some(conn_name)) => stored_connections
                     .iter()
                     .find(|c| c.name.to_lowercase() == conn_name.to_lowercase())
@@ -70,6 +136,13 @@ async fn main() -> Result<()> {
                 _ => None,
             });
 
+    let target = LaunchSpec::from(args.target_options)
+        .or(uri_spec)
+        .into_target()?;
+    if target.is_some() && connection.is_none() {
+        anyhow::bail!("A connection (--connection or --url) is needed to open a database");
+    }
+
     let mut terminal = setup_terminal()?;
     let mut app = App::new(
         connection,
@@ -77,6 +150,9 @@ async fn main() -> Result<()> {
         config,
         Rc::new(storage.clone()),
     );
+    if let Some(target) = target {
+        app.open_target(target);
+    }
 
     // load stored app state
 
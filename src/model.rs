@@ -1,3 +1,9 @@
 pub mod collection;
+pub mod concerns;
+pub mod conn_str;
 pub mod connection;
 pub mod database;
+pub mod launch;
+pub mod query_options;
+pub mod reference;
+pub mod server;
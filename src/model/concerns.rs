@@ -0,0 +1,321 @@
+use anyhow::{bail, Context, Result};
+use mongodb::options::{
+    Acknowledgment, DatabaseOptions, ReadConcern, ReadPreference, ReadPreferenceOptions,
+    SelectionCriteria, WriteConcern,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt::Display, str::FromStr, time::Duration};
+
+const READ_CONCERN_LEVELS: [&str; 5] =
+    ["local", "available", "majority", "linearizable", "snapshot"];
+
+/// Which members of a replica set reads are sent to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReadMode {
+    Primary,
+    PrimaryPreferred,
+    Secondary,
+    SecondaryPreferred,
+    Nearest,
+}
+
+impl FromStr for ReadMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "primary" => Ok(Self::Primary),
+            "primarypreferred" => Ok(Self::PrimaryPreferred),
+            "secondary" => Ok(Self::Secondary),
+            "secondarypreferred" => Ok(Self::SecondaryPreferred),
+            "nearest" => Ok(Self::Nearest),
+            _ => bail!(
+                "Read preference must be one of primary, primaryPreferred, secondary, secondaryPreferred, or nearest"
+            ),
+        }
+    }
+}
+
+impl Display for ReadMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Primary => write!(f, "primary"),
+            Self::PrimaryPreferred => write!(f, "primaryPreferred"),
+            Self::Secondary => write!(f, "secondary"),
+            Self::SecondaryPreferred => write!(f, "secondaryPreferred"),
+            Self::Nearest => write!(f, "nearest"),
+        }
+    }
+}
+
+/// Where a tab's reads are sent and how durable its reads and writes need to
+/// be. Anything that isn't set comes from the connection string.
+///
+/// These are written like connection string options, eg.
+/// `readPreference=secondaryPreferred&readPreferenceTags=dc:east&w=majority`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Concerns {
+    pub read_preference: Option<ReadMode>,
+
+    /// Tag sets that narrow down which members can be read from, tried in
+    /// order until one matches.
+    pub read_preference_tags: Vec<BTreeMap<String, String>>,
+
+    pub read_concern: Option<String>,
+
+    /// How many members (or `majority`, or a custom tag) need to acknowledge
+    /// a write.
+    pub w: Option<String>,
+
+    pub journal: Option<bool>,
+    pub wtimeout_ms: Option<u64>,
+}
+
+impl FromStr for Concerns {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut concerns = Self::default();
+        for option in s
+            .split(|c: char| c == '&' || c.is_whitespace())
+            .filter(|option| !option.is_empty())
+        {
+            let Some((key, value)) = option.split_once('=') else {
+                bail!("Option \"{option}\" is missing a value");
+            };
+            match key.to_lowercase().as_str() {
+                "readpreference" => concerns.read_preference = Some(value.parse()?),
+                "readpreferencetags" => concerns.read_preference_tags.push(parse_tag_set(value)?),
+                "readconcernlevel" => {
+                    if !READ_CONCERN_LEVELS.contains(&value) {
+                        bail!(
+                            "Read concern level must be one of {}",
+                            READ_CONCERN_LEVELS.join(", ")
+                        );
+                    }
+                    concerns.read_concern = Some(value.to_string());
+                }
+                "w" => concerns.w = Some(value.to_string()),
+                "journal" | "j" => {
+                    concerns.journal = Some(
+                        value
+                            .parse()
+                            .context("Journal must be either true or false")?,
+                    );
+                }
+                "wtimeoutms" => {
+                    concerns.wtimeout_ms = Some(
+                        value
+                            .parse()
+                            .context("wtimeoutMS must be a number of milliseconds")?,
+                    );
+                }
+                _ => bail!("Unknown read or write concern option: {key}"),
+            }
+        }
+
+        if !concerns.read_preference_tags.is_empty()
+            && matches!(concerns.read_preference, None | Some(ReadMode::Primary))
+        {
+            bail!("Read preference tags need a read preference other than primary");
+        }
+
+        Ok(concerns)
+    }
+}
+
+impl Display for Concerns {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut options = vec![];
+        if let Some(mode) = self.read_preference {
+            options.push(format!("readPreference={mode}"));
+        }
+        for tag_set in &self.read_preference_tags {
+            options.push(format!("readPreferenceTags={}", format_tag_set(tag_set)));
+        }
+        if let Some(level) = &self.read_concern {
+            options.push(format!("readConcernLevel={level}"));
+        }
+        if let Some(w) = &self.w {
+            options.push(format!("w={w}"));
+        }
+        if let Some(journal) = self.journal {
+            options.push(format!("journal={journal}"));
+        }
+        if let Some(wtimeout_ms) = self.wtimeout_ms {
+            options.push(format!("wtimeoutMS={wtimeout_ms}"));
+        }
+        write!(f, "{}", options.join("&"))
+    }
+}
+
+impl Concerns {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// A short description of the concerns that are set, for the status bar.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let mut parts = vec![];
+        if let Some(mode) = self.read_preference {
+            let tags = self
+                .read_preference_tags
+                .iter()
+                .map(format_tag_set)
+                .collect::<Vec<_>>();
+            if tags.is_empty() {
+                parts.push(mode.to_string());
+            } else {
+                parts.push(format!("{mode} ({})", tags.join(" | ")));
+            }
+        }
+        if let Some(level) = &self.read_concern {
+            parts.push(format!("read {level}"));
+        }
+
+        let mut write = vec![];
+        if let Some(w) = &self.w {
+            write.push(format!("w:{w}"));
+        }
+        if let Some(journal) = self.journal {
+            write.push(format!("j:{journal}"));
+        }
+        if let Some(wtimeout_ms) = self.wtimeout_ms {
+            write.push(format!("wtimeout:{wtimeout_ms}ms"));
+        }
+        if !write.is_empty() {
+            parts.push(write.join(" "));
+        }
+
+        parts.join(" · ")
+    }
+
+    /// The options to open databases with, so that every operation on them
+    /// (and their collections) uses these concerns.
+    #[must_use]
+    pub fn database_options(&self) -> DatabaseOptions {
+        let selection_criteria = self.read_preference.map(|mode| {
+            let options = (!self.read_preference_tags.is_empty()).then(|| {
+                ReadPreferenceOptions::builder()
+                    .tag_sets(
+                        self.read_preference_tags
+                            .iter()
+                            .map(|tag_set| tag_set.clone().into_iter().collect())
+                            .collect::<Vec<_>>(),
+                    )
+                    .build()
+            });
+            SelectionCriteria::ReadPreference(match mode {
+                ReadMode::Primary => ReadPreference::Primary,
+                ReadMode::PrimaryPreferred => ReadPreference::PrimaryPreferred { options },
+                ReadMode::Secondary => ReadPreference::Secondary { options },
+                ReadMode::SecondaryPreferred => ReadPreference::SecondaryPreferred { options },
+                ReadMode::Nearest => ReadPreference::Nearest { options },
+            })
+        });
+
+        let write_concern =
+            (self.w.is_some() || self.journal.is_some() || self.wtimeout_ms.is_some()).then(|| {
+                let w = self.w.as_ref().map(|w| {
+                    w.parse::<u32>()
+                        .map_or_else(|_| Acknowledgment::from(w.as_str()), Acknowledgment::from)
+                });
+                WriteConcern::builder()
+                    .w(w)
+                    .journal(self.journal)
+                    .w_timeout(self.wtimeout_ms.map(Duration::from_millis))
+                    .build()
+            });
+
+        DatabaseOptions::builder()
+            .selection_criteria(selection_criteria)
+            .read_concern(self.read_concern.as_ref().map(ReadConcern::custom))
+            .write_concern(write_concern)
+            .build()
+    }
+}
+
+/// Parses a tag set that's written like `dc:east,rack:1`.
+fn parse_tag_set(s: &str) -> Result<BTreeMap<String, String>> {
+    s.split(',')
+        .filter(|tag| !tag.is_empty())
+        .map(|tag| {
+            let Some((name, value)) = tag.split_once(':') else {
+                bail!("Read preference tag \"{tag}\" should be written like name:value");
+            };
+            Ok((name.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+fn format_tag_set(tag_set: &BTreeMap<String, String>) -> String {
+    tag_set
+        .iter()
+        .map(|(name, value)| format!("{name}:{value}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[expect(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_concerns() {
+        let concerns: Concerns = "readPreference=secondaryPreferred&readPreferenceTags=dc:east,rack:1 w=majority journal=true wtimeoutMS=5000"
+            .parse()
+            .unwrap();
+        assert_eq!(concerns.read_preference, Some(ReadMode::SecondaryPreferred));
+        assert_eq!(
+            concerns.read_preference_tags,
+            vec![BTreeMap::from([
+                ("dc".to_string(), "east".to_string()),
+                ("rack".to_string(), "1".to_string()),
+            ])]
+        );
+        assert_eq!(concerns.w.as_deref(), Some("majority"));
+        assert_eq!(
+            concerns.to_string(),
+            "readPreference=secondaryPreferred&readPreferenceTags=dc:east,rack:1&w=majority&journal=true&wtimeoutMS=5000"
+        );
+        assert_eq!(
+            concerns.summary(),
+            "secondaryPreferred (dc:east,rack:1) · w:majority j:true wtimeout:5000ms"
+        );
+
+        assert!("".parse::<Concerns>().unwrap().is_empty());
+        assert!("readPreferenceTags=dc:east".parse::<Concerns>().is_err());
+        assert!("readConcernLevel=strong".parse::<Concerns>().is_err());
+        assert!("maxPoolSize=5".parse::<Concerns>().is_err());
+    }
+
+    #[test]
+    fn build_database_options() {
+        let concerns: Concerns = "readPreference=nearest&readConcernLevel=majority&w=2"
+            .parse()
+            .unwrap();
+        let options = concerns.database_options();
+
+        assert!(matches!(
+            options.selection_criteria,
+            Some(SelectionCriteria::ReadPreference(ReadPreference::Nearest {
+                options: None
+            }))
+        ));
+        assert_eq!(options.read_concern, Some(ReadConcern::majority()));
+        assert_eq!(
+            options.write_concern.and_then(|w| w.w),
+            Some(Acknowledgment::Nodes(2))
+        );
+
+        let options = Concerns::default().database_options();
+        assert!(options.selection_criteria.is_none());
+        assert!(options.read_concern.is_none());
+        assert!(options.write_concern.is_none());
+    }
+}
@@ -0,0 +1,202 @@
+use mongodb::options::{ClientOptions, ReadPreference, SelectionCriteria, Tls};
+
+/// The options that the driver understands in a connection string, in
+/// lowercase.
+const KNOWN_OPTIONS: [&str; 40] = [
+    "appname",
+    "authmechanism",
+    "authmechanismproperties",
+    "authsource",
+    "compressors",
+    "connecttimeoutms",
+    "directconnection",
+    "heartbeatfrequencyms",
+    "journal",
+    "loadbalanced",
+    "localthresholdms",
+    "maxconnecting",
+    "maxidletimems",
+    "maxpoolsize",
+    "maxstalenessseconds",
+    "minpoolsize",
+    "readconcernlevel",
+    "readpreference",
+    "readpreferencetags",
+    "replicaset",
+    "retryreads",
+    "retrywrites",
+    "servermonitoringmode",
+    "serverselectiontimeoutms",
+    "sockettimeoutms",
+    "srvmaxhosts",
+    "srvservicename",
+    "ssl",
+    "tls",
+    "tlsallowinvalidcertificates",
+    "tlsallowinvalidhostnames",
+    "tlscafile",
+    "tlscertificatekeyfile",
+    "tlscertificatekeyfilepassword",
+    "tlsinsecure",
+    "uuidrepresentation",
+    "w",
+    "waitqueuetimeoutms",
+    "wtimeoutms",
+    "zlibcompressionlevel",
+];
+
+/// Takes the options that the driver doesn't know out of a connection string
+/// (since it refuses to parse a string with any of them), returning the
+/// cleaned-up string and the names of the unknown options.
+#[must_use]
+pub fn split_unknown_options(conn_str: &str) -> (String, Vec<String>) {
+    let Some((base, query)) = conn_str.split_once('?') else {
+        return (conn_str.to_string(), vec![]);
+    };
+
+    let (known, unknown): (Vec<&str>, Vec<&str>) = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .partition(|pair| {
+            let key = pair.split_once('=').map_or(*pair, |(key, _)| key);
+            KNOWN_OPTIONS.contains(&key.to_lowercase().as_str())
+        });
+    let unknown = unknown
+        .into_iter()
+        .map(|pair| {
+            pair.split_once('=')
+                .map_or(pair, |(key, _)| key)
+                .to_string()
+        })
+        .collect();
+
+    if known.is_empty() {
+        (base.to_string(), unknown)
+    } else {
+        (format!("{base}?{}", known.join("&")), unknown)
+    }
+}
+
+/// The parts of a parsed connection string that are worth checking before
+/// connecting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnStrInfo {
+    pub hosts: Vec<String>,
+    pub auth: Option<String>,
+    pub replica_set: Option<String>,
+    pub tls: bool,
+    pub read_preference: &'static str,
+    pub unknown_options: Vec<String>,
+}
+
+impl ConnStrInfo {
+    pub fn new(options: &ClientOptions, unknown_options: Vec<String>) -> Self {
+        let auth = options.credential.as_ref().map(|credential| {
+            let mechanism = credential
+                .mechanism
+                .as_ref()
+                .map_or("default mechanism", |mechanism| mechanism.as_str());
+            let source = credential
+                .source
+                .as_deref()
+                .or(options.default_database.as_deref())
+                .unwrap_or("admin");
+            credential.username.as_ref().map_or_else(
+                || format!("{mechanism}, source {source}"),
+                |username| format!("{username} with {mechanism}, source {source}"),
+            )
+        });
+
+        let read_preference = match &options.selection_criteria {
+            Some(SelectionCriteria::ReadPreference(read_preference)) => match read_preference {
+                ReadPreference::Secondary { .. } => "secondary",
+                ReadPreference::PrimaryPreferred { .. } => "primaryPreferred",
+                ReadPreference::SecondaryPreferred { .. } => "secondaryPreferred",
+                ReadPreference::Nearest { .. } => "nearest",
+                _ => "primary",
+            },
+            _ => "primary",
+        };
+
+        Self {
+            hosts: options.hosts.iter().map(ToString::to_string).collect(),
+            auth,
+            replica_set: options.repl_set_name.clone(),
+            tls: matches!(options.tls, Some(Tls::Enabled(..))),
+            read_preference,
+            unknown_options,
+        }
+    }
+
+    /// The info as labeled lines, for showing in a preview.
+    #[must_use]
+    pub fn lines(&self) -> Vec<(&'static str, String)> {
+        let or_none = |value: Option<&String>| value.cloned().unwrap_or_else(|| "none".into());
+
+        vec![
+            ("Hosts", self.hosts.join(", ")),
+            ("Auth", or_none(self.auth.as_ref())),
+            ("Replica set", or_none(self.replica_set.as_ref())),
+            ("TLS", if self.tls { "on" } else { "off" }.to_string()),
+            ("Read preference", self.read_preference.to_string()),
+            (
+                "Unknown options",
+                if self.unknown_options.is_empty() {
+                    "none".to_string()
+                } else {
+                    self.unknown_options.join(", ")
+                },
+            ),
+        ]
+    }
+}
+
+#[expect(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::options::{Credential, ServerAddress};
+
+    #[test]
+    fn take_out_unknown_options() {
+        let (conn_str, unknown) = split_unknown_options(
+            "mongodb://localhost/?replicaSet=rs0&replicaset2=x&retryWrites=true&foo",
+        );
+        assert_eq!(
+            conn_str,
+            "mongodb://localhost/?replicaSet=rs0&retryWrites=true"
+        );
+        assert_eq!(unknown, vec!["replicaset2", "foo"]);
+
+        let (conn_str, unknown) = split_unknown_options("mongodb://localhost/?foo=bar");
+        assert_eq!(conn_str, "mongodb://localhost/");
+        assert_eq!(unknown, vec!["foo"]);
+    }
+
+    #[test]
+    fn read_client_options() {
+        let options = ClientOptions::builder()
+            .hosts(vec![
+                ServerAddress::parse("db-1:27017").unwrap(),
+                ServerAddress::parse("db-2:27017").unwrap(),
+            ])
+            .credential(
+                Credential::builder()
+                    .username("admin".to_string())
+                    .source("users".to_string())
+                    .build(),
+            )
+            .repl_set_name("rs0".to_string())
+            .build();
+
+        let info = ConnStrInfo::new(&options, vec![]);
+        assert_eq!(info.hosts, vec!["db-1:27017", "db-2:27017"]);
+        assert_eq!(
+            info.auth.as_deref(),
+            Some("admin with default mechanism, source users")
+        );
+        assert_eq!(info.replica_set.as_deref(), Some("rs0"));
+        assert!(!info.tls);
+        assert_eq!(info.read_preference, "primary");
+    }
+}
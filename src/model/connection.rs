@@ -1,9 +1,13 @@
 use crate::utils::storage::{FileStorage, Storage};
 use anyhow::Result;
+use mongodb::options::{AuthMechanism, ClientOptions, Credential, Tls, TlsOptions};
 use serde::{Deserialize, Serialize};
 use std::{
     cell::{Ref, RefCell},
+    fmt::Display,
+    path::PathBuf,
     rc::Rc,
+    str::FromStr,
 };
 use uuid::Uuid;
 
@@ -13,6 +17,27 @@ pub struct Connection {
     id: Uuid,
     pub name: String,
     pub connection_str: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssh_tunnel: Option<SshTunnel>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsSettings>,
+
+    /// The folder that the connection is grouped under. Nested folders are
+    /// separated by `/`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<Environment>,
+
+    /// Blocks every write to the database through this connection.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub read_only: bool,
 }
 
 impl Connection {
@@ -23,6 +48,12 @@ impl Connection {
             id: Uuid::new_v4(),
             name,
             connection_str,
+            ssh_tunnel: None,
+            tls: None,
+            folder: None,
+            tags: vec![],
+            environment: None,
+            read_only: false,
         }
     }
 
@@ -32,6 +63,234 @@ impl Connection {
     }
 }
 
+/// The kind of deployment that a connection points at, which determines the
+/// color of its tab and panel borders.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Environment {
+    Dev,
+    Staging,
+    Prod,
+}
+
+impl FromStr for Environment {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "dev" | "development" => Ok(Self::Dev),
+            "staging" | "stage" => Ok(Self::Staging),
+            "prod" | "production" => Ok(Self::Prod),
+            _ => anyhow::bail!("Environment must be one of dev, staging, or prod"),
+        }
+    }
+}
+
+impl Display for Environment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Dev => write!(f, "dev"),
+            Self::Staging => write!(f, "staging"),
+            Self::Prod => write!(f, "prod"),
+        }
+    }
+}
+
+const DEFAULT_SSH_PORT: u16 = 22;
+
+/// Settings for reaching a server through an SSH bastion host.
+///
+/// These are written as a URI, like
+/// `ssh://user@bastion:2222?key_file=~/.ssh/id_ed25519&known_hosts=no`.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct SshTunnel {
+    pub host: String,
+    pub port: u16,
+    pub user: Option<String>,
+
+    /// The private key to log in with. Without one, the SSH agent (or the
+    /// user's SSH config) supplies the key.
+    pub key_file: Option<String>,
+
+    /// Whether to refuse to connect to a host that isn't in `known_hosts`, or
+    /// whose key doesn't match.
+    pub check_known_hosts: bool,
+}
+
+impl FromStr for SshTunnel {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let rest = s.strip_prefix("ssh://").unwrap_or(s);
+        let (authority, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let authority = authority.trim_end_matches('/');
+
+        let (user, host_port) = match authority.rsplit_once('@') {
+            Some((user, host_port)) => (Some(user.to_string()), host_port),
+            None => (None, authority),
+        };
+        let (host, port) = match host_port.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse()?),
+            None => (host_port, DEFAULT_SSH_PORT),
+        };
+        if host.is_empty() {
+            anyhow::bail!("SSH tunnel is missing a host");
+        }
+
+        let mut tunnel = Self {
+            host: host.to_string(),
+            port,
+            user,
+            key_file: None,
+            check_known_hosts: true,
+        };
+        for param in query.split('&').filter(|param| !param.is_empty()) {
+            match param.split_once('=').unwrap_or((param, "")) {
+                ("key_file", path) => tunnel.key_file = Some(path.to_string()),
+                ("known_hosts", "no") => tunnel.check_known_hosts = false,
+                ("known_hosts", "yes") => tunnel.check_known_hosts = true,
+                _ => anyhow::bail!("Unknown SSH tunnel option: {param}"),
+            }
+        }
+
+        Ok(tunnel)
+    }
+}
+
+impl Display for SshTunnel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ssh://")?;
+        if let Some(user) = &self.user {
+            write!(f, "{user}@")?;
+        }
+        write!(f, "{}", self.host)?;
+        if self.port != DEFAULT_SSH_PORT {
+            write!(f, ":{}", self.port)?;
+        }
+
+        let mut params = vec![];
+        if let Some(key_file) = &self.key_file {
+            params.push(format!("key_file={key_file}"));
+        }
+        if !self.check_known_hosts {
+            params.push("known_hosts=no".to_string());
+        }
+        if !params.is_empty() {
+            write!(f, "?{}", params.join("&"))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// TLS settings that are applied on top of the options in the connection
+/// string, so that file paths don't need to be encoded into it.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TlsSettings {
+    /// The CA certificates to trust, in a PEM file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_file: Option<String>,
+
+    /// The client certificate and its private key, together in a PEM file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cert_key_file: Option<String>,
+
+    /// Whether to accept any server certificate, even an expired one, one for
+    /// another host, or one that isn't signed by a trusted CA.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_invalid_certificates: bool,
+
+    /// Whether to authenticate with the client certificate (`MONGODB-X509`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub x509: bool,
+}
+
+impl TlsSettings {
+    const X509_FLAG: &str = "x509";
+    const ALLOW_INVALID_CERTIFICATES_FLAG: &str = "allow-invalid-certificates";
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Sets the on/off settings from a comma-separated list of flags, like
+    /// `x509, allow-invalid-certificates`.
+    ///
+    /// # Errors
+    /// If one of the flags isn't known.
+    pub fn set_flags(&mut self, flags: &str) -> Result<()> {
+        self.x509 = false;
+        self.allow_invalid_certificates = false;
+        for flag in flags.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+            match flag.to_lowercase().as_str() {
+                Self::X509_FLAG => self.x509 = true,
+                Self::ALLOW_INVALID_CERTIFICATES_FLAG => self.allow_invalid_certificates = true,
+                _ => anyhow::bail!(
+                    "Unknown TLS option \"{flag}\", expected {} or {}",
+                    Self::X509_FLAG,
+                    Self::ALLOW_INVALID_CERTIFICATES_FLAG
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// The on/off settings that are set, as written by `set_flags`.
+    #[must_use]
+    pub fn flags(&self) -> String {
+        let mut flags = vec![];
+        if self.x509 {
+            flags.push(Self::X509_FLAG);
+        }
+        if self.allow_invalid_certificates {
+            flags.push(Self::ALLOW_INVALID_CERTIFICATES_FLAG);
+        }
+        flags.join(", ")
+    }
+
+    /// Turns on TLS in the client options with these settings, keeping any
+    /// TLS options that came from the connection string.
+    pub fn apply(&self, options: &mut ClientOptions) {
+        let mut tls_options = match options.tls.take() {
+            Some(Tls::Enabled(tls_options)) => tls_options,
+            _ => TlsOptions::default(),
+        };
+        if let Some(ca_file) = &self.ca_file {
+            tls_options.ca_file_path = Some(expand_home(ca_file));
+        }
+        if let Some(cert_key_file) = &self.cert_key_file {
+            tls_options.cert_key_file_path = Some(expand_home(cert_key_file));
+        }
+        if self.allow_invalid_certificates {
+            tls_options.allow_invalid_certificates = Some(true);
+        }
+        options.tls = Some(Tls::Enabled(tls_options));
+
+        if self.x509 {
+            // the user comes from the certificate's subject, unless it's given
+            let username = options
+                .credential
+                .take()
+                .and_then(|credential| credential.username);
+            let mut credential = Credential::default();
+            credential.username = username;
+            credential.mechanism = Some(AuthMechanism::MongoDbX509);
+            credential.source = Some("$external".to_string());
+            options.credential = Some(credential);
+        }
+    }
+}
+
+/// Replaces a leading `~` in a path with the user's home directory.
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ConnectionManager {
     connections: Rc<RefCell<Vec<Connection>>>,
@@ -95,3 +354,91 @@ impl ConnectionManager {
         self.storage.write_connections(&connections)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ssh_tunnel() {
+        let tunnel: SshTunnel =
+            "ssh://deploy@bastion:2222?key_file=~/.ssh/id_ed25519&known_hosts=no"
+                .parse()
+                .expect("should parse");
+        assert_eq!(
+            tunnel,
+            SshTunnel {
+                host: "bastion".to_string(),
+                port: 2222,
+                user: Some("deploy".to_string()),
+                key_file: Some("~/.ssh/id_ed25519".to_string()),
+                check_known_hosts: false,
+            }
+        );
+        assert_eq!(
+            tunnel.to_string(),
+            "ssh://deploy@bastion:2222?key_file=~/.ssh/id_ed25519&known_hosts=no"
+        );
+
+        let tunnel: SshTunnel = "bastion".parse().expect("should parse");
+        assert_eq!(tunnel.port, 22);
+        assert!(tunnel.check_known_hosts);
+        assert_eq!(tunnel.to_string(), "ssh://bastion");
+
+        assert!("ssh://user@".parse::<SshTunnel>().is_err());
+        assert!("bastion?compress=yes".parse::<SshTunnel>().is_err());
+    }
+
+    #[test]
+    fn parse_tls_flags() {
+        let mut tls = TlsSettings::default();
+        tls.set_flags(" X509,allow-invalid-certificates, ")
+            .expect("should parse");
+        assert!(tls.x509 && tls.allow_invalid_certificates);
+        assert_eq!(tls.flags(), "x509, allow-invalid-certificates");
+
+        tls.set_flags("").expect("should parse");
+        assert!(tls.is_empty());
+
+        assert!(tls.set_flags("x509, insecure").is_err());
+        // the check can't be turned off for only the hostname
+        assert!(tls.set_flags("allow-invalid-hostnames").is_err());
+    }
+
+    #[test]
+    fn apply_tls_settings() {
+        let mut options = ClientOptions::default();
+        options.credential = Some(Credential::default());
+        TlsSettings {
+            ca_file: Some("/certs/ca.pem".to_string()),
+            cert_key_file: Some("/certs/client.pem".to_string()),
+            allow_invalid_certificates: false,
+            x509: true,
+        }
+        .apply(&mut options);
+
+        let Some(Tls::Enabled(tls_options)) = options.tls else {
+            panic!("TLS should be enabled");
+        };
+        assert_eq!(tls_options.ca_file_path, Some("/certs/ca.pem".into()));
+        assert_eq!(
+            tls_options.cert_key_file_path,
+            Some("/certs/client.pem".into())
+        );
+        assert_eq!(tls_options.allow_invalid_certificates, None);
+
+        let credential = options.credential.expect("should have a credential");
+        assert_eq!(credential.mechanism, Some(AuthMechanism::MongoDbX509));
+        assert_eq!(credential.source.as_deref(), Some("$external"));
+    }
+
+    #[test]
+    fn parse_environment() {
+        assert_eq!(
+            "Production".parse::<Environment>().ok(),
+            Some(Environment::Prod)
+        );
+        assert_eq!("dev".parse::<Environment>().ok(), Some(Environment::Dev));
+        assert!("qa".parse::<Environment>().is_err());
+    }
+}
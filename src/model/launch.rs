@@ -0,0 +1,183 @@
+use crate::utils::edit_doc::parse_query_doc;
+use anyhow::{bail, Context, Result};
+use mongodb::bson::Document;
+use percent_encoding::percent_decode_str;
+
+// the options that tongo reads from a connection string (rather than the
+// driver), in lowercase
+const COLL_OPTION: &str = "tongocoll";
+const FILTER_OPTION: &str = "tongofilter";
+const PROJECTION_OPTION: &str = "tongoprojection";
+const SORT_OPTION: &str = "tongosort";
+
+/// Where to open the app once it's connected, as it's written on the command
+/// line or in a connection string. Queries are still unparsed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LaunchSpec {
+    pub db: Option<String>,
+    pub coll: Option<String>,
+    pub filter: Option<String>,
+    pub projection: Option<String>,
+    pub sort: Option<String>,
+}
+
+impl LaunchSpec {
+    /// Takes tongo's own options (`tongoColl`, `tongoFilter`,
+    /// `tongoProjection`, and `tongoSort`) out of a connection string, since
+    /// the driver refuses to parse them. Returns the cleaned-up string and the
+    /// spec. The database in the string's path is only opened if one of those
+    /// options is there, since it's also the default database for auth.
+    #[must_use]
+    pub fn split_conn_str(conn_str: &str) -> (String, Self) {
+        let mut spec = Self::default();
+        let Some((base, query)) = conn_str.split_once('?') else {
+            return (conn_str.to_string(), spec);
+        };
+
+        let mut kept = vec![];
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let value = percent_decode_str(value).decode_utf8_lossy().to_string();
+            match key.to_lowercase().as_str() {
+                COLL_OPTION => spec.coll = Some(value),
+                FILTER_OPTION => spec.filter = Some(value),
+                PROJECTION_OPTION => spec.projection = Some(value),
+                SORT_OPTION => spec.sort = Some(value),
+                _ => kept.push(pair),
+            }
+        }
+
+        if !spec.is_empty() {
+            spec.db = path_db(base);
+        }
+
+        let conn_str = if kept.is_empty() {
+            base.to_string()
+        } else {
+            format!("{base}?{}", kept.join("&"))
+        };
+        (conn_str, spec)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Combines two specs, preferring the parts of this one.
+    #[must_use]
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            db: self.db.or(other.db),
+            coll: self.coll.or(other.coll),
+            filter: self.filter.or(other.filter),
+            projection: self.projection.or(other.projection),
+            sort: self.sort.or(other.sort),
+        }
+    }
+
+    /// Parses the spec's queries, returning `None` if the spec is empty.
+    ///
+    /// # Errors
+    /// If a query can't be parsed, or a part of the spec is missing one that
+    /// it depends on (like a collection without a database).
+    pub fn into_target(self) -> Result<Option<LaunchTarget>> {
+        if self.is_empty() {
+            return Ok(None);
+        }
+        let Some(db) = self.db else {
+            bail!("A database is needed to open a collection or query");
+        };
+        let has_query = self.filter.is_some() || self.projection.is_some() || self.sort.is_some();
+        if self.coll.is_none() && has_query {
+            bail!("A collection is needed to open a query");
+        }
+
+        let parse = |text: Option<String>, name: &str| {
+            text.map_or_else(
+                || Ok(Document::new()),
+                |text| parse_query_doc(&text).with_context(|| format!("Invalid {name}")),
+            )
+        };
+        Ok(Some(LaunchTarget {
+            db,
+            coll: self.coll,
+            filter: parse(self.filter, "filter")?,
+            projection: parse(self.projection, "projection")?,
+            sort: parse(self.sort, "sort")?,
+        }))
+    }
+}
+
+/// A database (and maybe a collection and query) to open at startup.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaunchTarget {
+    pub db: String,
+    pub coll: Option<String>,
+    pub filter: Document,
+    pub projection: Document,
+    pub sort: Document,
+}
+
+/// The database in the path of a connection string (without its options).
+fn path_db(base: &str) -> Option<String> {
+    let hosts_and_path = base.split_once("://").map_or(base, |(_, rest)| rest);
+    let (_, db) = hosts_and_path.split_once('/')?;
+    let db = percent_decode_str(db).decode_utf8_lossy().to_string();
+    (!db.is_empty()).then_some(db)
+}
+
+#[expect(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::bson::doc;
+
+    #[test]
+    fn take_launch_options_out_of_conn_str() {
+        let (conn_str, spec) = LaunchSpec::split_conn_str(
+            "mongodb://localhost:27017/shop?retryWrites=true&tongoColl=orders&tongoFilter=%7B%20status%3A%20%22open%22%20%7D",
+        );
+        assert_eq!(conn_str, "mongodb://localhost:27017/shop?retryWrites=true");
+        assert_eq!(spec.db.as_deref(), Some("shop"));
+        assert_eq!(spec.coll.as_deref(), Some("orders"));
+
+        let target = spec.into_target().unwrap().unwrap();
+        assert_eq!(target.filter, doc! { "status": "open" });
+        assert!(target.sort.is_empty());
+
+        // without tongo's options, the database is only the default for auth
+        let (conn_str, spec) = LaunchSpec::split_conn_str("mongodb://localhost/admin");
+        assert_eq!(conn_str, "mongodb://localhost/admin");
+        assert!(spec.is_empty());
+    }
+
+    #[test]
+    fn combine_flags_and_conn_str() {
+        let flags = LaunchSpec {
+            sort: Some("{ total: -1 }".into()),
+            ..Default::default()
+        };
+        let (_, from_conn_str) = LaunchSpec::split_conn_str(
+            "mongodb://localhost/shop?tongoColl=orders&tongoSort=%7Ba%3A1%7D",
+        );
+
+        let target = flags.or(from_conn_str).into_target().unwrap().unwrap();
+        assert_eq!(target.db, "shop");
+        assert_eq!(target.coll.as_deref(), Some("orders"));
+        assert_eq!(target.sort, doc! { "total": -1_i64 });
+
+        let no_db = LaunchSpec {
+            coll: Some("orders".into()),
+            ..Default::default()
+        };
+        assert!(no_db.into_target().is_err());
+
+        let no_coll = LaunchSpec {
+            db: Some("shop".into()),
+            filter: Some("{}".into()),
+            ..Default::default()
+        };
+        assert!(no_coll.into_target().is_err());
+    }
+}
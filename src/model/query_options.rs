@@ -0,0 +1,146 @@
+use anyhow::{bail, Context, Result};
+use mongodb::{
+    bson::{self, Bson, Document},
+    options::{Collation, CountOptions, FindOptions, Hint},
+};
+use std::time::Duration;
+
+/// The options of a query besides its filter, projection, and sort. They're
+/// written as a document, like
+/// `{ hint: "email_1", collation: { locale: "en", strength: 2 }, limit: 100 }`.
+#[derive(Debug, Clone, Default)]
+pub struct QueryOptions {
+    pub hint: Option<Hint>,
+    pub collation: Option<Collation>,
+    pub max_time: Option<Duration>,
+    pub allow_disk_use: Option<bool>,
+    pub comment: Option<Bson>,
+
+    /// The most documents that the query matches, across every page.
+    pub limit: Option<u64>,
+}
+
+impl TryFrom<&Document> for QueryOptions {
+    type Error = anyhow::Error;
+
+    fn try_from(doc: &Document) -> Result<Self> {
+        let mut options = Self::default();
+        for (key, value) in doc {
+            match key.as_str() {
+                "hint" => {
+                    options.hint = Some(match value {
+                        Bson::String(name) => Hint::Name(name.clone()),
+                        Bson::Document(keys) => Hint::Keys(keys.clone()),
+                        _ => bail!("hint must be an index name or the keys of an index"),
+                    });
+                }
+                "collation" => {
+                    let Bson::Document(collation) = value else {
+                        bail!("collation must be a document, like {{ locale: \"en\" }}");
+                    };
+                    options.collation =
+                        Some(bson::from_document(collation.clone()).context("Invalid collation")?);
+                }
+                "maxTimeMS" => options.max_time = Some(Duration::from_millis(as_u64(key, value)?)),
+                "allowDiskUse" => {
+                    let Bson::Boolean(allow) = value else {
+                        bail!("allowDiskUse must be true or false");
+                    };
+                    options.allow_disk_use = Some(*allow);
+                }
+                "comment" => options.comment = Some(value.clone()),
+                "limit" => options.limit = Some(as_u64(key, value)?),
+                _ => bail!("Unknown query option: {key}"),
+            }
+        }
+        Ok(options)
+    }
+}
+
+impl QueryOptions {
+    /// Adds the options to the options of a `find`, except for the limit,
+    /// which depends on the page.
+    pub fn apply_to_find(&self, options: &mut FindOptions) {
+        options.hint.clone_from(&self.hint);
+        options.collation.clone_from(&self.collation);
+        options.max_time = self.max_time;
+        options.allow_disk_use = self.allow_disk_use;
+        options.comment.clone_from(&self.comment);
+    }
+
+    #[must_use]
+    pub fn count_options(&self) -> CountOptions {
+        CountOptions::builder()
+            .hint(self.hint.clone())
+            .collation(self.collation.clone())
+            .max_time(self.max_time)
+            .comment(self.comment.clone())
+            .limit(self.limit)
+            .build()
+    }
+
+    /// How many documents to fetch for the page that starts at `skip`, so
+    /// that no more than the limit are shown in total.
+    #[must_use]
+    pub fn page_limit(&self, skip: u64, page_size: u64) -> u64 {
+        self.limit
+            .map_or(page_size, |limit| page_size.min(limit.saturating_sub(skip)))
+    }
+}
+
+fn as_u64(key: &str, value: &Bson) -> Result<u64> {
+    let number = match value {
+        Bson::Int32(n) => u64::try_from(*n).ok(),
+        Bson::Int64(n) => u64::try_from(*n).ok(),
+        _ => None,
+    };
+    number.with_context(|| format!("{key} must be a whole number that's at least 0"))
+}
+
+#[expect(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::{bson::doc, options::CollationStrength};
+
+    #[test]
+    fn read_query_options() {
+        let options = QueryOptions::try_from(&doc! {
+            "hint": "email_1",
+            "collation": { "locale": "en", "strength": 2_i64 },
+            "maxTimeMS": 5000_i64,
+            "allowDiskUse": true,
+            "comment": "analysis",
+            "limit": 250_i64,
+        })
+        .unwrap();
+
+        assert_eq!(options.hint, Some(Hint::Name("email_1".to_string())));
+        let collation = options.collation.as_ref().unwrap();
+        assert_eq!(collation.locale, "en");
+        assert!(matches!(
+            collation.strength,
+            Some(CollationStrength::Secondary)
+        ));
+        assert_eq!(options.max_time, Some(Duration::from_secs(5)));
+        assert_eq!(options.allow_disk_use, Some(true));
+        assert_eq!(options.limit, Some(250));
+
+        assert!(QueryOptions::try_from(&doc! { "limit": -1 }).is_err());
+        assert!(QueryOptions::try_from(&doc! { "batchSize": 10 }).is_err());
+        assert!(QueryOptions::try_from(&doc! { "collation": { "strength": 2 } }).is_err());
+    }
+
+    #[test]
+    fn stop_pages_at_limit() {
+        let options = QueryOptions {
+            limit: Some(25),
+            ..Default::default()
+        };
+        assert_eq!(options.page_limit(0, 10), 10);
+        assert_eq!(options.page_limit(20, 10), 5);
+        assert_eq!(options.page_limit(30, 10), 0);
+
+        assert_eq!(QueryOptions::default().page_limit(30, 10), 10);
+    }
+}
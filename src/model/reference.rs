@@ -0,0 +1,58 @@
+use super::{collection::Collection, database::Database};
+use mongodb::bson::{Bson, Document};
+
+/// A value in a document that points to another document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reference {
+    /// The database of the referenced document, if not the current one.
+    pub db: Option<String>,
+
+    /// The collection of the referenced document. If it isn't known, every
+    /// collection in the database is searched.
+    pub coll: Option<String>,
+
+    /// The field in the referenced document that has the value.
+    pub field: String,
+
+    pub value: Bson,
+}
+
+impl Reference {
+    /// Creates a reference from a value that points to a document by itself:
+    /// a `DBRef` (a sub-document with `$ref` and `$id` fields) or an `ObjectId`.
+    #[must_use]
+    pub fn from_value(value: &Bson) -> Option<Self> {
+        match value {
+            Bson::ObjectId(_) => Some(Self {
+                db: None,
+                coll: None,
+                field: "_id".to_string(),
+                value: value.clone(),
+            }),
+            Bson::Document(doc) => {
+                let coll = doc.get_str("$ref").ok()?;
+                let id = doc.get("$id")?;
+                Some(Self {
+                    db: doc.get_str("$db").ok().map(String::from),
+                    coll: Some(coll.to_string()),
+                    field: "_id".to_string(),
+                    value: id.clone(),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Where a followed reference led.
+#[derive(Debug, Clone)]
+pub struct ReferenceTarget {
+    pub db: Database,
+    pub coll: Collection,
+
+    /// All the collections in `db`.
+    pub colls: Vec<Collection>,
+
+    /// A filter that matches the referenced document.
+    pub filter: Document,
+}
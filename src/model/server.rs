@@ -0,0 +1,86 @@
+use mongodb::bson::Document;
+use std::{fmt::Display, time::Duration};
+
+/// How a Mongo deployment is set up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Topology {
+    Standalone,
+    ReplicaSet(String),
+    Sharded,
+}
+
+impl Display for Topology {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Standalone => write!(f, "standalone"),
+            Self::ReplicaSet(name) => write!(f, "replica set {name}"),
+            Self::Sharded => write!(f, "sharded"),
+        }
+    }
+}
+
+/// Details about the server that a client is connected to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerInfo {
+    pub version: String,
+    pub topology: Topology,
+    pub primary: Option<String>,
+}
+
+impl ServerInfo {
+    /// Reads the server's details from the responses to the `hello` and
+    /// `buildInfo` commands.
+    pub fn new(hello: &Document, build_info: &Document) -> Self {
+        let topology = if hello.get_str("msg") == Ok("isdbgrid") {
+            Topology::Sharded
+        } else if let Ok(set_name) = hello.get_str("setName") {
+            Topology::ReplicaSet(set_name.to_string())
+        } else {
+            Topology::Standalone
+        };
+
+        Self {
+            version: build_info.get_str("version").unwrap_or("?").to_string(),
+            topology,
+            primary: hello.get_str("primary").ok().map(ToString::to_string),
+        }
+    }
+}
+
+/// What a client knows about the health of its connection.
+#[derive(Debug, Clone, Default)]
+pub struct ServerStatus {
+    pub info: Option<ServerInfo>,
+
+    /// The round-trip time of the latest ping.
+    pub latency: Option<Duration>,
+
+    /// Whether the latest ping failed.
+    pub lost: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::bson::doc;
+
+    #[test]
+    fn read_server_info() {
+        let build_info = doc! { "version": "7.0.2" };
+
+        let info = ServerInfo::new(
+            &doc! { "setName": "rs0", "primary": "db-1:27017", "isWritablePrimary": true },
+            &build_info,
+        );
+        assert_eq!(info.version, "7.0.2");
+        assert_eq!(info.topology, Topology::ReplicaSet("rs0".to_string()));
+        assert_eq!(info.primary.as_deref(), Some("db-1:27017"));
+
+        let info = ServerInfo::new(&doc! { "msg": "isdbgrid" }, &build_info);
+        assert_eq!(info.topology, Topology::Sharded);
+
+        let info = ServerInfo::new(&doc! { "isWritablePrimary": true }, &build_info);
+        assert_eq!(info.topology, Topology::Standalone);
+        assert_eq!(info.primary, None);
+    }
+}
@@ -25,12 +25,35 @@ pub enum Command {
     CreateNew,
     Edit,
     Delete,
+    Encrypt,
+    MakeReadOnly,
+    TestConnection,
+    Reconnect,
+    EditConcerns,
     Search,
+    CycleSearchMode,
+    CycleSearchScope,
     Back,
     Quit,
 
     DuplicateDoc,
     Yank,
+    Undo,
+    Compare,
+    AddField,
+
+    FilterByValue,
+    FilterOutValue,
+    SortByField,
+    IncludeField,
+    ExcludeField,
+    FollowReference,
+
+    ToggleView,
+    SortColumn,
+    HideColumn,
+    ShrinkColumn,
+    GrowColumn,
 
     NewTab,
     NextTab,
@@ -39,6 +62,7 @@ pub enum Command {
     DuplicateTab,
     GotoTab(usize),
 
+    ShowAuditLog,
     ShowHelpModal,
 }
 
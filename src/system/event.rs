@@ -1,13 +1,18 @@
 use super::command::Command;
 use crate::{
+    client::{journal::WriteKind, search::SearchResult, tunnel::Tunnel},
     components::input::input_modal::InputKind,
     error::Error,
-    model::{collection::Collection, connection::Connection, database::Database},
+    model::{
+        collection::Collection, conn_str::ConnStrInfo, connection::Connection, database::Database,
+        reference::ReferenceTarget, server::ServerInfo,
+    },
 };
 use mongodb::{
     bson::{Bson, Document},
     Client as MongoClient,
 };
+use std::{sync::Arc, time::Duration};
 
 #[derive(Debug, Clone, strum_macros::Display)]
 pub enum Event {
@@ -17,9 +22,13 @@ pub enum Event {
     /// Emitted when the app (rather, the terminal window) loses focus.
     AppFocusLost,
 
+    /// Emitted when the user closes the audit log viewer.
+    AuditLogClosed,
+
     /// Emitted when a new client has been created and has successfully
-    /// connected to a Mongo instance.
-    ClientCreated(MongoClient),
+    /// connected to a Mongo instance, along with the SSH tunnel that it
+    /// connects through (if any).
+    ClientCreated(MongoClient, Option<Arc<Tunnel>>),
 
     /// Emitted when a collection has been successfully created on the Mongo
     /// server.
@@ -49,15 +58,33 @@ pub enum Event {
     /// that was being confirmed.
     ConfirmYes(Command),
 
+    /// Emitted when a connection string has been parsed in the background,
+    /// with its options or the reason it couldn't be parsed.
+    ConnStrInspected {
+        conn_str: String,
+        result: Result<ConnStrInfo, String>,
+    },
+
     /// Emitted when a new collection has been created and added to storage.
     ConnectionCreated(Connection),
 
     /// Emitted when a collection has been removed from the user's list.
     ConnectionDeleted,
 
+    /// Emitted when the server stops answering pings.
+    ConnectionLost,
+
+    /// Emitted when the server answers a ping again after the connection was
+    /// lost.
+    ConnectionRestored,
+
     /// Emitted when a collection has been selected by the user.
     ConnectionSelected(Connection),
 
+    /// Emitted when a connection being edited has been pinged successfully,
+    /// with the ping's round-trip time.
+    ConnectionTested(Duration),
+
     /// Emitted when an existing collection has been updated.
     ConnectionUpdated(Connection),
 
@@ -94,6 +121,10 @@ pub enum Event {
     /// Emitted when the user has changed the document search filter.
     DocFilterUpdated(Document),
 
+    /// Emitted when the user has changed the document search options, like
+    /// the index hint or collation.
+    DocOptionsUpdated(Document),
+
     /// Emitted when the user has changed the document search projection.
     DocProjectionUpdated(Document),
 
@@ -104,6 +135,16 @@ pub enum Event {
     /// in the Mongo server.
     DocInsertComplete,
 
+    /// Emitted when the user marks the first of two documents to compare.
+    DocMarkedForComparison,
+
+    /// Emitted when a search across every page of the query's results has
+    /// finished.
+    DocSearchAllComplete {
+        text: String,
+        results: Vec<SearchResult>,
+    },
+
     /// Emitted when the search string in the documents view is updated.
     DocSearchUpdated,
 
@@ -122,6 +163,14 @@ pub enum Event {
     /// Emitted when the user starts editing an existing collection
     EditConnectionStarted(Connection),
 
+    /// Emitted when the user has applied the changes shown in the edit review
+    /// modal.
+    EditReviewApplied,
+
+    /// Emitted when the user has discarded the changes shown in the edit
+    /// review modal.
+    EditReviewDiscarded,
+
     /// Emitted when an error occurs. The attached string should be a human-
     /// readable description of the error.
     ErrorOccurred(Error),
@@ -129,9 +178,17 @@ pub enum Event {
     /// Emitted when the currently-focused panel has changed.
     FocusedChanged,
 
+    /// Emitted when the paths of the fields in a sample of the selected
+    /// collection's documents have been found.
+    FieldPathsSampled(Vec<String>),
+
     /// Emitted when help modal has been opened or closed.
     HelpModalToggled,
 
+    /// Emitted when the names of the selected collection's indexes have been
+    /// listed.
+    IndexesListed(Vec<String>),
+
     /// Emitted when the user closes the input modal without confirming.
     InputCanceled,
 
@@ -147,6 +204,14 @@ pub enum Event {
     /// Emitted when the query input is expanded or collapsed
     QueryInputExpanded,
 
+    /// Emitted when the client connects, with whether the connection blocks
+    /// writes.
+    ReadOnlyModeChanged(bool),
+
+    /// Emitted when the document that a followed reference points to has been
+    /// found.
+    ReferenceResolved(Box<ReferenceTarget>),
+
     /// Emitted when the app has gone to and returned from an "alternate
     /// screen", such as opening the user's editor to edit a document.
     ReturnedFromAltScreen,
@@ -154,6 +219,25 @@ pub enum Event {
     /// Emitted when the terminal window changes size.
     ScreenResized,
 
+    /// Emitted when the user picks one of the results of a search across every
+    /// page.
+    SearchResultOpened(SearchResult),
+
+    /// Emitted when the user closes the list of results of a search across
+    /// every page.
+    SearchResultsClosed,
+
+    /// Emitted when connecting needs the user to type in a password or
+    /// passphrase first.
+    SecretRequested(InputKind),
+
+    /// Emitted when the server's version and topology have been loaded.
+    ServerInfoLoaded(ServerInfo),
+
+    /// Emitted when a ping to the server has finished, with its round-trip
+    /// time, or `None` if it failed or timed out.
+    ServerPinged(Option<Duration>),
+
     /// Emitted when a message that was being shown in the status bar has
     /// expired and the status bar has returned to its normal state.
     StatusMessageCleared,
@@ -167,6 +251,14 @@ pub enum Event {
     /// Emitted when a new tab has been created.
     TabCreated,
 
+    /// Emitted when a write from the audit log has been successfully reverted
+    /// on the Mongo server.
+    WriteReverted(WriteKind),
+
+    /// Emitted when a document write has been successfully reverted on the
+    /// Mongo server.
+    WriteUndone(WriteKind),
+
     /// Emitted every event loop iteration to give components (eg. client) an
     /// opportunity to check for and process async process results.
     Tick,
@@ -1,15 +1,28 @@
 use crate::{
+    client::audit_log::AuditEntry,
     components::{
-        confirm_modal::ConfirmKind, input::input_modal::InputKind, primary_screen::PrimScrFocus,
+        confirm_modal::ConfirmKind,
+        input::{conn_details_input::ConnDetail, conn_tls_input::TlsField, input_modal::InputKind},
+        primary_screen::PrimScrFocus,
+        query_input::QueryUpdate,
+    },
+    model::{
+        collection::Collection,
+        connection::Connection,
+        database::Database,
+        launch::LaunchTarget,
+        reference::{Reference, ReferenceTarget},
     },
-    model::{collection::Collection, connection::Connection, database::Database},
 };
-use mongodb::bson::Document;
+use mongodb::bson::{Bson, Document};
 
 use super::command::Command;
 
 #[derive(Debug, Clone, strum_macros::Display)]
 pub enum AppAction {
+    /// Tells `App` to stop showing the document comparison modal.
+    CloseCompareModal,
+
     /// Tells `App` to stop showing the help modal.
     CloseHelpModal,
 
@@ -23,17 +36,38 @@ pub enum AppAction {
     /// Tells `App` to sop recording user input as raw keystrokes and resume
     /// mapping input keys to commands.
     ExitRawMode,
+
+    /// Tells `App` to remember a document so it can be compared with the next
+    /// one that gets marked.
+    MarkDocForComparison(Document),
 }
 
 #[derive(Debug, Clone, strum_macros::Display)]
+#[expect(clippy::enum_variant_names)]
 pub enum TabAction {
     /// Tells the currently-visible `Tab` to show a modal asking the user to
     /// confirm an action of a given kind.
     RequestConfirmation(ConfirmKind),
 
+    /// Tells the currently-visible `Tab` to show a modal listing the
+    /// differences between a document and an edited version of it, so the
+    /// user can review the changes before they are saved.
+    RequestEditReview {
+        original: Document,
+        edited: Document,
+    },
+
     /// Tells the currently-visible `Tab` to show a modal prompting the user for
     /// input for a given purpose.
     RequestInput(InputKind),
+
+    /// Like `RequestInput`, but with the input already containing the given
+    /// value.
+    RequestInputWithValue(InputKind, String),
+
+    /// Tells the currently-visible `Tab` to show a modal listing the writes
+    /// in the audit log.
+    RequestAuditLog,
 }
 
 #[derive(Debug, Clone, strum_macros::Display)]
@@ -52,6 +86,13 @@ pub enum ClientAction {
     /// Tells `Client` to drop the given database.
     DropDatabase(Database),
 
+    /// Tells `Client` to find the document that a reference points to.
+    FollowReference(Reference),
+
+    /// Tells `Client` to parse a connection string in the background, for a
+    /// preview of its options.
+    InspectConnStr(String),
+
     /// Tells `Client` to insert the given document into the currently-selected
     /// collection.
     InsertDoc(Document),
@@ -59,9 +100,47 @@ pub enum ClientAction {
     /// Tells `Client` to refresh the current queries (document and count).
     RefreshQueries,
 
-    /// Tells `Client` to update the given document in the currently-selected
-    /// collection.
-    UpdateDoc(Document),
+    /// Tells `Client` to remove the element at an index of the array at a
+    /// (dotted) path, in the document with the given `_id`.
+    RemoveElement { id: Bson, path: String, idx: usize },
+
+    /// Tells `Client` to revert a write from the audit log, after asking the
+    /// user to confirm.
+    RevertWrite(Box<AuditEntry>),
+
+    /// Tells `Client` to search every document that matches the current query
+    /// for the given text.
+    SearchAll(String),
+
+    /// Tells `Client` to ping the server of a connection that's being edited,
+    /// without connecting to it.
+    TestConnection(Connection),
+
+    /// Tells `Client` to undo its most recent document write, after asking the
+    /// user to confirm.
+    UndoLastWrite,
+
+    /// Tells `Client` to apply an update (like `$set` or `$unset`) to the
+    /// document with the given `_id` in the currently-selected collection.
+    UpdateFields { id: Bson, update: Document },
+}
+
+impl ClientAction {
+    /// Whether the action writes to the database, and so is blocked on
+    /// read-only connections.
+    pub const fn is_write(&self) -> bool {
+        matches!(
+            self,
+            Self::DeleteDoc(..)
+                | Self::DropCollection(..)
+                | Self::DropDatabase(..)
+                | Self::InsertDoc(..)
+                | Self::RemoveElement { .. }
+                | Self::RevertWrite(..)
+                | Self::UndoLastWrite
+                | Self::UpdateFields { .. }
+        )
+    }
 }
 
 #[derive(Debug, Clone, strum_macros::Display)]
@@ -72,15 +151,26 @@ pub enum ConnScreenAction {
     /// Tells `ConnectionScreen` to stop editing a connection and save it.
     FinishEditingConn,
 
+    /// Tells `ConnectionScreen` to focus the input field for a connection's
+    /// folder, tags, or environment.
+    FocusConnDetailInput(ConnDetail),
+
     /// Tells `ConnectionScreen` to focus the connection name input field.
     FocusConnNameInput,
 
     /// Tells `ConnectionScreen` to focus the connection string input field.
     FocusConnStrInput,
 
+    /// Tells `ConnectionScreen` to focus the SSH tunnel input field.
+    FocusConnSshInput,
+
+    /// Tells `ConnectionScreen` to focus the input field for one of a
+    /// connection's TLS settings.
+    FocusConnTlsInput(TlsField),
+
     /// Tells `ConnectionScreen` to start editing the currently-selected
     /// connection.
-    StartEditingConn(Connection),
+    StartEditingConn(Box<Connection>),
 
     /// Tells `ConnectionScreen` to start editing a new connection.
     StartNewConn,
@@ -90,6 +180,18 @@ pub enum ConnScreenAction {
 pub enum PrimScreenAction {
     /// Tells `PrimaryScreenAction` to focus a specific component
     SetFocus(PrimScrFocus),
+
+    /// Tells `PrimaryScreen` to show the document that a followed reference
+    /// points to.
+    ShowReferencedDoc(Box<ReferenceTarget>),
+
+    /// Tells `PrimaryScreen` to open the database, collection, and query that
+    /// the app was launched with.
+    OpenTarget(Box<LaunchTarget>),
+
+    /// Tells `PrimaryScreen` to have its `QueryInput` add a value or a field
+    /// to the current query.
+    UpdateQuery(QueryUpdate),
 }
 
 #[derive(Debug, Clone, strum_macros::Display)]
@@ -66,6 +66,12 @@ impl<C: Component> ComponentTestHarness<C> {
         self.process_signals(queue);
     }
 
+    pub fn given_message(&mut self, message: Message) {
+        let mut queue = SignalQueue::default();
+        queue.push(message);
+        self.process_signals(queue);
+    }
+
     fn process_signals(&mut self, mut queue: SignalQueue) {
         while let Some(signal) = queue.pop() {
             let mut new_queue = SignalQueue::default();
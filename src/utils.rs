@@ -1,6 +1,10 @@
 pub mod clipboard;
+pub mod completion;
+pub mod doc_diff;
 pub mod doc_searcher;
 pub mod edit_doc;
+pub mod field_edit;
 pub mod json_labeler;
 pub mod mongo_tree;
+pub mod secrets;
 pub mod storage;
@@ -0,0 +1,353 @@
+use nucleo::{
+    pattern::{Atom, AtomKind, CaseMatching, Normalization},
+    Matcher, Utf32Str,
+};
+use std::collections::BTreeSet;
+
+/// The most suggestions that are offered at once.
+const MAX_SUGGESTIONS: usize = 50;
+
+/// Operators that can be used in a filter, either at the top level or on a
+/// field.
+pub const FILTER_OPERATORS: &[&str] = &[
+    "$all",
+    "$and",
+    "$bitsAllClear",
+    "$bitsAllSet",
+    "$bitsAnyClear",
+    "$bitsAnySet",
+    "$comment",
+    "$elemMatch",
+    "$eq",
+    "$exists",
+    "$expr",
+    "$geoIntersects",
+    "$geoWithin",
+    "$gt",
+    "$gte",
+    "$in",
+    "$jsonSchema",
+    "$lt",
+    "$lte",
+    "$mod",
+    "$ne",
+    "$near",
+    "$nearSphere",
+    "$nin",
+    "$nor",
+    "$not",
+    "$options",
+    "$or",
+    "$regex",
+    "$size",
+    "$text",
+    "$type",
+    "$where",
+];
+
+/// Operators that can be used in a projection.
+pub const PROJECTION_OPERATORS: &[&str] = &["$elemMatch", "$meta", "$slice"];
+
+/// The values that include or exclude a field in a projection.
+pub const PROJECTION_VALUES: &[&str] = &["1", "0", "true", "false"];
+
+/// The values that sort by a field in ascending or descending order.
+pub const SORT_VALUES: &[&str] = &["1", "-1"];
+
+/// The options of a query besides its filter, projection, and sort, along
+/// with the fields of a collation.
+pub const QUERY_OPTIONS: &[&str] = &[
+    "hint",
+    "collation",
+    "maxTimeMS",
+    "allowDiskUse",
+    "comment",
+    "limit",
+    "locale",
+    "strength",
+    "caseLevel",
+    "caseFirst",
+    "numericOrdering",
+    "alternate",
+    "maxVariable",
+    "normalization",
+    "backwards",
+];
+
+/// The values of the on/off query options.
+pub const QUERY_OPTION_VALUES: &[&str] = &["true", "false"];
+
+/// Suggestions for completing the word before the cursor in a query input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completions {
+    /// Where the word being completed starts, as a character index.
+    pub start: usize,
+
+    /// Whether the word is inside quotes.
+    pub quoted: bool,
+
+    /// Whether the word is a key, rather than a value.
+    pub is_key: bool,
+
+    /// The best suggestions first.
+    pub suggestions: Vec<String>,
+}
+
+impl Completions {
+    /// Replaces the word being completed with a suggestion, returning the new
+    /// text and cursor position. Keys that can't be written without quotes
+    /// (like dotted paths) are quoted.
+    #[must_use]
+    pub fn apply(&self, text: &str, cursor: usize, suggestion: &str) -> (String, usize) {
+        let needs_quotes = !self.quoted
+            && if self.is_key {
+                !suggestion
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || "_$".contains(c))
+            } else {
+                !is_literal(suggestion)
+            };
+        let replacement = if needs_quotes {
+            format!("\"{suggestion}\"")
+        } else {
+            suggestion.to_string()
+        };
+
+        let chars: Vec<_> = text.chars().collect();
+        let before: String = chars[..self.start].iter().collect();
+        let after: String = chars[cursor..].iter().collect();
+        let cursor = self.start + replacement.chars().count();
+
+        (format!("{before}{replacement}{after}"), cursor)
+    }
+}
+
+/// Suggests field paths, operators, and values while typing a filter,
+/// projection, sort, or query options.
+#[derive(Debug, Default, Clone)]
+pub struct Completer {
+    fields: BTreeSet<String>,
+    operators: &'static [&'static str],
+    values: &'static [&'static str],
+
+    /// Strings that are suggested as values, like the names of indexes.
+    strings: BTreeSet<String>,
+}
+
+impl Completer {
+    /// Creates a completer that suggests the given operators in key positions
+    /// and the given values in value positions, along with field paths.
+    #[must_use]
+    pub const fn new(operators: &'static [&'static str], values: &'static [&'static str]) -> Self {
+        Self {
+            fields: BTreeSet::new(),
+            operators,
+            values,
+            strings: BTreeSet::new(),
+        }
+    }
+
+    pub fn add_fields(&mut self, fields: impl IntoIterator<Item = String>) {
+        self.fields.extend(fields);
+    }
+
+    pub fn clear_fields(&mut self) {
+        self.fields.clear();
+    }
+
+    /// Replaces the strings that are suggested as values.
+    pub fn set_strings(&mut self, strings: impl IntoIterator<Item = String>) {
+        self.strings = strings.into_iter().collect();
+    }
+
+    /// Finds suggestions for the word before the cursor, which is a character
+    /// index into the text.
+    #[must_use]
+    pub fn complete(&self, text: &str, cursor: usize) -> Option<Completions> {
+        let before: Vec<_> = text.chars().take(cursor).collect();
+
+        let word_len = before
+            .iter()
+            .rev()
+            .take_while(|c| c.is_alphanumeric() || "_.$-".contains(**c))
+            .count();
+        let start = before.len() - word_len;
+        let word: String = before[start..].iter().collect();
+
+        let mut rest = before[..start]
+            .iter()
+            .rev()
+            .skip_while(|c| c.is_whitespace());
+        let mut context = rest.next()?;
+        let quoted = matches!(context, '"' | '\'');
+        if quoted {
+            context = rest.find(|c| !c.is_whitespace())?;
+        }
+
+        let is_key = match context {
+            '{' | ',' => true,
+            ':' | '[' => false,
+            _ => return None,
+        };
+        let candidates: Vec<&str> = if is_key {
+            self.fields
+                .iter()
+                .map(String::as_str)
+                .chain(self.operators.iter().copied())
+                .collect()
+        } else {
+            // values are short, so only ones that start with the word are
+            // useful (e.g. `1` shouldn't suggest `-1`). They can't be quoted,
+            // unlike strings
+            self.values
+                .iter()
+                .copied()
+                .filter(|value| !quoted && value.starts_with(&word))
+                .chain(self.strings.iter().map(String::as_str))
+                .collect()
+        };
+
+        let suggestions = fuzzy_match(&word, &candidates);
+        if suggestions.is_empty() {
+            return None;
+        }
+
+        Some(Completions {
+            start,
+            quoted,
+            is_key,
+            suggestions,
+        })
+    }
+}
+
+/// Whether a value can be written without quotes, like a number or `true`.
+fn is_literal(value: &str) -> bool {
+    matches!(value, "true" | "false" | "null") || value.parse::<f64>().is_ok()
+}
+
+/// Finds the candidates that fuzzily match a word, best first. Candidates that
+/// are the same as the word are left out, since there's nothing to complete.
+fn fuzzy_match(word: &str, candidates: &[&str]) -> Vec<String> {
+    let atom = Atom::new(
+        word,
+        CaseMatching::Smart,
+        Normalization::Smart,
+        AtomKind::Fuzzy,
+        false,
+    );
+    let mut matcher = Matcher::new(nucleo::Config::DEFAULT.match_paths());
+    let mut buf = vec![];
+
+    let mut scored: Vec<_> = candidates
+        .iter()
+        .filter(|candidate| **candidate != word)
+        .filter_map(|candidate| {
+            let score = atom.score(Utf32Str::new(candidate, &mut buf), &mut matcher)?;
+            Some((score, (*candidate).to_string()))
+        })
+        .collect();
+
+    // the sort is stable, so candidates with the same score stay in order
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn completer() -> Completer {
+        let mut completer = Completer::new(FILTER_OPERATORS, &[]);
+        completer.add_fields(["name".to_string(), "address.city".to_string()]);
+        completer
+    }
+
+    #[test]
+    fn suggest_fields_for_keys() {
+        let text = "{ adcit";
+        let completions = completer()
+            .complete(text, text.len())
+            .expect("should have suggestions");
+
+        assert_eq!(completions.suggestions, vec!["address.city"]);
+        assert_eq!(
+            completions.apply(text, text.len(), "address.city"),
+            ("{ \"address.city\"".to_string(), 16)
+        );
+    }
+
+    #[test]
+    fn suggest_operators_in_sub_documents() {
+        let text = "{ name: { $elem }";
+        let completions = completer()
+            .complete(text, 15)
+            .expect("should have suggestions");
+
+        assert_eq!(completions.suggestions[0], "$elemMatch");
+        assert_eq!(
+            completions.apply(text, 15, "$elemMatch"),
+            ("{ name: { $elemMatch }".to_string(), 20)
+        );
+    }
+
+    #[test]
+    fn quoted_keys_stay_quoted() {
+        let text = "{ 'addr";
+        let completions = completer()
+            .complete(text, text.len())
+            .expect("should have suggestions");
+
+        assert_eq!(
+            completions.apply(text, text.len(), "address.city"),
+            ("{ 'address.city".to_string(), 15)
+        );
+    }
+
+    #[test]
+    fn suggest_only_valid_sort_values() {
+        let completer = Completer::new(&[], SORT_VALUES);
+
+        let completions = completer
+            .complete("{ a: ", 5)
+            .expect("should have suggestions");
+        assert_eq!(completions.suggestions, vec!["1", "-1"]);
+
+        assert_eq!(completer.complete("{ a: 1", 6), None);
+    }
+
+    #[test]
+    fn suggest_index_names_as_strings() {
+        let mut completer = Completer::new(QUERY_OPTIONS, QUERY_OPTION_VALUES);
+        completer.set_strings(["email_1".to_string(), "_id_".to_string()]);
+
+        let text = "{ hint: ema";
+        let completions = completer
+            .complete(text, text.len())
+            .expect("should have suggestions");
+        assert_eq!(completions.suggestions, vec!["email_1"]);
+        assert_eq!(
+            completions.apply(text, text.len(), "email_1"),
+            ("{ hint: \"email_1\"".to_string(), 17)
+        );
+
+        let text = "{ hint: \"ema";
+        let completions = completer
+            .complete(text, text.len())
+            .expect("should have suggestions");
+        assert_eq!(
+            completions.apply(text, text.len(), "email_1"),
+            ("{ hint: \"email_1".to_string(), 16)
+        );
+
+        let text = "{ allowDiskUse: t";
+        let completions = completer
+            .complete(text, text.len())
+            .expect("should have suggestions");
+        assert_eq!(completions.suggestions, vec!["true"]);
+    }
+}
@@ -0,0 +1,412 @@
+use mongodb::bson::{Bson, Document};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A single difference between two documents, identified by the dotted path
+/// to the field that changed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub path: String,
+    pub old: Option<Bson>,
+    pub new: Option<Bson>,
+}
+
+impl FieldChange {
+    #[must_use]
+    pub const fn kind(&self) -> FieldChangeKind {
+        match (&self.old, &self.new) {
+            (None, _) => FieldChangeKind::Added,
+            (_, None) => FieldChangeKind::Removed,
+            _ => FieldChangeKind::Changed,
+        }
+    }
+}
+
+/// Computes the field-level differences between two documents. Nested
+/// documents and arrays are compared element by element, so only the leaves
+/// that actually differ are reported.
+#[must_use]
+pub fn diff_docs(old: &Document, new: &Document) -> Vec<FieldChange> {
+    let mut changes = vec![];
+    diff_doc_fields(None, old, new, &mut changes);
+    changes
+}
+
+/// Builds a `$set`/`$unset` update that makes the changes to the document on
+/// the server, leaving alone any fields that the edited copy doesn't have
+/// because of a projection.
+///
+/// Elements can't be added to or removed from an array by path, so an array
+/// that changed length is set to its edited value as a whole.
+#[must_use]
+pub fn update_for_changes(edited: &Document, changes: &[FieldChange]) -> Document {
+    let resized_arrays: Vec<&str> = changes
+        .iter()
+        .filter(|change| change.kind() != FieldChangeKind::Changed)
+        .filter_map(|change| change.path.rsplit_once('.').map(|(parent, _)| parent))
+        .filter(|parent| matches!(value_at_path(edited, parent), Some(Bson::Array(_))))
+        .collect();
+    let is_in_resized_array = |path: &str| {
+        resized_arrays
+            .iter()
+            .any(|array| path.starts_with(&format!("{array}.")))
+    };
+
+    let mut set = Document::new();
+    let mut unset = Document::new();
+    for array in &resized_arrays {
+        if !is_in_resized_array(array) {
+            if let Some(value) = value_at_path(edited, array) {
+                set.insert(*array, value.clone());
+            }
+        }
+    }
+    for change in changes {
+        if is_in_resized_array(&change.path) {
+            continue;
+        }
+        if let Some(value) = &change.new {
+            set.insert(change.path.clone(), value.clone());
+        } else {
+            unset.insert(change.path.clone(), "");
+        }
+    }
+
+    let mut update = Document::new();
+    if !set.is_empty() {
+        update.insert("$set", set);
+    }
+    if !unset.is_empty() {
+        update.insert("$unset", unset);
+    }
+    update
+}
+
+/// The value at a dotted path, where the parts of the path are field names or
+/// array indexes.
+fn value_at_path<'a>(doc: &'a Document, path: &str) -> Option<&'a Bson> {
+    let mut parts = path.split('.');
+    let mut value = doc.get(parts.next()?)?;
+    for part in parts {
+        value = match value {
+            Bson::Document(doc) => doc.get(part)?,
+            Bson::Array(array) => array.get(part.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+fn join_path(prefix: Option<&str>, key: &str) -> String {
+    prefix.map_or_else(|| key.to_string(), |prefix| format!("{prefix}.{key}"))
+}
+
+fn diff_doc_fields(
+    prefix: Option<&str>,
+    old: &Document,
+    new: &Document,
+    changes: &mut Vec<FieldChange>,
+) {
+    for (key, old_value) in old {
+        let path = join_path(prefix, key);
+        match new.get(key) {
+            Some(new_value) => diff_values(path, old_value, new_value, changes),
+            None => changes.push(FieldChange {
+                path,
+                old: Some(old_value.clone()),
+                new: None,
+            }),
+        }
+    }
+
+    for (key, new_value) in new {
+        if !old.contains_key(key) {
+            changes.push(FieldChange {
+                path: join_path(prefix, key),
+                old: None,
+                new: Some(new_value.clone()),
+            });
+        }
+    }
+}
+
+fn diff_values(path: String, old: &Bson, new: &Bson, changes: &mut Vec<FieldChange>) {
+    match (old, new) {
+        (Bson::Document(old), Bson::Document(new)) => {
+            diff_doc_fields(Some(&path), old, new, changes);
+        }
+        (Bson::Array(old), Bson::Array(new)) => {
+            for idx in 0..old.len().max(new.len()) {
+                let elem_path = join_path(Some(&path), &idx.to_string());
+                match (old.get(idx), new.get(idx)) {
+                    (Some(old), Some(new)) => diff_values(elem_path, old, new, changes),
+                    (old, new) => changes.push(FieldChange {
+                        path: elem_path,
+                        old: old.cloned(),
+                        new: new.cloned(),
+                    }),
+                }
+            }
+        }
+        (old, new) if old != new => changes.push(FieldChange {
+            path,
+            old: Some(old.clone()),
+            new: Some(new.clone()),
+        }),
+        _ => {}
+    }
+}
+
+/// A field that appears in at least one of two documents being compared.
+///
+/// Nested documents and arrays get a row of their own, followed by rows for
+/// their contents.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlignedField {
+    pub depth: usize,
+    pub key: String,
+    pub left: Option<Bson>,
+    pub right: Option<Bson>,
+}
+
+impl AlignedField {
+    /// How the field differs between the two documents, if it does.
+    #[must_use]
+    pub fn change_kind(&self) -> Option<FieldChangeKind> {
+        match (&self.left, &self.right) {
+            (None, _) => Some(FieldChangeKind::Added),
+            (_, None) => Some(FieldChangeKind::Removed),
+            (Some(left), Some(right)) if left != right => Some(FieldChangeKind::Changed),
+            _ => None,
+        }
+    }
+}
+
+/// Lines up the fields of two documents by key path.
+///
+/// Fields are listed in the order they appear in the left document, followed
+/// by any fields that only the right one has.
+#[must_use]
+pub fn align_docs(left: &Document, right: &Document) -> Vec<AlignedField> {
+    let mut fields = vec![];
+    align_doc_fields(0, Some(left), Some(right), &mut fields);
+    fields
+}
+
+fn align_doc_fields(
+    depth: usize,
+    left: Option<&Document>,
+    right: Option<&Document>,
+    fields: &mut Vec<AlignedField>,
+) {
+    let left_keys = left.into_iter().flat_map(Document::keys);
+    let right_only_keys = right
+        .into_iter()
+        .flat_map(Document::keys)
+        .filter(|key| !left.is_some_and(|left| left.contains_key(key)));
+
+    for key in left_keys.chain(right_only_keys) {
+        let left = left.and_then(|doc| doc.get(key));
+        let right = right.and_then(|doc| doc.get(key));
+        align_values(depth, key.clone(), left, right, fields);
+    }
+}
+
+fn align_values(
+    depth: usize,
+    key: String,
+    left: Option<&Bson>,
+    right: Option<&Bson>,
+    fields: &mut Vec<AlignedField>,
+) {
+    fields.push(AlignedField {
+        depth,
+        key,
+        left: left.cloned(),
+        right: right.cloned(),
+    });
+
+    match (left, right) {
+        (Some(Bson::Document(left)), Some(Bson::Document(right))) => {
+            align_doc_fields(depth + 1, Some(left), Some(right), fields);
+        }
+        (Some(Bson::Document(doc)), None) => align_doc_fields(depth + 1, Some(doc), None, fields),
+        (None, Some(Bson::Document(doc))) => align_doc_fields(depth + 1, None, Some(doc), fields),
+        (Some(Bson::Array(left)), Some(Bson::Array(right))) => {
+            for idx in 0..left.len().max(right.len()) {
+                align_values(depth + 1, idx.to_string(), left.get(idx), right.get(idx), fields);
+            }
+        }
+        (Some(Bson::Array(arr)), None) => {
+            for (idx, item) in arr.iter().enumerate() {
+                align_values(depth + 1, idx.to_string(), Some(item), None, fields);
+            }
+        }
+        (None, Some(Bson::Array(arr))) => {
+            for (idx, item) in arr.iter().enumerate() {
+                align_values(depth + 1, idx.to_string(), None, Some(item), fields);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The name of a value's BSON type, as shown to users.
+#[must_use]
+pub const fn bson_type_name(bson: &Bson) -> &'static str {
+    match bson {
+        Bson::Double(_) => "Double",
+        Bson::String(_) => "String",
+        Bson::Array(_) => "Array",
+        Bson::Document(_) => "Object",
+        Bson::Boolean(_) => "Boolean",
+        Bson::Null => "Null",
+        Bson::RegularExpression(_) => "Regex",
+        Bson::JavaScriptCode(_) => "JavaScript",
+        Bson::JavaScriptCodeWithScope(_) => "JavaScriptWithScope",
+        Bson::Int32(_) => "Int32",
+        Bson::Int64(_) => "Int64",
+        Bson::Timestamp(_) => "Timestamp",
+        Bson::Binary(_) => "Binary",
+        Bson::ObjectId(_) => "ObjectId",
+        Bson::DateTime(_) => "Date",
+        Bson::Symbol(_) => "Symbol",
+        Bson::Decimal128(_) => "Decimal128",
+        Bson::Undefined => "Undefined",
+        Bson::MaxKey => "MaxKey",
+        Bson::MinKey => "MinKey",
+        Bson::DbPointer(_) => "DBPointer",
+    }
+}
+
+/// A short, single-line rendering of a value, used when listing changes.
+#[must_use]
+pub fn bson_preview(bson: &Bson) -> String {
+    match bson {
+        Bson::ObjectId(id) => format!("ObjectId({id})"),
+        Bson::String(s) => format!("\"{s}\""),
+        Bson::DateTime(d) => d.to_string(),
+        Bson::Array(_) | Bson::Document(_) => bson.clone().into_relaxed_extjson().to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::bson::doc;
+
+    #[test]
+    fn identical_docs_have_no_changes() {
+        let doc = doc! { "_id": 1, "name": "tongo", "tags": ["a", "b"] };
+        assert!(diff_docs(&doc, &doc).is_empty());
+    }
+
+    #[test]
+    fn top_level_changes() {
+        let old = doc! { "_id": 1, "name": "tongo", "stars": 3 };
+        let new = doc! { "_id": 1, "name": "mongo", "forks": 2 };
+
+        let changes = diff_docs(&old, &new);
+
+        assert_eq!(
+            changes,
+            vec![
+                FieldChange {
+                    path: "name".to_string(),
+                    old: Some(Bson::String("tongo".to_string())),
+                    new: Some(Bson::String("mongo".to_string())),
+                },
+                FieldChange {
+                    path: "stars".to_string(),
+                    old: Some(Bson::Int32(3)),
+                    new: None,
+                },
+                FieldChange {
+                    path: "forks".to_string(),
+                    old: None,
+                    new: Some(Bson::Int32(2)),
+                },
+            ]
+        );
+        assert_eq!(changes[0].kind(), FieldChangeKind::Changed);
+        assert_eq!(changes[1].kind(), FieldChangeKind::Removed);
+        assert_eq!(changes[2].kind(), FieldChangeKind::Added);
+    }
+
+    #[test]
+    fn nested_changes_use_dotted_paths() {
+        let old = doc! { "a": { "b": 1, "c": [1, 2] } };
+        let new = doc! { "a": { "b": 1, "c": [1, 3, 4] } };
+
+        let paths: Vec<_> = diff_docs(&old, &new)
+            .into_iter()
+            .map(|change| (change.kind(), change.path))
+            .collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                (FieldChangeKind::Changed, "a.c.1".to_string()),
+                (FieldChangeKind::Added, "a.c.2".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn type_changes_are_reported() {
+        let old = doc! { "n": 1 };
+        let new = doc! { "n": 1_i64 };
+
+        let changes = diff_docs(&old, &new);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].old.as_ref().map(bson_type_name), Some("Int32"));
+        assert_eq!(changes[0].new.as_ref().map(bson_type_name), Some("Int64"));
+    }
+
+    #[test]
+    fn aligned_fields_follow_both_docs() {
+        let left = doc! { "a": 1, "b": { "c": true }, "d": [1] };
+        let right = doc! { "a": 2, "d": [1, 2], "e": "new" };
+
+        let rows: Vec<_> = align_docs(&left, &right)
+            .into_iter()
+            .map(|field| (field.depth, field.key.clone(), field.change_kind()))
+            .collect();
+
+        assert_eq!(
+            rows,
+            vec![
+                (0, "a".to_string(), Some(FieldChangeKind::Changed)),
+                (0, "b".to_string(), Some(FieldChangeKind::Removed)),
+                (1, "c".to_string(), Some(FieldChangeKind::Removed)),
+                (0, "d".to_string(), Some(FieldChangeKind::Changed)),
+                (1, "0".to_string(), None),
+                (1, "1".to_string(), Some(FieldChangeKind::Added)),
+                (0, "e".to_string(), Some(FieldChangeKind::Added)),
+            ]
+        );
+    }
+
+    #[test]
+    fn update_only_changed_fields() {
+        let old =
+            doc! { "_id": 1, "a": 1, "b": { "c": 2, "d": 3 }, "tags": ["x", "y"], "nums": [1, 2] };
+        let new = doc! { "_id": 1, "a": 5, "b": { "c": 2 }, "tags": ["x"], "nums": [1, 3] };
+
+        let update = update_for_changes(&new, &diff_docs(&old, &new));
+        assert_eq!(
+            update,
+            doc! {
+                "$set": { "tags": ["x"], "a": 5, "nums.1": 3 },
+                "$unset": { "b.d": "" },
+            }
+        );
+    }
+}
@@ -1,148 +1,382 @@
-use super::mongo_tree::MongoKey;
+use super::mongo_tree::{value_to_string, Highlight, Highlights, MongoKey};
 use itertools::Itertools;
 use mongodb::bson::Bson;
-use nucleo::Nucleo;
-use std::sync::Arc;
+use nucleo::{
+    pattern::{CaseMatching, Normalization, Pattern},
+    Matcher, Utf32Str,
+};
+use regex::RegexBuilder;
 
-struct SearchItem(Vec<MongoKey>, Bson);
+/// How the search text is compared with the keys and values of documents.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SearchMode {
+    #[default]
+    Fuzzy,
+    Substring,
+    Regex,
+    Exact,
+}
+
+impl SearchMode {
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Fuzzy => Self::Substring,
+            Self::Substring => Self::Regex,
+            Self::Regex => Self::Exact,
+            Self::Exact => Self::Fuzzy,
+        }
+    }
+}
+
+impl std::fmt::Display for SearchMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fuzzy => write!(f, "fuzzy"),
+            Self::Substring => write!(f, "substring"),
+            Self::Regex => write!(f, "regex"),
+            Self::Exact => write!(f, "exact"),
+        }
+    }
+}
+
+/// Which parts of the documents are searched.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum SearchScope {
+    #[default]
+    All,
+    Keys,
+    Values,
+
+    /// The values at or under a path, not including the document's id.
+    Path(Vec<MongoKey>),
+}
+
+impl std::fmt::Display for SearchScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::All => write!(f, "keys and values"),
+            Self::Keys => write!(f, "keys"),
+            Self::Values => write!(f, "values"),
+            Self::Path(path) => write!(f, "values in {}", mongo_key_path_to_str(path)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SearchItem {
+    /// The path to the item in the documents tree, starting with the id of
+    /// its document.
+    path: Vec<MongoKey>,
+
+    /// The item's value, if it isn't a document or an array.
+    value: Option<String>,
+}
+
+impl SearchItem {
+    fn key(&self) -> String {
+        self.path
+            .last()
+            .map(ToString::to_string)
+            .unwrap_or_default()
+    }
+}
 
 fn flatten_doc(doc: &Bson) -> Vec<SearchItem> {
     let doc = doc.as_document().expect("should only accept documents");
     let id = doc
         .get("_id")
         .expect("all mongo documents should have an '_id' field");
-    let id = MongoKey::from(id);
+    let path = vec![MongoKey::from(id)];
 
-    let mut flattened_docs: Vec<_> = doc
-        .clone()
-        .into_iter()
-        .flat_map(|(key, bson)| flatten_bson(key.into(), bson))
-        .map(|SearchItem(keys, bson)| prepend_key(id.clone(), keys, bson))
-        .collect();
-    flattened_docs.push(SearchItem(vec![id], Bson::String(String::default())));
+    let mut items = vec![SearchItem {
+        path: path.clone(),
+        value: None,
+    }];
+    for (key, bson) in doc {
+        flatten_bson(&path, key.clone().into(), bson, &mut items);
+    }
 
-    flattened_docs
+    items
 }
 
-fn flatten_bson(key: MongoKey, bson: Bson) -> Vec<SearchItem> {
+fn flatten_bson(parent: &[MongoKey], key: MongoKey, bson: &Bson, items: &mut Vec<SearchItem>) {
+    let mut path = parent.to_vec();
+    path.push(key);
+
     match bson {
         Bson::Document(doc) => {
-            let mut flattened_docs: Vec<_> = doc
-                .into_iter()
-                .flat_map(|(key, bson)| flatten_bson(key.into(), bson))
-                .map(|SearchItem(keys, bson)| prepend_key(key.clone(), keys, bson))
-                .collect();
-
-            // include just the key with an empty bson so that search can target the key too
-            flattened_docs.push(SearchItem(vec![key], Bson::String(String::default())));
-
-            flattened_docs
+            // include just the key so that search can target the key too
+            items.push(SearchItem {
+                path: path.clone(),
+                value: None,
+            });
+            for (key, bson) in doc {
+                flatten_bson(&path, key.clone().into(), bson, items);
+            }
         }
 
         Bson::Array(arr) => {
-            let mut flattened_docs: Vec<_> = arr
-                .into_iter()
-                .enumerate()
-                .flat_map(|(idx, bson)| flatten_bson(idx.into(), bson))
-                .map(|SearchItem(keys, bson)| prepend_key(key.clone(), keys, bson))
-                .collect();
-
-            // include just the key with an empty bson so that search can target the key too
-            flattened_docs.push(SearchItem(vec![key], Bson::String(String::default())));
-
-            flattened_docs
+            items.push(SearchItem {
+                path: path.clone(),
+                value: None,
+            });
+            for (idx, bson) in arr.iter().enumerate() {
+                flatten_bson(&path, idx.into(), bson, items);
+            }
         }
 
-        bson => vec![SearchItem(vec![key], bson)],
+        bson => items.push(SearchItem {
+            path,
+            value: Some(value_to_string(bson)),
+        }),
     }
 }
 
-fn prepend_key(key: MongoKey, keys: Vec<MongoKey>, bson: Bson) -> SearchItem {
-    let mut new_keys = vec![key];
-    new_keys.extend(keys);
-    SearchItem(new_keys, bson)
-}
-
 fn mongo_key_path_to_str(path: &[MongoKey]) -> String {
     path.iter().map(MongoKey::to_string).join(".")
 }
 
-pub struct DocSearcher {
-    nucleo: Nucleo<SearchItem>,
-    match_idx: u32,
+/// Compares search text with the keys and values of documents, finding which
+/// characters matched.
+struct TextMatcher {
+    mode: SearchMode,
+    text: String,
+    case_sensitive: bool,
+    pattern: Pattern,
+    matcher: Matcher,
+    regex: Option<regex::Regex>,
 }
 
-impl Default for DocSearcher {
-    fn default() -> Self {
+impl TextMatcher {
+    fn new(mode: SearchMode, text: &str) -> Self {
+        // use "smart case", like the fuzzy matcher does
+        let case_sensitive = text.chars().any(char::is_uppercase);
+        let regex = RegexBuilder::new(text)
+            .case_insensitive(!case_sensitive)
+            .build()
+            .ok()
+            .filter(|_| mode == SearchMode::Regex);
+
         Self {
-            nucleo: Nucleo::new(nucleo::Config::DEFAULT, Arc::new(|| {}), None, 1),
-            match_idx: 0,
+            mode,
+            text: text.to_string(),
+            case_sensitive,
+            pattern: Pattern::parse(text, CaseMatching::Smart, Normalization::Smart),
+            matcher: Matcher::new(nucleo::Config::DEFAULT),
+            regex,
         }
     }
-}
 
-impl std::fmt::Debug for DocSearcher {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "DocSearcher!")
+    /// Matches the search text against a haystack, returning a score and the
+    /// indices of the characters that matched.
+    fn find(&mut self, haystack: &str) -> Option<(u32, Vec<usize>)> {
+        match self.mode {
+            SearchMode::Fuzzy => {
+                let mut buf = vec![];
+                let mut indices = vec![];
+                let score = self.pattern.indices(
+                    Utf32Str::new(haystack, &mut buf),
+                    &mut self.matcher,
+                    &mut indices,
+                )?;
+                indices.sort_unstable();
+                indices.dedup();
+                Some((score, indices.into_iter().map(|idx| idx as usize).collect()))
+            }
+            SearchMode::Substring => {
+                let haystack = self.fold_case(haystack);
+                let needle = self.fold_case(&self.text);
+                let indices: Vec<_> = (0..=haystack.len().saturating_sub(needle.len()))
+                    .filter(|start| haystack[*start..].starts_with(&needle))
+                    .flat_map(|start| start..start + needle.len())
+                    .unique()
+                    .collect();
+                (!indices.is_empty()).then_some((0, indices))
+            }
+            SearchMode::Regex => {
+                let regex = self.regex.as_ref()?;
+                let indices: Vec<_> = regex
+                    .find_iter(haystack)
+                    .flat_map(|m| {
+                        let start = haystack[..m.start()].chars().count();
+                        start..start + m.as_str().chars().count()
+                    })
+                    .collect();
+                regex.is_match(haystack).then_some((0, indices))
+            }
+            SearchMode::Exact => {
+                let is_match = self.fold_case(haystack) == self.fold_case(&self.text);
+                is_match.then(|| (0, (0..haystack.chars().count()).collect()))
+            }
+        }
     }
+
+    fn fold_case(&self, text: &str) -> Vec<char> {
+        if self.case_sensitive {
+            text.chars().collect()
+        } else {
+            text.chars()
+                .map(|c| c.to_lowercase().next().unwrap_or(c))
+                .collect()
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct DocSearcher {
+    items: Vec<SearchItem>,
+    pattern: String,
+    mode: SearchMode,
+    scope: SearchScope,
+
+    /// The paths to the items that matched, best first, along with the
+    /// characters in each that matched.
+    matches: Vec<(Vec<MongoKey>, Highlight)>,
+    match_idx: usize,
 }
 
 impl DocSearcher {
     pub fn load_docs(&mut self, docs: &[Bson]) {
-        let docs = docs.to_vec();
-        self.nucleo.restart(true);
-        let injector = self.nucleo.injector();
-
-        let future = async move {
-            for doc in docs {
-                for item in flatten_doc(&doc) {
-                    injector.push(item, |item, cols| {
-                        cols[0] = format!("{}:{}", mongo_key_path_to_str(&item.0), item.1).into();
-                    });
+        self.items = docs.iter().flat_map(flatten_doc).collect();
+        self.find_matches();
+    }
+
+    pub fn update_pattern(&mut self, pat: &str) {
+        pat.clone_into(&mut self.pattern);
+        self.find_matches();
+    }
+
+    #[must_use]
+    pub const fn mode(&self) -> SearchMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: SearchMode) {
+        self.mode = mode;
+        self.find_matches();
+    }
+
+    #[must_use]
+    pub const fn scope(&self) -> &SearchScope {
+        &self.scope
+    }
+
+    pub fn set_scope(&mut self, scope: SearchScope) {
+        self.scope = scope;
+        self.find_matches();
+    }
+
+    fn find_matches(&mut self) {
+        self.match_idx = 0;
+        self.matches = vec![];
+        if self.pattern.is_empty() {
+            return;
+        }
+
+        let mut matcher = TextMatcher::new(self.mode, &self.pattern);
+        let mut scored: Vec<_> = self
+            .items
+            .iter()
+            .filter_map(|item| {
+                let (score, highlight) = self.match_item(&mut matcher, item)?;
+                Some((score, item.path.clone(), highlight))
+            })
+            .collect();
+
+        // the sort is stable, so items with the same score stay in order
+        scored.sort_by_key(|(score, ..)| std::cmp::Reverse(*score));
+        self.matches = scored
+            .into_iter()
+            .map(|(_, path, highlight)| (path, highlight))
+            .collect();
+    }
+
+    fn match_item(&self, matcher: &mut TextMatcher, item: &SearchItem) -> Option<(u32, Highlight)> {
+        match (&self.scope, matcher.mode) {
+            // fuzzy searches of keys use the whole path, so that eg. "addrcity"
+            // finds `address.city`
+            (SearchScope::All | SearchScope::Keys, SearchMode::Fuzzy) => {
+                let path = mongo_key_path_to_str(&item.path);
+                let value = item
+                    .value
+                    .as_ref()
+                    .filter(|_| self.scope == SearchScope::All);
+                let haystack =
+                    value.map_or_else(|| path.clone(), |value| format!("{path}:{value}"));
+                let (score, indices) = matcher.find(&haystack)?;
+
+                // split the indices between the last key and the value
+                let path_len = path.chars().count();
+                let key_start = path_len - item.key().chars().count();
+                let highlight = Highlight {
+                    key: indices
+                        .iter()
+                        .filter(|idx| (key_start..path_len).contains(idx))
+                        .map(|idx| idx - key_start)
+                        .collect(),
+                    value: indices
+                        .iter()
+                        .filter(|idx| **idx > path_len)
+                        .map(|idx| idx - path_len - 1)
+                        .collect(),
+                };
+                Some((score, highlight))
+            }
+            (SearchScope::All, _) => {
+                let key = matcher.find(&item.key());
+                let value = item.value.as_ref().and_then(|value| matcher.find(value));
+                if key.is_none() && value.is_none() {
+                    return None;
                 }
+                let highlight = Highlight {
+                    key: key.map(|(_, indices)| indices).unwrap_or_default(),
+                    value: value.map(|(_, indices)| indices).unwrap_or_default(),
+                };
+                Some((0, highlight))
+            }
+            (SearchScope::Keys, _) => {
+                let (score, key) = matcher.find(&item.key())?;
+                Some((score, Highlight { key, value: vec![] }))
+            }
+            (SearchScope::Values, _) => {
+                let (score, value) = matcher.find(item.value.as_ref()?)?;
+                Some((score, Highlight { key: vec![], value }))
+            }
+            (SearchScope::Path(path), _) => {
+                if !item.path.get(1..)?.starts_with(path) {
+                    return None;
+                }
+                let (score, value) = matcher.find(item.value.as_ref()?)?;
+                Some((score, Highlight { key: vec![], value }))
             }
-        };
-
-        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
-            runtime.spawn(future);
-        } else {
-            futures::executor::block_on(future);
         }
     }
-    pub fn update_pattern(&mut self, pat: &str) {
-        self.match_idx = 0;
-        self.nucleo.pattern.reparse(
-            0,
-            pat,
-            nucleo::pattern::CaseMatching::Smart,
-            nucleo::pattern::Normalization::Smart,
-            false,
-        );
 
-        self.nucleo.tick(10);
+    /// The characters that matched in each item that matched.
+    #[must_use]
+    pub fn highlights(&self) -> Highlights {
+        self.matches.iter().cloned().collect()
     }
 
     #[must_use]
-    pub const fn match_idx(&self) -> u32 {
+    pub const fn match_idx(&self) -> usize {
         self.match_idx
     }
 
     #[must_use]
-    pub fn num_matches(&self) -> u32 {
-        self.nucleo.snapshot().matched_item_count()
+    pub const fn num_matches(&self) -> usize {
+        self.matches.len()
     }
 
     #[must_use]
     pub fn current_match(&self) -> Option<&Vec<MongoKey>> {
         let idx = self.match_idx;
         tracing::debug!("current match idx: {idx}");
-        self.nucleo
-            .snapshot()
-            .get_matched_item(idx)
-            .map(|item| &item.data.0)
+        self.matches.get(idx).map(|(path, _)| path)
     }
 
-    pub fn next_match(&mut self) {
+    pub const fn next_match(&mut self) {
         let num_matches = self.num_matches();
         if num_matches <= 1 {
             return;
@@ -151,7 +385,7 @@ impl DocSearcher {
         self.match_idx = (self.match_idx + 1) % num_matches;
     }
 
-    pub fn prev_match(&mut self) {
+    pub const fn prev_match(&mut self) {
         let num_matches = self.num_matches();
         if num_matches <= 1 {
             return;
@@ -160,3 +394,97 @@ impl DocSearcher {
         self.match_idx = (self.match_idx + num_matches - 1) % num_matches;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::bson::bson;
+
+    fn searcher(mode: SearchMode, scope: SearchScope, pattern: &str) -> DocSearcher {
+        let mut searcher = DocSearcher::default();
+        searcher.load_docs(&[
+            bson!({ "_id": 1, "name": "Ann", "address": { "city": "Annapolis" } }),
+            bson!({ "_id": 2, "name": "Bob", "tags": ["banana"] }),
+        ]);
+        searcher.set_mode(mode);
+        searcher.set_scope(scope);
+        searcher.update_pattern(pattern);
+        searcher
+    }
+
+    fn key(s: &str) -> MongoKey {
+        MongoKey::String(s.to_string())
+    }
+
+    #[test]
+    fn substring_search_highlights_every_occurrence() {
+        let searcher = searcher(SearchMode::Substring, SearchScope::All, "an");
+
+        assert_eq!(
+            searcher
+                .highlights()
+                .get(&vec![MongoKey::Usize(2), key("tags"), 0.into()]),
+            Some(&Highlight {
+                key: vec![],
+                value: vec![1, 2, 3, 4],
+            })
+        );
+        assert_eq!(searcher.num_matches(), 3);
+    }
+
+    #[test]
+    fn exact_search_of_values() {
+        let searcher = searcher(SearchMode::Exact, SearchScope::Values, "ann");
+
+        assert_eq!(
+            searcher.current_match(),
+            Some(&vec![MongoKey::Usize(1), key("name")])
+        );
+        assert_eq!(searcher.num_matches(), 1);
+    }
+
+    #[test]
+    fn regex_search_of_keys() {
+        let searcher = searcher(SearchMode::Regex, SearchScope::Keys, "^(name|city)$");
+
+        assert_eq!(searcher.num_matches(), 3);
+        assert!(searcher
+            .highlights()
+            .values()
+            .all(|highlight| highlight.value.is_empty()));
+    }
+
+    #[test]
+    fn search_under_path() {
+        let searcher = searcher(
+            SearchMode::Substring,
+            SearchScope::Path(vec![key("address")]),
+            "ann",
+        );
+
+        assert_eq!(
+            searcher.current_match(),
+            Some(&vec![MongoKey::Usize(1), key("address"), key("city")])
+        );
+        assert_eq!(searcher.num_matches(), 1);
+    }
+
+    #[test]
+    fn fuzzy_search_highlights_key_and_value() {
+        let searcher = searcher(SearchMode::Fuzzy, SearchScope::All, "name:bob");
+
+        assert_eq!(
+            searcher.current_match(),
+            Some(&vec![MongoKey::Usize(2), key("name")])
+        );
+        assert_eq!(
+            searcher
+                .highlights()
+                .get(&vec![MongoKey::Usize(2), key("name")]),
+            Some(&Highlight {
+                key: vec![0, 1, 2, 3],
+                value: vec![0, 1, 2],
+            })
+        );
+    }
+}
@@ -3,7 +3,7 @@ use crossterm::{
     ExecutableCommand,
 };
 use edit::{edit_with_builder, Builder};
-use mongodb::bson::{from_document, to_document, Document};
+use mongodb::bson::{from_document, to_document, Bson, Document};
 use serde_json::{from_str, Value};
 use std::io::stdout;
 use thiserror::Error;
@@ -67,13 +67,83 @@ pub fn edit_doc(doc: Document) -> Result<Document, EditDocError> {
                 .map_err(|e| EditDocError::JsonToString(e.to_string()))
         })?;
 
+    let updated_string = edit_string(doc_string, ".json")?;
+
+    // convert back into a doc
+    let new_doc_json = from_str::<Value>(&updated_string)
+        .map_err(|e| EditDocError::StringToJson(e.to_string()))?;
+
+    to_document(&new_doc_json).map_err(|e| EditDocError::JsonToDoc(e.to_string()))
+}
+
+/// Edit a filter, projection, or sort in external editor, as JSON5.
+///
+/// If the edited text can't be parsed, the editor is reopened with the error
+/// in a comment at the top. Returns `None` if the text is left empty.
+///
+/// # Errors
+/// Emits a `EditDocError` if something goes wrong. See that struct for details
+/// on the possible cases.
+pub fn edit_query_doc(text: &str) -> Result<Option<Document>, EditDocError> {
+    // start from a pretty version of the text, as long as it's valid
+    let mut text = parse_query_doc(text)
+        .ok()
+        .and_then(|doc| {
+            serde_json::to_string_pretty(&Bson::Document(doc).into_relaxed_extjson()).ok()
+        })
+        .unwrap_or_else(|| text.to_string());
+
+    loop {
+        let edited = edit_string(text, ".json5")?;
+
+        // drop the error comment from the last attempt
+        let edited = edited
+            .lines()
+            .skip_while(|line| line.starts_with(ERROR_COMMENT_PREFIX))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        if edited.trim().is_empty() {
+            return Ok(None);
+        }
+
+        match parse_query_doc(&edited) {
+            Ok(doc) => return Ok(Some(doc)),
+            Err(err) => {
+                // parse errors can span several lines, but the comment can't
+                let err = err
+                    .to_string()
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                text = format!("{ERROR_COMMENT_PREFIX} {err}\n{edited}\n");
+            }
+        }
+    }
+}
+
+const ERROR_COMMENT_PREFIX: &str = "// Error:";
+
+/// Parses the text of a filter, projection, or sort, which may be any JSON5.
+///
+/// # Errors
+/// Emits a `EditDocError` if the text isn't valid JSON5 or isn't a document.
+pub fn parse_query_doc(text: &str) -> Result<Document, EditDocError> {
+    let json =
+        json5::from_str::<Value>(text).map_err(|e| EditDocError::StringToJson(e.to_string()))?;
+    to_document(&json).map_err(|e| EditDocError::JsonToDoc(e.to_string()))
+}
+
+/// Opens the string in the external editor in a file with the given suffix,
+/// and returns the edited string.
+fn edit_string(text: String, suffix: &str) -> Result<String, EditDocError> {
     // setup terminal for external editor
     stdout()
         .execute(LeaveAlternateScreen)
         .map_err(|e| EditDocError::TerminalCommand(e.to_string()))?;
 
     // call function from `edit` crate
-    let updated_string = edit_with_builder(doc_string, Builder::new().suffix(".json"))
+    let edited = edit_with_builder(text, Builder::new().suffix(suffix))
         .map_err(|e| EditDocError::EditString(e.to_string()))?;
 
     // setup terminal for returning to app
@@ -81,9 +151,23 @@ pub fn edit_doc(doc: Document) -> Result<Document, EditDocError> {
         .execute(EnterAlternateScreen)
         .map_err(|e| EditDocError::TerminalCommand(e.to_string()))?;
 
-    // convert back into a doc
-    let new_doc_json = from_str::<Value>(&updated_string)
-        .map_err(|e| EditDocError::StringToJson(e.to_string()))?;
+    Ok(edited)
+}
 
-    to_document(&new_doc_json).map_err(|e| EditDocError::JsonToDoc(e.to_string()))
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::bson::doc;
+
+    #[test]
+    fn parse_json5_query() {
+        let text = "{\n  // only recent ones\n  year: { $gte: 2020 },\n  'a.b': 'x',\n}";
+        assert_eq!(
+            parse_query_doc(text).expect("should parse"),
+            doc! { "year": { "$gte": 2020_i64 }, "a.b": "x" }
+        );
+
+        assert!(parse_query_doc("[1, 2]").is_err());
+        assert!(parse_query_doc("{ a: ").is_err());
+    }
 }
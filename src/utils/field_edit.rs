@@ -0,0 +1,298 @@
+use super::mongo_tree::MongoKey;
+use anyhow::{anyhow, bail, Context, Result};
+use itertools::Itertools;
+use mongodb::bson::{doc, oid::ObjectId, Bson, DateTime, Decimal128, Document};
+
+/// Builds the dotted path used in update operators like `$set`, eg. `a.b.0`,
+/// from a path of keys in a document.
+#[must_use]
+pub fn dotted_path(path: &[MongoKey]) -> String {
+    path.iter().map(MongoKey::to_string).join(".")
+}
+
+/// Builds the dotted path for an update, like `dotted_path`, but only if it
+/// points at the same field.
+///
+/// # Errors
+/// If a field name has a `.` in it or starts with `$`, since the path would
+/// point at a nested field instead (or be refused by the server).
+pub fn update_path(path: &[MongoKey]) -> Result<String> {
+    for key in path {
+        if let MongoKey::String(name) = key {
+            if name.contains('.') || name.starts_with('$') {
+                bail!("Can't edit \"{name}\", since its name has a `.` or starts with `$`");
+            }
+        }
+    }
+    Ok(dotted_path(path))
+}
+
+/// Formats a value so that it can be edited as text and then parsed back
+/// into a value of the same type with `parse_value`.
+#[must_use]
+pub fn value_to_text(value: &Bson) -> String {
+    match value {
+        Bson::String(s) => s.clone(),
+        Bson::ObjectId(oid) => format!("ObjectId(\"{oid}\")"),
+        Bson::DateTime(date) => format!(
+            "ISODate(\"{}\")",
+            date.try_to_rfc3339_string().unwrap_or_default()
+        ),
+        Bson::Int64(n) => format!("NumberLong({n})"),
+        Bson::Decimal128(n) => format!("NumberDecimal(\"{n}\")"),
+        value => value.clone().into_relaxed_extjson().to_string(),
+    }
+}
+
+/// Parses text entered by the user into a value.
+///
+/// Values are parsed as JSON5 (with Extended JSON types), or with a
+/// shell-style helper like `ObjectId("...")`, `ISODate("...")`,
+/// `NumberInt(1)`, `NumberLong(1)`, or `NumberDecimal("1.5")`.
+///
+/// If the value replaces an existing one, the parsed value is converted to
+/// the existing value's type when that can be done without losing anything,
+/// so that eg. editing an `Int64` or a string doesn't change its type.
+///
+/// # Errors
+/// If the text can't be parsed as a value.
+pub fn parse_value(text: &str, original: Option<&Bson>) -> Result<Bson> {
+    let text = text.trim();
+
+    if let Some(value) = parse_shell_helper(text)? {
+        return Ok(value);
+    }
+
+    let parsed = json5::from_str::<serde_json::Value>(text)
+        .context("Could not parse value")
+        .and_then(|json| Bson::try_from(json).context("Could not parse value"));
+
+    match (parsed, original) {
+        (Ok(parsed), Some(original)) => Ok(keep_type(parsed, original, text)),
+        (Ok(parsed), None) => Ok(parsed),
+
+        // let strings be entered without quotes
+        (Err(_), Some(Bson::String(_))) => Ok(Bson::String(text.to_string())),
+        (Err(err), _) => Err(err),
+    }
+}
+
+/// Parses a new field entered as `name: value`, eg. `count: 5`.
+///
+/// # Errors
+/// If the text isn't in the form `name: value`.
+pub fn parse_field(text: &str) -> Result<(String, Bson)> {
+    let (name, value) = text
+        .split_once(':')
+        .ok_or_else(|| anyhow!("Enter the new field as `name: value`"))?;
+
+    let name = name.trim().trim_matches(|c| c == '"' || c == '\'');
+    if name.is_empty() {
+        bail!("The new field needs a name");
+    }
+
+    Ok((name.to_string(), parse_value(value, None)?))
+}
+
+fn parse_shell_helper(text: &str) -> Result<Option<Bson>> {
+    let Some((name, arg)) = text.strip_suffix(')').and_then(|text| text.split_once('(')) else {
+        return Ok(None);
+    };
+    let arg = arg.trim().trim_matches(|c| c == '"' || c == '\'');
+
+    let value = match name.trim() {
+        "ObjectId" => Bson::ObjectId(ObjectId::parse_str(arg).context("Invalid ObjectId")?),
+        "ISODate" | "Date" => {
+            Bson::DateTime(DateTime::parse_rfc3339_str(arg).context("Invalid date")?)
+        }
+        "NumberInt" => Bson::Int32(arg.parse().context("Invalid NumberInt")?),
+        "NumberLong" => Bson::Int64(arg.parse().context("Invalid NumberLong")?),
+        "NumberDecimal" => Bson::Decimal128(
+            arg.parse::<Decimal128>()
+                .map_err(|_| anyhow!("Invalid NumberDecimal"))?,
+        ),
+        _ => return Ok(None),
+    };
+
+    Ok(Some(value))
+}
+
+#[expect(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+fn keep_type(parsed: Bson, original: &Bson, text: &str) -> Bson {
+    let as_integer = match &parsed {
+        Bson::Int32(n) => Some(i64::from(*n)),
+        Bson::Int64(n) => Some(*n),
+        Bson::Double(n) if n.fract() == 0.0 => Some(*n as i64),
+        _ => None,
+    };
+    let as_double = match &parsed {
+        Bson::Int32(n) => Some(f64::from(*n)),
+        Bson::Int64(n) => Some(*n as f64),
+        Bson::Double(n) => Some(*n),
+        _ => None,
+    };
+
+    match (original, &parsed) {
+        (Bson::Int32(_), _) => as_integer
+            .and_then(|n| i32::try_from(n).ok())
+            .map_or(parsed, Bson::Int32),
+        (Bson::Int64(_), _) => as_integer.map_or(parsed, Bson::Int64),
+        (Bson::Double(_), _) => as_double.map_or(parsed, Bson::Double),
+        (Bson::Decimal128(_), Bson::Int32(_) | Bson::Int64(_) | Bson::Double(_)) => {
+            text.parse::<Decimal128>().map_or(parsed, Bson::Decimal128)
+        }
+        (
+            Bson::String(_),
+            Bson::Int32(_) | Bson::Int64(_) | Bson::Double(_) | Bson::Boolean(_) | Bson::Null,
+        ) => Bson::String(text.to_string()),
+        (Bson::ObjectId(_), Bson::String(s)) => {
+            ObjectId::parse_str(s).map_or(parsed, Bson::ObjectId)
+        }
+        (Bson::DateTime(_), Bson::String(s)) => {
+            DateTime::parse_rfc3339_str(s).map_or(parsed, Bson::DateTime)
+        }
+        _ => parsed,
+    }
+}
+
+/// Builds a pipeline update that removes the element at an index of an array.
+///
+/// Mongo has no update operator for removing an element by its index, so the
+/// array is rebuilt on the server from the elements before and after it. The
+/// path can only go through subdocuments, since a pipeline can't get at an
+/// array element by its index.
+#[must_use]
+pub fn remove_element_update(array_path: &str, idx: usize) -> Vec<Document> {
+    let array = format!("${array_path}");
+    #[expect(clippy::cast_possible_wrap)]
+    let idx = idx as i64;
+    vec![doc! {
+        "$set": {
+            array_path: {
+                "$concatArrays": [
+                    { "$slice": [&array, idx] },
+                    // `$slice` needs a count, so it's one that's big enough
+                    { "$slice": [&array, idx + 1, i32::MAX] },
+                ]
+            }
+        }
+    }]
+}
+
+#[expect(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::bson::bson;
+
+    #[test]
+    fn parse_values_keeping_type() {
+        assert_eq!(
+            parse_value("5", Some(&Bson::Int64(1))).unwrap(),
+            Bson::Int64(5)
+        );
+        assert_eq!(
+            parse_value("5", Some(&Bson::Double(1.5))).unwrap(),
+            Bson::Double(5.0)
+        );
+        assert_eq!(
+            parse_value("5.5", Some(&Bson::Int32(1))).unwrap(),
+            Bson::Double(5.5)
+        );
+        assert_eq!(
+            parse_value("123", Some(&bson!("abc"))).unwrap(),
+            Bson::String("123".into())
+        );
+        assert_eq!(
+            parse_value("hello there", Some(&bson!("abc"))).unwrap(),
+            Bson::String("hello there".into())
+        );
+        assert_eq!(
+            parse_value("{ a: 1 }", Some(&bson!("abc"))).unwrap(),
+            bson!({ "a": 1 })
+        );
+        assert!(parse_value("hello there", Some(&Bson::Int32(1))).is_err());
+    }
+
+    #[test]
+    fn parse_shell_helpers() {
+        let oid = ObjectId::new();
+        assert_eq!(
+            parse_value(&format!("ObjectId('{oid}')"), None).unwrap(),
+            Bson::ObjectId(oid)
+        );
+        assert_eq!(parse_value("NumberInt(3)", None).unwrap(), Bson::Int32(3));
+        assert_eq!(
+            parse_value("ISODate(\"2024-01-02T03:04:05Z\")", None).unwrap(),
+            Bson::DateTime(DateTime::parse_rfc3339_str("2024-01-02T03:04:05Z").unwrap())
+        );
+        assert!(parse_value("ObjectId(\"nope\")", None).is_err());
+    }
+
+    #[test]
+    fn values_round_trip_through_text() {
+        let values = [
+            Bson::ObjectId(ObjectId::new()),
+            Bson::Int64(7),
+            Bson::Int32(7),
+            Bson::Double(7.5),
+            Bson::String("a string".into()),
+            Bson::Boolean(true),
+            bson!([1, "two"]),
+        ];
+
+        for value in values {
+            let text = value_to_text(&value);
+            assert_eq!(parse_value(&text, Some(&value)).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn parse_new_field() {
+        assert_eq!(
+            parse_field("count: 5").unwrap(),
+            ("count".to_string(), Bson::Int32(5))
+        );
+        assert!(parse_field("count").is_err());
+    }
+
+    #[test]
+    fn refuse_ambiguous_update_paths() {
+        let path = |keys: &[&str]| {
+            keys.iter()
+                .map(|key| MongoKey::String((*key).to_string()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(update_path(&path(&["a", "b"])).unwrap(), "a.b");
+        assert_eq!(
+            update_path(&[MongoKey::String("a".into()), MongoKey::Usize(2)]).unwrap(),
+            "a.2"
+        );
+        assert!(update_path(&path(&["a", "b.c"])).is_err());
+        assert!(update_path(&path(&["$price"])).is_err());
+    }
+
+    #[test]
+    fn remove_array_element() {
+        let update = remove_element_update("a.b", 1);
+        assert_eq!(
+            update,
+            vec![doc! {
+                "$set": {
+                    "a.b": {
+                        "$concatArrays": [
+                            { "$slice": ["$a.b", 1_i64] },
+                            { "$slice": ["$a.b", 2_i64, i32::MAX] },
+                        ]
+                    }
+                }
+            }]
+        );
+
+        // the update only says which index to drop, so it can't touch anything
+        // else in the array (like a `null` that's already there)
+        let text = update[0].to_string();
+        assert!(!text.contains("$pull") && !text.contains("null"));
+    }
+}
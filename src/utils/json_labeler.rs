@@ -1,3 +1,4 @@
+use crate::config::color_map::ColorKey;
 use anyhow::Result;
 use itertools::Itertools;
 use std::str::FromStr;
@@ -21,6 +22,23 @@ pub enum JsonLabel {
     Error,
 }
 
+impl JsonLabel {
+    /// The color used to highlight text with this label, if any.
+    #[must_use]
+    pub const fn color_key(&self) -> Option<ColorKey> {
+        match self {
+            Self::Punctuation => Some(ColorKey::Punctuation),
+            Self::Number => Some(ColorKey::Number),
+            Self::Key => Some(ColorKey::Key),
+            Self::Value => Some(ColorKey::String),
+            Self::Constant => Some(ColorKey::Boolean),
+            Self::DollarSignKey => Some(ColorKey::MongoOperator),
+            Self::Error => Some(ColorKey::FgPrimary),
+            Self::Whitespace => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct JsonLabeler {
     syntax_set: SyntaxSet,
@@ -93,8 +111,31 @@ impl JsonLabeler {
     /// If something goes wrong while parsing the line.
     pub fn label_line(&self, line: &str) -> Result<JsonLabels> {
         let mut parse_state = ParseState::new(&self.syntax);
-        let ops = parse_state.parse_line(line, &self.syntax_set)?;
         let mut stack = ScopeStack::new();
+        self.label_line_with_state(line, &mut parse_state, &mut stack)
+    }
+
+    /// Labels each line of a multi-line JSON string, such as a pretty-printed
+    /// document. Unlike `label_line`, the parser's state is carried from one
+    /// line to the next.
+    ///
+    /// # Errors
+    /// If something goes wrong while parsing one of the lines.
+    pub fn label_lines(&self, text: &str) -> Result<Vec<JsonLabels>> {
+        let mut parse_state = ParseState::new(&self.syntax);
+        let mut stack = ScopeStack::new();
+        text.lines()
+            .map(|line| self.label_line_with_state(line, &mut parse_state, &mut stack))
+            .collect()
+    }
+
+    fn label_line_with_state(
+        &self,
+        line: &str,
+        parse_state: &mut ParseState,
+        stack: &mut ScopeStack,
+    ) -> Result<JsonLabels> {
+        let ops = parse_state.parse_line(line, &self.syntax_set)?;
 
         let mut labeled_substrings = vec![];
 
@@ -254,4 +295,27 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn parse_multiple_lines() {
+        let parser = JsonLabeler::new();
+        let json = "{\n  \"key\": 1\n}";
+        let parsed = parser.label_lines(json).unwrap();
+
+        assert_eq!(
+            parsed,
+            vec![
+                vec![("{".to_string(), JsonLabel::Punctuation)],
+                vec![
+                    ("  ".to_string(), JsonLabel::Whitespace),
+                    ("\"".to_string(), JsonLabel::Punctuation),
+                    ("key".to_string(), JsonLabel::Key),
+                    ("\":".to_string(), JsonLabel::Punctuation),
+                    (" ".to_string(), JsonLabel::Whitespace),
+                    ("1".to_string(), JsonLabel::Number),
+                ],
+                vec![("}".to_string(), JsonLabel::Punctuation)],
+            ]
+        );
+    }
 }
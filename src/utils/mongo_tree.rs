@@ -1,7 +1,7 @@
 use mongodb::bson::{oid::ObjectId, Bson, Document};
 use ratatui::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::fmt::Display;
+use std::{collections::HashMap, fmt::Display};
 use tui_tree_widget::TreeItem;
 
 use crate::config::{color_map::ColorKey, Config};
@@ -73,6 +73,18 @@ impl Display for MongoKey {
     }
 }
 
+/// The characters of a tree item's key and value that matched a search, as
+/// character indices.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Highlight {
+    pub key: Vec<usize>,
+    pub value: Vec<usize>,
+}
+
+/// Highlights for tree items, by the path to the item (starting with the id of
+/// its document).
+pub type Highlights = HashMap<Vec<MongoKey>, Highlight>;
+
 #[derive(Debug, Default, Clone)]
 pub struct MongoTreeBuilder<'a> {
     config: Config,
@@ -92,118 +104,214 @@ impl<'a> MongoTreeBuilder<'a> {
     /// If the passed-in document does not uphold the Mongo invariant of every doc
     /// having an `_id` field.
     #[must_use]
-    pub fn build_tree_item(&self, doc: &Document) -> TreeItem<'a, MongoKey> {
+    pub fn build_tree_item(
+        &self,
+        doc: &Document,
+        highlights: &Highlights,
+    ) -> TreeItem<'a, MongoKey> {
         let id = doc
             .get("_id")
             .expect("all mongo documents should have an '_id' field");
         let id = MongoKey::from(id);
+        let path = vec![id.clone()];
+
+        let mut text = vec![Span::from("[")];
+        text.extend(self.highlighted_spans(
+            id.to_string(),
+            Style::default(),
+            highlights.get(&path).map(|h| h.key.as_slice()),
+        ));
+        text.push(Span::from("]"));
+        let text = Line::from(text).fg(self.config.color_map.get(&ColorKey::FgPrimary));
 
-        let text = Span::styled(
-            format!("[{id}]"),
-            Style::default().fg(self.config.color_map.get(&ColorKey::FgPrimary)),
-        );
-        TreeItem::new(id, text, self.build_doc_children(doc)).expect("document keys are unique")
+        TreeItem::new(id, text, self.build_doc_children(doc, &path, highlights))
+            .expect("document keys are unique")
     }
 
-    fn build_doc_children(&self, doc: &Document) -> Vec<TreeItem<'a, MongoKey>> {
+    fn build_doc_children(
+        &self,
+        doc: &Document,
+        path: &[MongoKey],
+        highlights: &Highlights,
+    ) -> Vec<TreeItem<'a, MongoKey>> {
         doc.iter()
-            .map(|(key, value)| self.bson_to_tree_item(value, MongoKey::String(key.clone())))
+            .map(|(key, value)| {
+                self.bson_to_tree_item(value, MongoKey::String(key.clone()), path, highlights)
+            })
             .collect()
     }
 
-    fn build_document(&self, doc: &Document, key: MongoKey) -> TreeItem<'a, MongoKey> {
-        let text = Line::from(vec![
-            Span::styled(
-                key.to_string(),
-                Style::default().fg(self.config.color_map.get(&ColorKey::Key)),
-            ),
-            Span::from(" "),
-        ]);
-        TreeItem::new(key, text, self.build_doc_children(doc)).expect("document keys are unique")
+    fn build_document(
+        &self,
+        doc: &Document,
+        key: MongoKey,
+        path: &[MongoKey],
+        highlights: &Highlights,
+    ) -> TreeItem<'a, MongoKey> {
+        let mut text = self.key_to_spans(&key, highlights.get(path));
+        text.push(Span::from(" "));
+
+        TreeItem::new(
+            key,
+            Line::from(text),
+            self.build_doc_children(doc, path, highlights),
+        )
+        .expect("document keys are unique")
     }
 
-    fn build_array(&self, arr: &[Bson], key: MongoKey) -> TreeItem<'a, MongoKey> {
+    fn build_array(
+        &self,
+        arr: &[Bson],
+        key: MongoKey,
+        path: &[MongoKey],
+        highlights: &Highlights,
+    ) -> TreeItem<'a, MongoKey> {
         let elements: Vec<TreeItem<'_, _>> = arr
             .iter()
             .enumerate()
-            .map(|(index, value)| self.bson_to_tree_item(value, MongoKey::Usize(index)))
+            .map(|(index, value)| {
+                self.bson_to_tree_item(value, MongoKey::Usize(index), path, highlights)
+            })
             .collect();
 
-        let text = Line::from(vec![
-            Span::styled(
-                key.to_string(),
-                Style::default().fg(self.config.color_map.get(&ColorKey::Key)),
-            ),
+        let mut text = self.key_to_spans(&key, highlights.get(path));
+        text.extend([
             Span::from(" "),
             Span::styled(
                 format!("({} elements)", elements.len()),
                 Style::default().fg(self.config.color_map.get(&ColorKey::DocumentsNote)),
             ),
         ]);
-        TreeItem::new(key, text, elements).expect("document keys are unique")
+        TreeItem::new(key, Line::from(text), elements).expect("document keys are unique")
     }
 
-    fn bson_to_tree_item(&self, bson: &Bson, key: MongoKey) -> TreeItem<'a, MongoKey> {
+    fn bson_to_tree_item(
+        &self,
+        bson: &Bson,
+        key: MongoKey,
+        parent_path: &[MongoKey],
+        highlights: &Highlights,
+    ) -> TreeItem<'a, MongoKey> {
+        let mut path = parent_path.to_vec();
+        path.push(key.clone());
+
         match bson {
-            Bson::Document(doc) => self.build_document(doc, key),
-            Bson::Array(arr) => self.build_array(arr, key),
+            Bson::Document(doc) => self.build_document(doc, key, &path, highlights),
+            Bson::Array(arr) => self.build_array(arr, key, &path, highlights),
             bson => {
-                let text = Line::from(vec![
-                    self.key_to_span(&key),
-                    Span::styled(
-                        ": ",
-                        Style::default().fg(self.config.color_map.get(&ColorKey::Punctuation)),
-                    ),
-                    self.value_to_span(bson),
-                ]);
-                TreeItem::new_leaf(key, text)
+                let highlight = highlights.get(&path);
+                let mut text = self.key_to_spans(&key, highlight);
+                text.push(Span::styled(
+                    ": ",
+                    Style::default().fg(self.config.color_map.get(&ColorKey::Punctuation)),
+                ));
+                text.extend(self.value_to_spans(bson, highlight));
+                TreeItem::new_leaf(key, Line::from(text))
             }
         }
     }
 
-    fn key_to_span<'b>(&self, key: &MongoKey) -> Span<'b> {
-        let string = match key {
-            MongoKey::String(s) => s.clone(),
-            MongoKey::ObjectId(id) => format!("ObjectId({id})"),
-            MongoKey::Usize(n) => format!("{n}"),
-        };
-        Span::styled(
-            string,
+    fn key_to_spans<'b>(&self, key: &MongoKey, highlight: Option<&Highlight>) -> Vec<Span<'b>> {
+        self.highlighted_spans(
+            key.to_string(),
             Style::default().fg(self.config.color_map.get(&ColorKey::Key)),
+            highlight.map(|h| h.key.as_slice()),
         )
     }
 
-    fn value_to_span<'b>(&self, bson: &Bson) -> Span<'b> {
-        let string = match bson {
-            Bson::ObjectId(id) => format!("ObjectId({id})"),
-            Bson::String(s) => format!("\"{s}\""),
-            Bson::Boolean(b) => b.to_string(),
-
-            Bson::Double(n) => n.to_string(),
-            Bson::Decimal128(n) => n.to_string(),
-            Bson::Int32(n) => n.to_string(),
-            Bson::Int64(n) => n.to_string(),
+    fn value_to_spans<'b>(&self, bson: &Bson, highlight: Option<&Highlight>) -> Vec<Span<'b>> {
+        let color = self.config.color_map.get(&value_color_key(bson));
+        let style = Style::default().fg(color);
+        let indices = highlight.map(|h| h.value.as_slice());
 
-            Bson::Null => "null".to_string(),
-            Bson::Undefined => "undefined".to_string(),
+        // strings are shown in quotes, which aren't part of what gets searched
+        if let Bson::String(s) = bson {
+            let mut spans = vec![Span::styled("\"", style)];
+            spans.extend(self.highlighted_spans(s.clone(), style, indices));
+            spans.push(Span::styled("\"", style));
+            return spans;
+        }
 
-            Bson::Timestamp(t) => t.to_string(),
-            Bson::DateTime(d) => d.to_string(),
+        self.highlighted_spans(value_to_string(bson), style, indices)
+    }
 
-            other => format!("{other:?}"),
+    /// Splits some text into spans so that the characters at the given
+    /// indices are shown in the search highlight style.
+    fn highlighted_spans<'b>(
+        &self,
+        text: String,
+        style: Style,
+        indices: Option<&[usize]>,
+    ) -> Vec<Span<'b>> {
+        let Some(indices) = indices.filter(|indices| !indices.is_empty()) else {
+            return vec![Span::styled(text, style)];
         };
+        let highlight_style = style
+            .fg(self.config.color_map.get(&ColorKey::DocumentsSearch))
+            .bold()
+            .underlined();
 
-        let color = match bson {
-            Bson::ObjectId(_) => self.config.color_map.get(&ColorKey::ObjectId),
-            Bson::String(_) => self.config.color_map.get(&ColorKey::String),
-            Bson::Boolean(_) => self.config.color_map.get(&ColorKey::Boolean),
-            Bson::Double(_) | Bson::Decimal128(_) | Bson::Int32(_) | Bson::Int64(_) => {
-                self.config.color_map.get(&ColorKey::Number)
+        let mut spans = vec![];
+        let mut current = String::new();
+        let mut current_is_match = false;
+        for (idx, c) in text.chars().enumerate() {
+            let is_match = indices.contains(&idx);
+            if is_match != current_is_match && !current.is_empty() {
+                let span_style = if current_is_match {
+                    highlight_style
+                } else {
+                    style
+                };
+                spans.push(Span::styled(std::mem::take(&mut current), span_style));
             }
-            Bson::Timestamp(_) | Bson::DateTime(_) => self.config.color_map.get(&ColorKey::Date),
-            _ => self.config.color_map.get(&ColorKey::DocumentsNote),
-        };
+            current_is_match = is_match;
+            current.push(c);
+        }
+        if !current.is_empty() {
+            let span_style = if current_is_match {
+                highlight_style
+            } else {
+                style
+            };
+            spans.push(Span::styled(current, span_style));
+        }
+        spans
+    }
+}
+
+/// The text that the tree shows for a value that isn't a document or an array.
+/// Strings aren't quoted.
+#[must_use]
+pub fn value_to_string(bson: &Bson) -> String {
+    match bson {
+        Bson::ObjectId(id) => format!("ObjectId({id})"),
+        Bson::String(s) => s.clone(),
+        Bson::Boolean(b) => b.to_string(),
+
+        Bson::Double(n) => n.to_string(),
+        Bson::Decimal128(n) => n.to_string(),
+        Bson::Int32(n) => n.to_string(),
+        Bson::Int64(n) => n.to_string(),
+
+        Bson::Null => "null".to_string(),
+        Bson::Undefined => "undefined".to_string(),
+
+        Bson::Timestamp(t) => t.to_string(),
+        Bson::DateTime(d) => d.to_string(),
+
+        other => format!("{other:?}"),
+    }
+}
 
-        Span::styled(string, Style::default().fg(color))
+/// The color used to display a value, based on its type.
+#[must_use]
+pub const fn value_color_key(bson: &Bson) -> ColorKey {
+    match bson {
+        Bson::ObjectId(_) => ColorKey::ObjectId,
+        Bson::String(_) => ColorKey::String,
+        Bson::Boolean(_) => ColorKey::Boolean,
+        Bson::Double(_) | Bson::Decimal128(_) | Bson::Int32(_) | Bson::Int64(_) => ColorKey::Number,
+        Bson::Timestamp(_) | Bson::DateTime(_) => ColorKey::Date,
+        _ => ColorKey::DocumentsNote,
     }
 }